@@ -0,0 +1,116 @@
+//! This module covers the guidance from an API point of view.
+use anyhow::Result;
+use rusqlite::Transaction;
+use serde::Serialize;
+
+use super::{json_page, Organisation, StandardReference};
+use crate::cache::{GuidanceRecord, GuidanceStandardRecord, OrganisationRecord, StandardRecord};
+use crate::integrity;
+use crate::markdown;
+use crate::sink::Page;
+
+/// An entry of `guidance.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct GuidanceSummary {
+    pub id: String,
+    pub title: String,
+    pub status: String,
+    pub path: String,
+}
+
+/// The document of a piece of guidance, `guidance/{id}.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Guidance {
+    pub id: String,
+    pub title: String,
+    pub description: Option<String>,
+    /// One of `draft`, `published` or `obsolete`.
+    pub status: String,
+    pub creation_date: String,
+    pub update_date: String,
+    pub publication_date: Option<String>,
+    /// The URL to the publication in GOV.UK.
+    pub canonical_url: Option<String>,
+    pub maintainer: Organisation,
+    pub standards: Vec<StandardReference>,
+    /// The body of the guidance as Markdown, without its title.
+    pub content: String,
+}
+
+fn path(id: &str) -> String {
+    format!("guidance/{}.json", id)
+}
+
+/// Renders `guidance.json` and `guidance/{id}.json`.
+pub(super) fn render(tx: &Transaction) -> Result<Vec<Page>> {
+    let guidance = get_all(tx)?;
+    let summaries: Vec<GuidanceSummary> = guidance
+        .iter()
+        .map(|guidance| GuidanceSummary {
+            id: guidance.id.clone(),
+            title: guidance.title.clone(),
+            status: guidance.status.clone(),
+            path: path(&guidance.id),
+        })
+        .collect();
+    let mut pages = vec![json_page("guidance.json".into(), &summaries)?];
+
+    for guidance in &guidance {
+        pages.push(json_page(path(&guidance.id), guidance)?);
+    }
+
+    Ok(pages)
+}
+
+/// Collects every piece of guidance, sorted by identifier.
+pub(super) fn get_all(tx: &Transaction) -> Result<Vec<Guidance>> {
+    let mut records = GuidanceRecord::select_all(tx)?;
+    let mut guidance = Vec::new();
+
+    records.sort_by(|a, b| a.id.cmp(&b.id));
+
+    for record in records {
+        guidance.extend(into_resource(tx, record)?);
+    }
+
+    Ok(guidance)
+}
+
+fn into_resource(tx: &Transaction, record: GuidanceRecord) -> Result<Option<Guidance>> {
+    let (title, content) = markdown::split_title(&record.content)?;
+    let maintainer = match OrganisationRecord::select(tx, &record.maintainer_id)? {
+        Some(maintainer) => maintainer,
+        None => {
+            integrity::skip_unknown_organisation(
+                tx,
+                "guidance",
+                &record.id,
+                "maintainer",
+                &record.maintainer_id,
+            )?;
+
+            return Ok(None);
+        }
+    };
+    let mut standards = Vec::new();
+
+    for related in GuidanceStandardRecord::select(tx, &record.id)? {
+        if let Some(standard) = StandardRecord::select(tx, &related.standard_id)? {
+            standards.push(StandardReference::new(&standard.id, &standard.name));
+        }
+    }
+
+    Ok(Some(Guidance {
+        id: record.id,
+        title,
+        description: record.description,
+        status: record.status.to_string(),
+        creation_date: record.creation_date,
+        update_date: record.update_date,
+        publication_date: record.publication_date,
+        canonical_url: record.canonical_url,
+        maintainer: maintainer.into(),
+        standards,
+        content,
+    }))
+}
@@ -0,0 +1,238 @@
+//! This module renders the catalogue as a static JSON API.
+//!
+//! Every reference to another resource is resolved (e.g. a standard carries the name and URL of its maintainer, not
+//! just its identifier) and optional values are always present as `null` so consumers can rely on the shape of each
+//! document. The schema is described in `hammer/API.md` and versioned with [`VERSION`]: adding a field keeps the
+//! version, renaming, removing or changing the type of one bumps it.
+
+mod guidance;
+mod reference;
+mod standard;
+mod taxonomy;
+
+pub use guidance::{Guidance, GuidanceSummary};
+pub use reference::{Licence, Organisation};
+pub use standard::{Standard, StandardReference, StandardSummary};
+pub use taxonomy::{Theme, ThemeReference, Topic, TopicReference};
+
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::cache::Cache;
+use crate::sink::{self, Page, Summary};
+
+/// The version of the API schema.
+pub const VERSION: u32 = 1;
+
+/// The entry point of the API, `index.json`.
+#[derive(Debug, Clone, Serialize)]
+struct Index {
+    version: u32,
+    standards: &'static str,
+    themes: &'static str,
+    guidance: &'static str,
+    organisations: &'static str,
+    licences: &'static str,
+}
+
+impl Default for Index {
+    fn default() -> Self {
+        Index {
+            version: VERSION,
+            standards: "standards.json",
+            themes: "themes.json",
+            guidance: "guidance.json",
+            organisations: "organisations.json",
+            licences: "licences.json",
+        }
+    }
+}
+
+/// Writes the API documents that differ from the ones in the sink and removes any file no longer rendered.
+pub fn write(sink_dir: &Path, cache: &mut Cache) -> Result<Summary> {
+    let pages = render(cache)?;
+
+    sink::write(sink_dir, &pages)
+}
+
+/// Renders every API document from the cache.
+pub fn render(cache: &mut Cache) -> Result<Vec<Page>> {
+    let tx = cache.transaction()?;
+    let mut pages = vec![json_page("index.json".into(), &Index::default())?];
+
+    pages.extend(standard::render(&tx)?);
+    pages.extend(taxonomy::render(&tx)?);
+    pages.extend(guidance::render(&tx)?);
+    pages.extend(reference::render(&tx)?);
+
+    tx.commit()?;
+
+    Ok(pages)
+}
+
+/// Collects every standard with its references resolved, sorted by identifier.
+pub fn standards(cache: &mut Cache) -> Result<Vec<Standard>> {
+    let tx = cache.transaction()?;
+    let result = standard::get_all(&tx)?;
+
+    tx.commit()?;
+
+    Ok(result)
+}
+
+/// Collects every piece of guidance with its references resolved, sorted by identifier.
+pub fn guidance(cache: &mut Cache) -> Result<Vec<Guidance>> {
+    let tx = cache.transaction()?;
+    let result = guidance::get_all(&tx)?;
+
+    tx.commit()?;
+
+    Ok(result)
+}
+
+/// Collects every theme with its topics, sorted by ordinal.
+pub fn themes(cache: &mut Cache) -> Result<Vec<Theme>> {
+    let tx = cache.transaction()?;
+    let result = taxonomy::get_themes(&tx)?;
+
+    tx.commit()?;
+
+    Ok(result)
+}
+
+/// Collects every topic with its standards, sorted by identifier.
+pub fn topics(cache: &mut Cache) -> Result<Vec<Topic>> {
+    let tx = cache.transaction()?;
+    let result = taxonomy::get_topics(&tx)?;
+
+    tx.commit()?;
+
+    Ok(result)
+}
+
+pub fn organisations(cache: &mut Cache) -> Result<Vec<Organisation>> {
+    let tx = cache.transaction()?;
+    let result = reference::get_organisations(&tx)?;
+
+    tx.commit()?;
+
+    Ok(result)
+}
+
+pub fn licences(cache: &mut Cache) -> Result<Vec<Licence>> {
+    let tx = cache.transaction()?;
+    let result = reference::get_licences(&tx)?;
+
+    tx.commit()?;
+
+    Ok(result)
+}
+
+fn json_page<T: Serialize>(path: String, value: &T) -> Result<Page> {
+    let mut content = serde_json::to_string_pretty(value)?;
+    content.push('\n');
+
+    Ok(Page::new(PathBuf::from(path), content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source;
+    use std::str::FromStr;
+
+    #[test]
+    fn catalogue() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&source::Organisation::from_str(
+            r#"{"id": "geoplace", "name": "GeoPlace", "url": "https://www.geoplace.co.uk/"}"#,
+        )?)?;
+        cache.add(&source::Licence::from_str(
+            r#"{"id": "ogl-3", "name": "Open Government Licence", "acronym": "OGL", "url": "https://ogl.gov.uk"}"#,
+        )?)?;
+        cache.add(&source::Theme::from_str(
+            "---\ntype: theme\nidentifier: reference-data\nname: Reference Data\nordinal: 1\n---\nShared codes.",
+        )?)?;
+        cache.add(&source::Topic::from_str(
+            "---\ntype: topic\nidentifier: geospatial\nname: Geospatial\ntheme: reference-data\nordinal: 1\n---\nPlaces.",
+        )?)?;
+        cache.add(&source::Standard::from_str(
+            r#"---
+type: standard
+identifier: uprn
+name: Unique Property Reference Number
+acronym: UPRN
+topic: geospatial
+specification: https://www.geoplace.co.uk/addresses/uprn
+licence: ogl-3
+maintainer: geoplace
+related:
+  - usrn
+endorsement_state:
+    status: endorsed
+    start_date: 2020-12-16
+    review_date: 2021-12-16
+---
+The UPRN."#,
+        )?)?;
+        cache.add(&source::Standard::from_str(
+            r#"---
+type: standard
+identifier: usrn
+name: Unique Street Reference Number
+topic: geospatial
+specification: https://www.geoplace.co.uk/addresses/usrn
+maintainer: geoplace
+endorsement_state:
+    status: proposed
+    start_date: 2020-12-16
+    review_date: 2021-12-16
+---
+The USRN."#,
+        )?)?;
+
+        let pages = render(&mut cache)?;
+        let paths: Vec<_> = pages
+            .iter()
+            .map(|page| page.path.display().to_string())
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "index.json",
+                "standards.json",
+                "standards/uprn.json",
+                "standards/usrn.json",
+                "themes.json",
+                "topics/geospatial.json",
+                "guidance.json",
+                "organisations.json",
+                "licences.json",
+            ]
+        );
+
+        let uprn: serde_json::Value = serde_json::from_str(&pages[2].content)?;
+
+        assert_eq!(uprn["maintainer"]["name"], "GeoPlace");
+        assert_eq!(uprn["licence"]["acronym"], "OGL");
+        assert_eq!(uprn["topic"]["theme"]["name"], "Reference Data");
+        assert_eq!(uprn["related"][0]["name"], "Unique Street Reference Number");
+        assert_eq!(uprn["related"][0]["path"], "standards/usrn.json");
+        assert_eq!(
+            uprn["endorsement_state"]["end_date"],
+            serde_json::Value::Null
+        );
+        assert_eq!(uprn["consultation"], serde_json::Value::Null);
+
+        let topic: serde_json::Value = serde_json::from_str(&pages[5].content)?;
+
+        assert_eq!(topic["standards"][1]["id"], "usrn");
+        assert_eq!(topic["standards"][1]["status"], "proposed");
+
+        Ok(())
+    }
+}
@@ -0,0 +1,64 @@
+//! This module covers the auxiliary organisation and licence lists from an API point of view.
+use anyhow::Result;
+use rusqlite::Transaction;
+use serde::Serialize;
+
+use super::json_page;
+use crate::cache::{LicenceRecord, OrganisationRecord};
+use crate::sink::Page;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Organisation {
+    pub id: String,
+    pub name: String,
+    pub url: String,
+}
+
+impl From<OrganisationRecord> for Organisation {
+    fn from(record: OrganisationRecord) -> Self {
+        Organisation {
+            id: record.id,
+            name: record.name,
+            url: record.url,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Licence {
+    pub id: String,
+    pub name: String,
+    pub acronym: Option<String>,
+    pub url: String,
+}
+
+impl From<LicenceRecord> for Licence {
+    fn from(record: LicenceRecord) -> Self {
+        Licence {
+            id: record.id,
+            name: record.name,
+            acronym: record.acronym,
+            url: record.url,
+        }
+    }
+}
+
+/// Renders `organisations.json` and `licences.json`.
+pub(super) fn render(tx: &Transaction) -> Result<Vec<Page>> {
+    Ok(vec![
+        json_page("organisations.json".into(), &get_organisations(tx)?)?,
+        json_page("licences.json".into(), &get_licences(tx)?)?,
+    ])
+}
+
+pub(super) fn get_organisations(tx: &Transaction) -> Result<Vec<Organisation>> {
+    let records = OrganisationRecord::select_all(tx)?;
+
+    Ok(records.into_iter().map(Into::into).collect())
+}
+
+pub(super) fn get_licences(tx: &Transaction) -> Result<Vec<Licence>> {
+    let records = LicenceRecord::select_all(tx)?;
+
+    Ok(records.into_iter().map(Into::into).collect())
+}
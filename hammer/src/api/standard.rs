@@ -0,0 +1,250 @@
+//! This module covers the standard from an API point of view.
+use anyhow::Result;
+use rusqlite::Transaction;
+use serde::Serialize;
+
+use super::taxonomy::{self, TopicReference};
+use super::{json_page, Licence, Organisation};
+use crate::cache::records::*;
+use crate::integrity;
+use crate::sink::Page;
+
+/// A link to the document of a standard.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct StandardReference {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+}
+
+impl StandardReference {
+    pub fn new(id: &str, name: &str) -> Self {
+        StandardReference {
+            id: id.into(),
+            name: name.into(),
+            path: path(id),
+        }
+    }
+}
+
+/// An entry of `standards.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct StandardSummary {
+    pub id: String,
+    pub name: String,
+    pub acronym: Option<String>,
+    /// The current endorsement status.
+    pub status: String,
+    pub topic: Option<TopicReference>,
+    pub maintainer: Organisation,
+    pub path: String,
+}
+
+impl From<&Standard> for StandardSummary {
+    fn from(standard: &Standard) -> Self {
+        StandardSummary {
+            id: standard.id.clone(),
+            name: standard.name.clone(),
+            acronym: standard.acronym.clone(),
+            status: standard.endorsement_state.status.clone(),
+            topic: standard.topic.clone(),
+            maintainer: standard.maintainer.clone(),
+            path: path(&standard.id),
+        }
+    }
+}
+
+/// The document of a standard, `standards/{id}.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Standard {
+    pub id: String,
+    pub name: String,
+    pub acronym: Option<String>,
+    /// The URL to the technical specification.
+    pub specification: String,
+    pub subjects: Vec<String>,
+    /// The identifier of the external standard this standard conforms to.
+    pub conforms_to: Option<String>,
+    pub topic: Option<TopicReference>,
+    pub licence: Option<Licence>,
+    pub maintainer: Organisation,
+    pub related: Vec<StandardReference>,
+    pub endorsement_state: EndorsementState,
+    pub endorsement_history: Vec<EndorsementTransition>,
+    pub dsa_process: Option<DsaProcess>,
+    pub consultation: Option<Consultation>,
+    pub guidance: Vec<ExternalResource>,
+    pub use_cases: Vec<ExternalResource>,
+    pub case_studies: Vec<ExternalResource>,
+    /// The description of the standard as Markdown.
+    pub content: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EndorsementState {
+    pub status: String,
+    pub start_date: String,
+    pub review_date: String,
+    pub end_date: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct EndorsementTransition {
+    pub status: String,
+    pub date: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct DsaProcess {
+    pub stage: String,
+    pub start_date: String,
+    pub update_date: String,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct Consultation {
+    pub title: String,
+    pub url: String,
+    pub start_date: String,
+    pub end_date: Option<String>,
+}
+
+/// Guidance, a use case or a case study about the standard published elsewhere.
+#[derive(Debug, Clone, Serialize)]
+pub struct ExternalResource {
+    pub title: String,
+    pub url: String,
+    /// Missing when the publisher is not a known organisation.
+    pub publisher: Option<Organisation>,
+}
+
+fn path(id: &str) -> String {
+    format!("standards/{}.json", id)
+}
+
+/// Renders `standards.json` and `standards/{id}.json`.
+pub(super) fn render(tx: &Transaction) -> Result<Vec<Page>> {
+    let standards = get_all(tx)?;
+    let summaries: Vec<StandardSummary> = standards.iter().map(Into::into).collect();
+    let mut pages = vec![json_page("standards.json".into(), &summaries)?];
+
+    for standard in &standards {
+        pages.push(json_page(path(&standard.id), standard)?);
+    }
+
+    Ok(pages)
+}
+
+/// Collects every standard, sorted by identifier.
+pub(super) fn get_all(tx: &Transaction) -> Result<Vec<Standard>> {
+    let mut records = StandardRecord::select_all(tx)?;
+    let mut standards = Vec::new();
+
+    records.sort_by(|a, b| a.id.cmp(&b.id));
+
+    for record in records {
+        standards.extend(into_resource(tx, record)?);
+    }
+
+    Ok(standards)
+}
+
+fn into_resource(tx: &Transaction, record: StandardRecord) -> Result<Option<Standard>> {
+    let standard_id = &record.id;
+    let endorsement_record = EndorsementStateRecord::select(tx, standard_id)?
+        .expect("missing endorsement state. the cache is corrupted.");
+    let mut related = Vec::new();
+
+    for related_record in RelatedStandardRecord::select(tx, standard_id)? {
+        if let Some(std_record) = StandardRecord::select(tx, &related_record.related_standard_id)? {
+            related.push(StandardReference::new(&std_record.id, &std_record.name));
+        }
+    }
+
+    let licence = match &record.licence_id {
+        Some(licence_id) => LicenceRecord::select(tx, licence_id)?.map(Into::into),
+        None => None,
+    };
+    let maintainer = match OrganisationRecord::select(tx, &record.maintainer_id)? {
+        Some(maintainer) => maintainer,
+        None => {
+            integrity::skip_unknown_organisation(
+                tx,
+                "standard",
+                &record.id,
+                "maintainer",
+                &record.maintainer_id,
+            )?;
+
+            return Ok(None);
+        }
+    };
+    let endorsement_history = EndorsementTransitionRecord::select(tx, standard_id)?
+        .into_iter()
+        .map(|record| EndorsementTransition {
+            status: record.status,
+            date: record.date,
+        })
+        .collect();
+    let subjects = StandardSubjectRecord::select(tx, standard_id)?
+        .into_iter()
+        .map(|record| record.subject_id)
+        .collect();
+    let dsa_process = DsaProcessRecord::select(tx, standard_id)?.map(|record| DsaProcess {
+        stage: record.stage,
+        start_date: record.start_date,
+        update_date: record.update_date,
+    });
+    let consultation = ConsultationRecord::select(tx, standard_id)?.map(|record| Consultation {
+        title: record.title,
+        url: record.url,
+        start_date: record.start_date,
+        end_date: record.end_date,
+    });
+
+    Ok(Some(Standard {
+        id: record.id.clone(),
+        name: record.name,
+        acronym: record.acronym,
+        specification: record.specification,
+        subjects,
+        conforms_to: record.conforms_to,
+        topic: taxonomy::topic_reference(tx, &record.topic_id)?,
+        licence,
+        maintainer: maintainer.into(),
+        related,
+        endorsement_state: EndorsementState {
+            status: endorsement_record.status,
+            start_date: endorsement_record.start_date,
+            review_date: endorsement_record.review_date,
+            end_date: endorsement_record.end_date,
+        },
+        endorsement_history,
+        dsa_process,
+        consultation,
+        guidance: select_external_resources(tx, standard_id, "guidance")?,
+        use_cases: select_external_resources(tx, standard_id, "use-case")?,
+        case_studies: select_external_resources(tx, standard_id, "case-study")?,
+        content: record.content,
+    }))
+}
+
+fn select_external_resources(
+    tx: &Transaction,
+    standard_id: &str,
+    resource_type: &str,
+) -> Result<Vec<ExternalResource>> {
+    let mut result = Vec::new();
+
+    for record in ExternalResourceRecord::select(tx, standard_id, resource_type)? {
+        let publisher = OrganisationRecord::select(tx, &record.publisher_id)?;
+
+        result.push(ExternalResource {
+            title: record.title,
+            url: record.url,
+            publisher: publisher.map(Into::into),
+        });
+    }
+
+    Ok(result)
+}
@@ -0,0 +1,174 @@
+//! This module covers the themes and topics from an API point of view.
+use anyhow::Result;
+use rusqlite::Transaction;
+use serde::Serialize;
+
+use super::json_page;
+use crate::cache::{ThemeRecord, TopicRecord, TopicStandardRecord};
+use crate::sink::Page;
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ThemeReference {
+    pub id: String,
+    pub name: String,
+}
+
+/// A link to the document of a topic.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct TopicReference {
+    pub id: String,
+    pub name: String,
+    pub theme: ThemeReference,
+    pub path: String,
+}
+
+/// An entry of `themes.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Theme {
+    pub id: String,
+    pub name: String,
+    pub ordinal: u32,
+    /// The description of the theme as Markdown.
+    pub description: String,
+    pub topics: Vec<TopicReference>,
+}
+
+/// A standard classified under a topic.
+#[derive(Debug, Clone, Serialize)]
+pub struct TopicStandard {
+    pub id: String,
+    pub name: String,
+    /// The current endorsement status.
+    pub status: String,
+    pub review_date: String,
+    pub path: String,
+}
+
+/// The document of a topic, `topics/{id}.json`.
+#[derive(Debug, Clone, Serialize)]
+pub struct Topic {
+    pub id: String,
+    pub name: String,
+    pub ordinal: u32,
+    pub theme: ThemeReference,
+    /// The description of the topic as Markdown.
+    pub description: String,
+    pub standards: Vec<TopicStandard>,
+}
+
+fn path(id: &str) -> String {
+    format!("topics/{}.json", id)
+}
+
+/// Resolves the given topic identifier. Dangling topics are reported by the integrity check so they are left out.
+pub(super) fn topic_reference(tx: &Transaction, topic_id: &str) -> Result<Option<TopicReference>> {
+    let record = match TopicRecord::select(tx, topic_id)? {
+        Some(record) => record,
+        None => return Ok(None),
+    };
+    let theme = theme_reference(tx, &record.theme_id)?;
+
+    Ok(Some(TopicReference {
+        path: path(&record.id),
+        id: record.id,
+        name: record.name,
+        theme,
+    }))
+}
+
+fn theme_reference(tx: &Transaction, theme_id: &str) -> Result<ThemeReference> {
+    let name = ThemeRecord::select(tx, theme_id)?
+        .map(|record| record.name)
+        .unwrap_or_else(|| theme_id.into());
+
+    Ok(ThemeReference {
+        id: theme_id.into(),
+        name,
+    })
+}
+
+/// Renders `themes.json` and `topics/{id}.json`.
+pub(super) fn render(tx: &Transaction) -> Result<Vec<Page>> {
+    let mut pages = vec![json_page("themes.json".into(), &get_themes(tx)?)?];
+
+    for topic in get_topics(tx)? {
+        pages.push(json_page(path(&topic.id), &topic)?);
+    }
+
+    Ok(pages)
+}
+
+/// Collects every theme with its topics, both sorted by ordinal.
+pub(super) fn get_themes(tx: &Transaction) -> Result<Vec<Theme>> {
+    let mut theme_records = ThemeRecord::select_all(tx)?;
+    let topic_records = TopicRecord::select_all(tx)?;
+    let mut themes = Vec::new();
+
+    theme_records.sort_by_key(|record| record.ordinal);
+
+    for record in theme_records {
+        let mut topics: Vec<_> = topic_records
+            .iter()
+            .filter(|topic| topic.theme_id == record.id)
+            .collect();
+
+        topics.sort_by_key(|topic| topic.ordinal);
+
+        let theme = ThemeReference {
+            id: record.id.clone(),
+            name: record.name.clone(),
+        };
+
+        themes.push(Theme {
+            id: record.id,
+            name: record.name,
+            ordinal: record.ordinal,
+            description: record.description,
+            topics: topics
+                .iter()
+                .map(|topic| TopicReference {
+                    id: topic.id.clone(),
+                    name: topic.name.clone(),
+                    theme: theme.clone(),
+                    path: path(&topic.id),
+                })
+                .collect(),
+        });
+    }
+
+    Ok(themes)
+}
+
+/// Collects every topic with its standards, sorted by identifier.
+pub(super) fn get_topics(tx: &Transaction) -> Result<Vec<Topic>> {
+    let mut topic_records = TopicRecord::select_all(tx)?;
+    let mut topics = Vec::new();
+
+    topic_records.sort_by(|a, b| a.id.cmp(&b.id));
+
+    for record in topic_records {
+        let mut standards: Vec<TopicStandard> = TopicStandardRecord::select(tx, &record.id)?
+            .into_iter()
+            .map(|standard| TopicStandard {
+                path: format!("standards/{}.json", &standard.id),
+                id: standard.id,
+                name: standard.name,
+                status: standard.status,
+                review_date: standard.review_date,
+            })
+            .collect();
+
+        standards.sort_by(|a, b| a.id.cmp(&b.id));
+
+        topics.push(Topic {
+            theme: theme_reference(tx, &record.theme_id)?,
+            id: record.id,
+            name: record.name,
+            ordinal: record.ordinal,
+            description: record.description,
+            standards,
+        });
+    }
+
+    Ok(topics)
+}
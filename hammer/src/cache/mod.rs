@@ -2,10 +2,13 @@ use anyhow::{self, Result};
 use chrono::{DateTime, Utc};
 pub use rusqlite::Transaction;
 use rusqlite::{self, params, Connection};
+use std::fmt;
 use std::str::FromStr;
 
 pub(crate) mod records;
 pub use records::*;
+pub mod schema;
+pub use schema::Migration;
 mod strategy;
 pub use strategy::Strategy;
 
@@ -18,13 +21,15 @@ pub struct Cache {
     pub conn: Connection,
     pub strategy: Strategy,
     pub report: Report,
+    /// The schema migration applied when connecting.
+    pub migration: Migration,
 }
 
 impl Cache {
     pub fn connect_with_strategy(strategy: Strategy) -> Result<Cache> {
         let mut report = Report::new();
         let timestamp = Utc::now();
-        let conn = match &strategy {
+        let mut conn = match &strategy {
             Strategy::Disk(path) => {
                 let conn = Connection::open(path)?;
                 conn.pragma_update(None, "journal_mode", &"wal")?;
@@ -33,14 +38,14 @@ impl Cache {
             }
             Strategy::Memory => Connection::open_in_memory()?,
         };
-        let bootstrap = include_str!("../sql/cache.sql");
+        let migration = schema::migrate(&mut conn)
+            .map_err(|err| err.context(format!("Failed to open the cache at {}", &strategy)))?;
 
-        conn.execute_batch(&bootstrap)?;
         report.log(
             Action::Chore,
             Entity::Cache,
             &strategy.to_string(),
-            "Cache bootstrap.",
+            &format!("Cache bootstrap: {}.", &migration),
         );
 
         Ok(Cache {
@@ -48,6 +53,7 @@ impl Cache {
             conn,
             strategy,
             report,
+            migration,
         })
     }
     pub fn connect(path: &str) -> Result<Cache> {
@@ -69,19 +75,89 @@ impl Cache {
         &self.report
     }
 
-    pub fn transaction(&mut self) -> Result<Transaction> {
+    pub fn transaction(&mut self) -> Result<Transaction<'_>> {
         let tx = self.conn.transaction()?;
 
         Ok(tx)
     }
 
-    /// Remove all stale records for the given session.
-    pub fn prune(&mut self) -> Result<()> {
+    /// Starts a new session so only the resources added from now on count as seen when pruning.
+    ///
+    /// A long lived cache, e.g. in watch mode, must start a session for every full read of the source.
+    pub fn start_session(&mut self) {
+        self.timestamp = Utc::now();
+    }
+
+    /// Records the source file the given resource was read from.
+    pub fn insert_origin(
+        &mut self,
+        resource_type: &str,
+        resource_id: &str,
+        path: &str,
+    ) -> Result<()> {
+        let tx = self.conn.transaction()?;
+
+        OriginRecord::insert(
+            &tx,
+            &OriginRecord {
+                resource_type: resource_type.into(),
+                resource_id: resource_id.into(),
+                path: path.into(),
+            },
+        )?;
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    /// Detaches the resources read from the given source file, returning them.
+    pub fn take_origins(&mut self, path: &str) -> Result<Vec<OriginRecord>> {
+        let tx = self.conn.transaction()?;
+        let records = OriginRecord::select_by_path(&tx, path)?;
+
+        OriginRecord::delete_by_path(&tx, path)?;
+
+        tx.commit()?;
+
+        Ok(records)
+    }
+
+    pub fn origins(&mut self, path: &str) -> Result<Vec<OriginRecord>> {
         let tx = self.conn.transaction()?;
+        let records = OriginRecord::select_by_path(&tx, path)?;
 
-        Cache::delete_stale_standards(&tx, &timestamp_string(&self.timestamp))?;
+        tx.commit()?;
 
-        &self.report.log(
+        Ok(records)
+    }
+
+    /// Removes every resource not seen in the current session, returning what was removed.
+    ///
+    /// Records depending on a removed resource (e.g. related standards) go with it via `ON DELETE CASCADE`.
+    pub fn prune(&mut self) -> Result<Vec<StaleRecord>> {
+        let tx = self.conn.transaction()?;
+        let timestamp = timestamp_string(&self.timestamp);
+        let mut result = Vec::new();
+
+        for (resource_type, table, entity) in TRAILED_TABLES.iter() {
+            for id in Cache::select_stale(&tx, resource_type, table, &timestamp)? {
+                OriginRecord::delete(&tx, resource_type, &id)?;
+                SearchRecord::delete(&tx, resource_type, &id)?;
+
+                self.report
+                    .log(Action::Prune, entity.clone(), &id, "Stale resource.");
+
+                result.push(StaleRecord {
+                    resource_type: resource_type.to_string(),
+                    id,
+                });
+            }
+
+            Cache::delete_stale(&tx, resource_type, table, &timestamp)?;
+        }
+
+        self.report.log(
             Action::Prune,
             Entity::Cache,
             &self.strategy.to_string(),
@@ -90,7 +166,7 @@ impl Cache {
 
         tx.commit()?;
 
-        Ok(())
+        Ok(result)
     }
 
     /// Deletes all trails from past sessions but the latesT.
@@ -99,7 +175,7 @@ impl Cache {
 
         Cache::delete_old_trailmarks(&tx, &timestamp_string(&self.timestamp))?;
 
-        &self.report.log(
+        self.report.log(
             Action::Prune,
             Entity::Cache,
             &self.strategy.to_string(),
@@ -152,16 +228,20 @@ impl Cache {
         Ok(())
     }
 
-    /// Selects all standard checksum that are not present in the given session trail.
-    #[allow(dead_code)]
-    pub(crate) fn select_stale_standards(tx: &Transaction, timestamp: &str) -> Result<Vec<String>> {
-        let values = params![timestamp];
-        let mut stmt = tx.prepare(
+    /// Selects the identifiers of the resources in the given table that are not present in the given session trail.
+    pub(crate) fn select_stale(
+        tx: &Transaction,
+        resource_type: &str,
+        table: &str,
+        timestamp: &str,
+    ) -> Result<Vec<String>> {
+        let values = params![resource_type, timestamp];
+        let mut stmt = tx.prepare(&format!(
             r#"
             SELECT
-                checksum
+                id
             FROM
-                standard
+                {}
             WHERE
                 checksum NOT IN (
                     SELECT
@@ -169,12 +249,15 @@ impl Cache {
                     FROM
                         session_trail
                     WHERE
-                        resource_type = "standard"
+                        resource_type = ?
                     AND
                         timestamp = ?
                 )
+            ORDER BY
+                id
         "#,
-        )?;
+            table
+        ))?;
 
         let mut rows = stmt.query(values)?;
         let mut list = Vec::new();
@@ -187,35 +270,34 @@ impl Cache {
         Ok(list)
     }
 
-    /// Deletes all standard records that are not present in the given session trail.
+    /// Deletes all records in the given table that are not present in the given session trail.
     ///
     /// Use [`Cache.prune`] for a full cleanup.
-    pub(crate) fn delete_stale_standards(tx: &Transaction, timestamp: &str) -> Result<()> {
-        let values = params![timestamp];
-        let mut stmt = tx.prepare(
+    pub(crate) fn delete_stale(
+        tx: &Transaction,
+        resource_type: &str,
+        table: &str,
+        timestamp: &str,
+    ) -> Result<()> {
+        let values = params![resource_type, timestamp];
+        let mut stmt = tx.prepare(&format!(
             r#"
             DELETE FROM
-                standard
+                {}
             WHERE
-                checksum IN (
+                checksum NOT IN (
                     SELECT
                         checksum
                     FROM
-                        standard
+                        session_trail
                     WHERE
-                        checksum NOT IN (
-                            SELECT
-                                checksum
-                            FROM
-                                session_trail
-                            WHERE
-                                resource_type = "standard"
-                            AND
-                                timestamp = ?
-                        )
+                        resource_type = ?
+                    AND
+                        timestamp = ?
                 )
         "#,
-        )?;
+            table
+        ))?;
 
         stmt.execute(values)?;
 
@@ -223,6 +305,35 @@ impl Cache {
     }
 }
 
+/// The resource types tracked by the session trail, the table holding them and their report entity.
+const TRAILED_TABLES: [(&str, &str, Entity); 12] = [
+    ("asset", "asset", Entity::Asset),
+    ("case-study", "case_study", Entity::Casestudy),
+    ("dataset", "dataset", Entity::Dataset),
+    ("guidance", "guidance", Entity::Guidance),
+    ("licence", "licence", Entity::Licence),
+    ("organisation", "organisation", Entity::Organisation),
+    ("process", "process", Entity::Process),
+    ("section", "section", Entity::Section),
+    ("standard", "standard", Entity::Standard),
+    ("theme", "theme", Entity::Theme),
+    ("topic", "topic", Entity::Topic),
+    ("use-case", "use_case", Entity::Usecase),
+];
+
+/// A resource removed from the cache because it was not seen in the current session.
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleRecord {
+    pub resource_type: String,
+    pub id: String,
+}
+
+impl fmt::Display for StaleRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} `{}`", self.resource_type, self.id)
+    }
+}
+
 pub fn timestamp_string(timestamp: &DateTime<Utc>) -> String {
     timestamp.to_rfc3339()
 }
@@ -255,4 +366,41 @@ mod tests {
             "Failed whilst connecting to a disk-based cache."
         );
     }
+
+    #[test]
+    fn prune_unseen_resources() -> anyhow::Result<()> {
+        use crate::resource::Resource;
+        use crate::source::{Guidance, Topic};
+
+        let temp = TempDir::default();
+        let path = temp.as_ref().join("cache.db").display().to_string();
+        let topic = |id: &str| {
+            Topic::from_str(&format!(
+                "---\ntype: topic\nidentifier: {}\nname: {}\ntheme: other\nordinal: 1\n---\n",
+                id, id
+            ))
+        };
+        let guidance = Guidance::from_str(
+            "---\ntype: guidance\nidentifier: vapour\nmaintainer: data-standards-authority\nstatus: draft\ncreation_date: 2021-06-01\nupdate_date: 2021-06-01\nstandards:\n  - vapour\n---\n# Vapour",
+        )?;
+
+        let mut first = Cache::connect(&path)?;
+        first.add(&topic("exchange")?)?;
+        first.add(&topic("steam")?)?;
+        first.add(&guidance)?;
+        first.insert_origin("guidance", "vapour", "guidance/vapour.md")?;
+
+        let mut second = Cache::connect(&path)?;
+        second.add(&topic("exchange")?)?;
+
+        let actual = second.prune()?;
+        let ids: Vec<_> = actual.iter().map(|record| record.to_string()).collect();
+
+        assert_eq!(ids, vec!["guidance `vapour`", "topic `steam`"]);
+        assert!(Resource::<Topic>::get(&mut second, "exchange")?.is_some());
+        assert!(Resource::<Guidance>::get(&mut second, "vapour")?.is_none());
+        assert!(second.origins("guidance/vapour.md")?.is_empty());
+
+        Ok(())
+    }
 }
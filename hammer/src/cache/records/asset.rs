@@ -0,0 +1,104 @@
+use anyhow::Result;
+use rusqlite::{params, Transaction};
+
+#[derive(Debug, Clone)]
+pub struct AssetRecord {
+    pub id: String,
+    pub checksum: String,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+impl AssetRecord {
+    pub(crate) fn select_all(tx: &Transaction) -> Result<Vec<AssetRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                asset
+            ORDER BY
+                id;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![])?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let record = AssetRecord {
+                id: row.get(0)?,
+                checksum: row.get(1)?,
+                content_type: row.get(2)?,
+                content: row.get(3)?,
+            };
+            result.push(record);
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) fn select(tx: &Transaction, id: &str) -> Result<Option<AssetRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                asset
+            WHERE
+                id = ?;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![id])?;
+
+        if let Some(row) = rows.next()? {
+            let result = AssetRecord {
+                id: row.get(0)?,
+                checksum: row.get(1)?,
+                content_type: row.get(2)?,
+                content: row.get(3)?,
+            };
+            return Ok(Some(result));
+        }
+
+        Ok(None)
+    }
+
+    pub(crate) fn delete(tx: &Transaction, id: &str) -> Result<()> {
+        let mut stmt = tx.prepare(
+            r#"
+            DELETE FROM
+                asset
+            WHERE
+                id = ?;
+        "#,
+        )?;
+
+        stmt.execute(params![id])?;
+
+        Ok(())
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &AssetRecord) -> Result<()> {
+        let values = params![
+            &record.id,
+            &record.checksum,
+            &record.content_type,
+            &record.content,
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO asset (
+                id,
+                checksum,
+                content_type,
+                content
+            )
+            VALUES (?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
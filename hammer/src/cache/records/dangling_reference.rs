@@ -0,0 +1,241 @@
+use anyhow::Result;
+use rusqlite::{params, Transaction};
+
+/// A reference from a resource to another resource that is not in the cache.
+#[derive(Debug, Clone)]
+pub struct DanglingReferenceRecord {
+    pub resource_type: String,
+    pub resource_id: String,
+    /// The source property holding the reference.
+    pub field: String,
+    pub reference_type: String,
+    pub reference_id: String,
+    /// The source file the referencing resource was read from, if known.
+    pub path: Option<String>,
+}
+
+impl DanglingReferenceRecord {
+    pub fn select_all(tx: &Transaction) -> Result<Vec<DanglingReferenceRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                dangling.resource_type,
+                dangling.resource_id,
+                dangling.field,
+                dangling.reference_type,
+                dangling.reference_id,
+                origin.path
+            FROM (
+                SELECT
+                    'standard' AS resource_type,
+                    standard.id AS resource_id,
+                    'topic' AS field,
+                    'topic' AS reference_type,
+                    standard.topic_id AS reference_id
+                FROM
+                    standard
+                LEFT JOIN topic ON
+                    topic.id = standard.topic_id
+                WHERE
+                    topic.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'standard',
+                    standard.id,
+                    'licence',
+                    'licence',
+                    standard.licence_id
+                FROM
+                    standard
+                LEFT JOIN licence ON
+                    licence.id = standard.licence_id
+                WHERE
+                    standard.licence_id IS NOT NULL
+                AND
+                    licence.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'standard',
+                    standard.id,
+                    'maintainer',
+                    'organisation',
+                    standard.maintainer_id
+                FROM
+                    standard
+                LEFT JOIN organisation ON
+                    organisation.id = standard.maintainer_id
+                WHERE
+                    organisation.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'standard',
+                    related_standard.standard_id,
+                    'related',
+                    'standard',
+                    related_standard.related_standard_id
+                FROM
+                    related_standard
+                LEFT JOIN standard ON
+                    standard.id = related_standard.related_standard_id
+                WHERE
+                    standard.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'standard',
+                    external_resource.standard_id,
+                    CASE external_resource.resource_type
+                        WHEN 'use-case' THEN 'use_cases'
+                        WHEN 'case-study' THEN 'case_studies'
+                        ELSE external_resource.resource_type
+                    END,
+                    'organisation',
+                    external_resource.publisher_id
+                FROM
+                    external_resource
+                LEFT JOIN organisation ON
+                    organisation.id = external_resource.publisher_id
+                WHERE
+                    organisation.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'guidance',
+                    guidance.id,
+                    'maintainer',
+                    'organisation',
+                    guidance.maintainer_id
+                FROM
+                    guidance
+                LEFT JOIN organisation ON
+                    organisation.id = guidance.maintainer_id
+                WHERE
+                    organisation.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'guidance',
+                    guidance_standard.guidance_id,
+                    'standards',
+                    'standard',
+                    guidance_standard.standard_id
+                FROM
+                    guidance_standard
+                LEFT JOIN standard ON
+                    standard.id = guidance_standard.standard_id
+                WHERE
+                    standard.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'topic',
+                    topic.id,
+                    'theme',
+                    'theme',
+                    topic.theme_id
+                FROM
+                    topic
+                LEFT JOIN theme ON
+                    theme.id = topic.theme_id
+                WHERE
+                    theme.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'case-study',
+                    case_study.id,
+                    'publisher',
+                    'organisation',
+                    case_study.publisher_id
+                FROM
+                    case_study
+                LEFT JOIN organisation ON
+                    organisation.id = case_study.publisher_id
+                WHERE
+                    organisation.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'case-study',
+                    case_study_standard.case_study_id,
+                    'standards',
+                    'standard',
+                    case_study_standard.standard_id
+                FROM
+                    case_study_standard
+                LEFT JOIN standard ON
+                    standard.id = case_study_standard.standard_id
+                WHERE
+                    standard.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'use-case',
+                    use_case.id,
+                    'publisher',
+                    'organisation',
+                    use_case.publisher_id
+                FROM
+                    use_case
+                LEFT JOIN organisation ON
+                    organisation.id = use_case.publisher_id
+                WHERE
+                    organisation.id IS NULL
+
+                UNION ALL
+
+                SELECT
+                    'use-case',
+                    use_case_standard.use_case_id,
+                    'standards',
+                    'standard',
+                    use_case_standard.standard_id
+                FROM
+                    use_case_standard
+                LEFT JOIN standard ON
+                    standard.id = use_case_standard.standard_id
+                WHERE
+                    standard.id IS NULL
+            ) AS dangling
+            LEFT JOIN origin ON
+                origin.resource_type = dangling.resource_type
+            AND
+                origin.resource_id = dangling.resource_id
+            ORDER BY
+                origin.path,
+                dangling.resource_id,
+                dangling.field,
+                dangling.reference_id;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![])?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let record = DanglingReferenceRecord {
+                resource_type: row.get(0)?,
+                resource_id: row.get(1)?,
+                field: row.get(2)?,
+                reference_type: row.get(3)?,
+                reference_id: row.get(4)?,
+                path: row.get(5)?,
+            };
+            result.push(record);
+        }
+
+        Ok(result)
+    }
+}
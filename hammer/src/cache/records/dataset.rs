@@ -0,0 +1,260 @@
+use anyhow::Result;
+use rusqlite::{params, Transaction};
+
+#[derive(Debug, Clone)]
+pub struct DatasetRecord {
+    pub id: String,
+    pub checksum: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub maintainer: Option<String>,
+    pub url: String,
+    /// Comma separated list of column names.
+    pub primary_key: Option<String>,
+}
+
+impl DatasetRecord {
+    pub(crate) fn select_all(tx: &Transaction) -> Result<Vec<DatasetRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                dataset
+            ORDER BY
+                id;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![])?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let record = DatasetRecord {
+                id: row.get(0)?,
+                checksum: row.get(1)?,
+                name: row.get(2)?,
+                description: row.get(3)?,
+                maintainer: row.get(4)?,
+                url: row.get(5)?,
+                primary_key: row.get(6)?,
+            };
+            result.push(record);
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) fn select(tx: &Transaction, id: &str) -> Result<Option<DatasetRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                dataset
+            WHERE
+                id = ?;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![id])?;
+
+        if let Some(row) = rows.next()? {
+            let result = DatasetRecord {
+                id: row.get(0)?,
+                checksum: row.get(1)?,
+                name: row.get(2)?,
+                description: row.get(3)?,
+                maintainer: row.get(4)?,
+                url: row.get(5)?,
+                primary_key: row.get(6)?,
+            };
+            return Ok(Some(result));
+        }
+
+        Ok(None)
+    }
+
+    /// Deletes the dataset with its columns and rows.
+    ///
+    /// Columns and rows are deleted explicitly as in-memory caches don't enforce foreign keys.
+    pub(crate) fn delete(tx: &Transaction, id: &str) -> Result<()> {
+        for table in &["dataset_row", "dataset_column"] {
+            let mut stmt = tx.prepare(&format!(
+                r#"
+                DELETE FROM
+                    {}
+                WHERE
+                    dataset_id = ?;
+            "#,
+                table
+            ))?;
+
+            stmt.execute(params![id])?;
+        }
+
+        let mut stmt = tx.prepare(
+            r#"
+            DELETE FROM
+                dataset
+            WHERE
+                id = ?;
+        "#,
+        )?;
+
+        stmt.execute(params![id])?;
+
+        Ok(())
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &DatasetRecord) -> Result<()> {
+        let values = params![
+            &record.id,
+            &record.checksum,
+            &record.name,
+            &record.description,
+            &record.maintainer,
+            &record.url,
+            &record.primary_key,
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO dataset (
+                id,
+                checksum,
+                name,
+                description,
+                maintainer,
+                url,
+                primary_key
+            )
+            VALUES (?, ?, ?, ?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DatasetColumnRecord {
+    pub dataset_id: String,
+    pub ordinal: u32,
+    pub name: String,
+    pub title: String,
+    pub description: Option<String>,
+    pub datatype: String,
+    pub required: bool,
+}
+
+impl DatasetColumnRecord {
+    /// Selects the columns of the given dataset in order.
+    pub(crate) fn select(tx: &Transaction, dataset_id: &str) -> Result<Vec<DatasetColumnRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                dataset_column
+            WHERE
+                dataset_id = ?
+            ORDER BY
+                ordinal;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![dataset_id])?;
+        let mut list = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let result = DatasetColumnRecord {
+                dataset_id: row.get(0)?,
+                ordinal: row.get(1)?,
+                name: row.get(2)?,
+                title: row.get(3)?,
+                description: row.get(4)?,
+                datatype: row.get(5)?,
+                required: row.get(6)?,
+            };
+
+            list.push(result);
+        }
+
+        Ok(list)
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &DatasetColumnRecord) -> Result<()> {
+        let values = params![
+            &record.dataset_id,
+            &record.ordinal,
+            &record.name,
+            &record.title,
+            &record.description,
+            &record.datatype,
+            &record.required,
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO dataset_column
+            VALUES (?, ?, ?, ?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DatasetRowRecord {
+    pub dataset_id: String,
+    pub ordinal: u32,
+    /// JSON array with the cells of the row in column order.
+    pub cells: String,
+}
+
+impl DatasetRowRecord {
+    /// Selects the rows of the given dataset in order.
+    pub(crate) fn select(tx: &Transaction, dataset_id: &str) -> Result<Vec<DatasetRowRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                dataset_row
+            WHERE
+                dataset_id = ?
+            ORDER BY
+                ordinal;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![dataset_id])?;
+        let mut list = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let result = DatasetRowRecord {
+                dataset_id: row.get(0)?,
+                ordinal: row.get(1)?,
+                cells: row.get(2)?,
+            };
+
+            list.push(result);
+        }
+
+        Ok(list)
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &DatasetRowRecord) -> Result<()> {
+        let values = params![&record.dataset_id, &record.ordinal, &record.cells];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO dataset_row
+            VALUES (?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
@@ -50,7 +50,7 @@ impl GuidanceRecord {
         let mut result = Vec::new();
 
         while let Some(row) = rows.next()? {
-            let record = into_record(&row)?;
+            let record = into_record(row)?;
             result.push(record);
         }
 
@@ -71,7 +71,7 @@ impl GuidanceRecord {
         let mut rows = stmt.query(params![id])?;
 
         if let Some(row) = rows.next()? {
-            let result = into_record(&row)?;
+            let result = into_record(row)?;
             return Ok(Some(result));
         }
 
@@ -167,6 +167,134 @@ impl GuidanceStandardRecord {
     }
 }
 
+/// The editorial fields of a guidance that hold a single value. A guidance without any has no record.
+#[derive(Debug, Clone)]
+pub struct GuidanceEditorialRecord {
+    pub(crate) guidance_id: String,
+    pub(crate) purpose: Option<String>,
+    pub(crate) target: Option<String>,
+    pub(crate) user_needs: Option<String>,
+    pub(crate) route: Option<String>,
+    pub(crate) out_of_scope: Option<String>,
+}
+
+impl GuidanceEditorialRecord {
+    pub(crate) fn select(
+        tx: &Transaction,
+        guidance_id: &str,
+    ) -> Result<Option<GuidanceEditorialRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                guidance_editorial
+            WHERE
+                guidance_id = ?;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![guidance_id])?;
+
+        if let Some(row) = rows.next()? {
+            let result = GuidanceEditorialRecord {
+                guidance_id: row.get(0)?,
+                purpose: row.get(1)?,
+                target: row.get(2)?,
+                user_needs: row.get(3)?,
+                route: row.get(4)?,
+                out_of_scope: row.get(5)?,
+            };
+            return Ok(Some(result));
+        }
+
+        Ok(None)
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &GuidanceEditorialRecord) -> Result<()> {
+        let values = params![
+            &record.guidance_id,
+            &record.purpose,
+            &record.target,
+            &record.user_needs,
+            &record.route,
+            &record.out_of_scope,
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO guidance_editorial
+            VALUES (?, ?, ?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
+
+/// An entry in one of the editorial lists of a guidance, e.g. an `author` or a `tag`.
+#[derive(Debug, Clone)]
+pub struct GuidanceEditorialItemRecord {
+    pub(crate) guidance_id: String,
+    pub(crate) field: String,
+    pub(crate) ordinal: u32,
+    pub(crate) value: String,
+}
+
+impl GuidanceEditorialItemRecord {
+    pub(crate) fn select(
+        tx: &Transaction,
+        guidance_id: &str,
+    ) -> Result<Vec<GuidanceEditorialItemRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                guidance_editorial_item
+            WHERE
+                guidance_id = ?
+            ORDER BY
+                field, ordinal;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![guidance_id])?;
+        let mut list = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let result = GuidanceEditorialItemRecord {
+                guidance_id: row.get(0)?,
+                field: row.get(1)?,
+                ordinal: row.get(2)?,
+                value: row.get(3)?,
+            };
+
+            list.push(result);
+        }
+
+        Ok(list)
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &GuidanceEditorialItemRecord) -> Result<()> {
+        let values = params![
+            &record.guidance_id,
+            &record.field,
+            &record.ordinal,
+            &record.value,
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO guidance_editorial_item
+            VALUES (?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
+
 impl FromSql for Status {
     #[inline]
     fn column_result(value: ValueRef<'_>) -> FromSqlResult<Self> {
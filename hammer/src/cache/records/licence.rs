@@ -11,6 +11,34 @@ pub struct LicenceRecord {
 }
 
 impl LicenceRecord {
+    pub(crate) fn select_all(tx: &Transaction) -> Result<Vec<LicenceRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                licence
+            ORDER BY
+                id;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![])?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let record = LicenceRecord {
+                id: row.get(0)?,
+                checksum: row.get(1)?,
+                name: row.get(2)?,
+                acronym: row.get(3)?,
+                url: row.get(4)?,
+            };
+            result.push(record);
+        }
+
+        Ok(result)
+    }
+
     pub(crate) fn select(tx: &Transaction, licence_id: &str) -> Result<Option<LicenceRecord>> {
         let mut stmt = tx.prepare(
             r#"
@@ -1,19 +1,39 @@
 //! This module contains the table records as per the cache physical data model.
 //!
-//! They should strictly match [`../cache.sql`].
+//! They should strictly match the schema built by [`../sql/migrations`].
 
+mod asset;
+mod dangling_reference;
+mod dataset;
 mod guidance;
 mod licence;
 mod organisation;
+mod origin;
+mod process;
+mod search;
 mod section;
 mod standard;
+mod story;
 mod theme;
 mod topic;
 
-pub use guidance::{GuidanceRecord, GuidanceStandardRecord};
+pub use asset::AssetRecord;
+pub use dangling_reference::DanglingReferenceRecord;
+pub use dataset::{DatasetColumnRecord, DatasetRecord, DatasetRowRecord};
+pub use guidance::{
+    GuidanceEditorialItemRecord, GuidanceEditorialRecord, GuidanceRecord, GuidanceStandardRecord,
+};
 pub use licence::LicenceRecord;
 pub use organisation::OrganisationRecord;
+pub use origin::OriginRecord;
+pub use process::ProcessRecord;
+pub use search::{SearchHitRecord, SearchRecord};
 pub use section::SectionRecord;
-pub use standard::{EndorsementStateRecord, RelatedStandardRecord, StandardRecord};
+pub use standard::{
+    ConsultationRecord, DsaProcessRecord, EndorsementStateRecord, EndorsementTransitionRecord,
+    ExternalResourceRecord, RelatedStandardRecord, StandardRecord, StandardReviewRecord,
+    StandardSubjectRecord,
+};
+pub use story::{StoryRecord, StoryStandardRecord};
 pub use theme::ThemeRecord;
 pub use topic::{TopicRecord, TopicStandardRecord};
@@ -10,6 +10,33 @@ pub struct OrganisationRecord {
 }
 
 impl OrganisationRecord {
+    pub(crate) fn select_all(tx: &Transaction) -> Result<Vec<OrganisationRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                organisation
+            ORDER BY
+                id;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![])?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let record = OrganisationRecord {
+                id: row.get(0)?,
+                checksum: row.get(1)?,
+                name: row.get(2)?,
+                url: row.get(3)?,
+            };
+            result.push(record);
+        }
+
+        Ok(result)
+    }
+
     pub(crate) fn select(tx: &Transaction, id: &str) -> Result<Option<OrganisationRecord>> {
         let mut stmt = tx.prepare(
             r#"
@@ -0,0 +1,127 @@
+use anyhow::Result;
+use rusqlite::{params, Transaction};
+
+/// The source file a resource was read from.
+#[derive(Debug, Clone)]
+pub struct OriginRecord {
+    pub resource_type: String,
+    pub resource_id: String,
+    pub path: String,
+}
+
+impl OriginRecord {
+    /// Inserts the origin of a resource, replacing any previous one.
+    pub(crate) fn insert(tx: &Transaction, record: &OriginRecord) -> Result<()> {
+        let values = params![&record.resource_type, &record.resource_id, &record.path];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT OR REPLACE INTO origin (
+                resource_type,
+                resource_id,
+                path
+            )
+            VALUES (?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+
+    /// Selects the origin of the given resource.
+    pub(crate) fn select(
+        tx: &Transaction,
+        resource_type: &str,
+        resource_id: &str,
+    ) -> Result<Option<OriginRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                resource_type,
+                resource_id,
+                path
+            FROM
+                origin
+            WHERE
+                resource_type = ?
+            AND
+                resource_id = ?;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![resource_type, resource_id])?;
+
+        if let Some(row) = rows.next()? {
+            let record = OriginRecord {
+                resource_type: row.get(0)?,
+                resource_id: row.get(1)?,
+                path: row.get(2)?,
+            };
+
+            return Ok(Some(record));
+        }
+
+        Ok(None)
+    }
+
+    /// Selects the resources read from the given source file.
+    pub(crate) fn select_by_path(tx: &Transaction, path: &str) -> Result<Vec<OriginRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                resource_type,
+                resource_id,
+                path
+            FROM
+                origin
+            WHERE
+                path = ?;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![path])?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let record = OriginRecord {
+                resource_type: row.get(0)?,
+                resource_id: row.get(1)?,
+                path: row.get(2)?,
+            };
+            result.push(record);
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) fn delete(tx: &Transaction, resource_type: &str, resource_id: &str) -> Result<()> {
+        let mut stmt = tx.prepare(
+            r#"
+            DELETE FROM
+                origin
+            WHERE
+                resource_type = ?
+            AND
+                resource_id = ?;
+        "#,
+        )?;
+
+        stmt.execute(params![resource_type, resource_id])?;
+
+        Ok(())
+    }
+
+    pub(crate) fn delete_by_path(tx: &Transaction, path: &str) -> Result<()> {
+        let mut stmt = tx.prepare(
+            r#"
+            DELETE FROM
+                origin
+            WHERE
+                path = ?;
+        "#,
+        )?;
+
+        stmt.execute(params![path])?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,98 @@
+use anyhow::Result;
+use rusqlite::{params, Row, Transaction};
+
+#[derive(Debug, Clone)]
+pub struct ProcessRecord {
+    pub(crate) id: String,
+    pub(crate) checksum: String,
+    pub(crate) description: Option<String>,
+    pub(crate) content: String,
+}
+
+fn into_record(row: &Row) -> Result<ProcessRecord> {
+    let record = ProcessRecord {
+        id: row.get(0)?,
+        checksum: row.get(1)?,
+        description: row.get(2)?,
+        content: row.get(3)?,
+    };
+
+    Ok(record)
+}
+
+impl ProcessRecord {
+    pub(crate) fn select_all(tx: &Transaction) -> Result<Vec<ProcessRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                process;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![])?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let record = into_record(row)?;
+            result.push(record);
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) fn select(tx: &Transaction, id: &str) -> Result<Option<ProcessRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                process
+            WHERE
+                id = ?;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![id])?;
+
+        if let Some(row) = rows.next()? {
+            let result = into_record(row)?;
+            return Ok(Some(result));
+        }
+
+        Ok(None)
+    }
+
+    pub(crate) fn delete(tx: &Transaction, id: &str) -> Result<()> {
+        let mut stmt = tx.prepare(
+            r#"
+            DELETE FROM
+                process
+            WHERE
+                id = ?;
+        "#,
+        )?;
+
+        stmt.execute(params![id])?;
+
+        Ok(())
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &ProcessRecord) -> Result<()> {
+        let values = params![
+            &record.id,
+            &record.checksum,
+            &record.description,
+            &record.content,
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO process
+            VALUES (?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use anyhow::Result;
+use rusqlite::{params, Transaction};
+
+/// An entry of the full-text search index.
+#[derive(Debug, Clone)]
+pub struct SearchRecord {
+    pub resource_type: String,
+    pub resource_id: String,
+    pub title: String,
+    pub content: String,
+}
+
+impl SearchRecord {
+    /// Indexes the given resource, replacing any previous entry.
+    pub(crate) fn insert(tx: &Transaction, record: &SearchRecord) -> Result<()> {
+        SearchRecord::delete(tx, &record.resource_type, &record.resource_id)?;
+
+        let values = params![
+            &record.resource_type,
+            &record.resource_id,
+            &record.title,
+            &record.content
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO search_index (
+                resource_type,
+                resource_id,
+                title,
+                content
+            )
+            VALUES (?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+
+    pub(crate) fn delete(tx: &Transaction, resource_type: &str, resource_id: &str) -> Result<()> {
+        let mut stmt = tx.prepare(
+            r#"
+            DELETE FROM
+                search_index
+            WHERE
+                resource_type = ?
+            AND
+                resource_id = ?;
+        "#,
+        )?;
+
+        stmt.execute(params![resource_type, resource_id])?;
+
+        Ok(())
+    }
+}
+
+/// A search match with the fragment of text around the matched terms.
+///
+/// Matched terms in the snippet are wrapped in `**`.
+#[derive(Debug, Clone)]
+pub struct SearchHitRecord {
+    pub resource_type: String,
+    pub resource_id: String,
+    pub title: String,
+    pub snippet: String,
+    pub rank: f64,
+}
+
+impl SearchHitRecord {
+    /// Selects the best matches for the given FTS5 query, titles weighing ten times as much as bodies.
+    pub(crate) fn select(
+        tx: &Transaction,
+        query: &str,
+        limit: u32,
+    ) -> Result<Vec<SearchHitRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                resource_type,
+                resource_id,
+                title,
+                snippet(search_index, -1, '**', '**', '…', 16),
+                bm25(search_index, 0.0, 0.0, 10.0, 1.0) AS score
+            FROM
+                search_index
+            WHERE
+                search_index MATCH ?
+            ORDER BY
+                score,
+                resource_type,
+                resource_id
+            LIMIT ?;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![query, limit])?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let record = SearchHitRecord {
+                resource_type: row.get(0)?,
+                resource_id: row.get(1)?,
+                title: row.get(2)?,
+                snippet: row.get(3)?,
+                rank: row.get(4)?,
+            };
+            result.push(record);
+        }
+
+        Ok(result)
+    }
+}
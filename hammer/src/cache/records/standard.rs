@@ -12,6 +12,7 @@ pub struct StandardRecord {
     pub(crate) licence_id: Option<String>,
     pub(crate) maintainer_id: String,
     pub(crate) content: String,
+    pub(crate) conforms_to: Option<String>,
 }
 
 fn into_record(row: &Row) -> Result<StandardRecord> {
@@ -25,6 +26,7 @@ fn into_record(row: &Row) -> Result<StandardRecord> {
         licence_id: row.get(6)?,
         maintainer_id: row.get(7)?,
         content: row.get(8)?,
+        conforms_to: row.get(9)?,
     };
 
     Ok(record)
@@ -44,7 +46,7 @@ impl StandardRecord {
         let mut result = Vec::new();
 
         while let Some(row) = rows.next()? {
-            let record = into_record(&row)?;
+            let record = into_record(row)?;
             result.push(record);
         }
 
@@ -65,7 +67,7 @@ impl StandardRecord {
         let mut rows = stmt.query(params![standard_id])?;
 
         if let Some(row) = rows.next()? {
-            let result = into_record(&row)?;
+            let result = into_record(row)?;
             return Ok(Some(result));
         }
 
@@ -98,11 +100,12 @@ impl StandardRecord {
             &record.licence_id,
             &record.maintainer_id,
             &record.content,
+            &record.conforms_to,
         ];
         let mut stmt = tx.prepare(
             r#"
             INSERT INTO standard
-            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?);
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?);
         "#,
         )?;
 
@@ -223,3 +226,363 @@ impl EndorsementStateRecord {
         Ok(())
     }
 }
+
+#[derive(Debug, Clone)]
+pub struct EndorsementTransitionRecord {
+    pub(crate) standard_id: String,
+    pub(crate) ordinal: u32,
+    pub(crate) status: String,
+    pub(crate) date: String,
+}
+
+impl EndorsementTransitionRecord {
+    pub(crate) fn select(
+        tx: &Transaction,
+        standard_id: &str,
+    ) -> Result<Vec<EndorsementTransitionRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                endorsement_transition
+            WHERE
+                standard_id = ?
+            ORDER BY
+                ordinal;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![standard_id])?;
+        let mut list = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let result = EndorsementTransitionRecord {
+                standard_id: row.get(0)?,
+                ordinal: row.get(1)?,
+                status: row.get(2)?,
+                date: row.get(3)?,
+            };
+
+            list.push(result);
+        }
+
+        Ok(list)
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &EndorsementTransitionRecord) -> Result<()> {
+        let values = params![
+            &record.standard_id,
+            &record.ordinal,
+            &record.status,
+            &record.date,
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO endorsement_transition
+            VALUES (?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StandardSubjectRecord {
+    pub(crate) standard_id: String,
+    pub(crate) ordinal: u32,
+    pub(crate) subject_id: String,
+}
+
+impl StandardSubjectRecord {
+    pub(crate) fn select(
+        tx: &Transaction,
+        standard_id: &str,
+    ) -> Result<Vec<StandardSubjectRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                standard_subject
+            WHERE
+                standard_id = ?
+            ORDER BY
+                ordinal;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![standard_id])?;
+        let mut list = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let result = StandardSubjectRecord {
+                standard_id: row.get(0)?,
+                ordinal: row.get(1)?,
+                subject_id: row.get(2)?,
+            };
+
+            list.push(result);
+        }
+
+        Ok(list)
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &StandardSubjectRecord) -> Result<()> {
+        let values = params![&record.standard_id, &record.ordinal, &record.subject_id];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO standard_subject
+            VALUES (?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DsaProcessRecord {
+    pub(crate) standard_id: String,
+    pub(crate) stage: String,
+    pub(crate) start_date: String,
+    pub(crate) update_date: String,
+}
+
+impl DsaProcessRecord {
+    pub(crate) fn select(tx: &Transaction, standard_id: &str) -> Result<Option<DsaProcessRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                dsa_process
+            WHERE
+                standard_id = ?;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![standard_id])?;
+
+        if let Some(row) = rows.next()? {
+            let result = DsaProcessRecord {
+                standard_id: row.get(0)?,
+                stage: row.get(1)?,
+                start_date: row.get(2)?,
+                update_date: row.get(3)?,
+            };
+            return Ok(Some(result));
+        }
+
+        Ok(None)
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &DsaProcessRecord) -> Result<()> {
+        let values = params![
+            &record.standard_id,
+            &record.stage,
+            &record.start_date,
+            &record.update_date,
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO dsa_process
+            VALUES (?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct ConsultationRecord {
+    pub(crate) standard_id: String,
+    pub(crate) title: String,
+    pub(crate) url: String,
+    pub(crate) start_date: String,
+    pub(crate) end_date: Option<String>,
+}
+
+impl ConsultationRecord {
+    pub(crate) fn select(
+        tx: &Transaction,
+        standard_id: &str,
+    ) -> Result<Option<ConsultationRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                consultation
+            WHERE
+                standard_id = ?;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![standard_id])?;
+
+        if let Some(row) = rows.next()? {
+            let result = ConsultationRecord {
+                standard_id: row.get(0)?,
+                title: row.get(1)?,
+                url: row.get(2)?,
+                start_date: row.get(3)?,
+                end_date: row.get(4)?,
+            };
+            return Ok(Some(result));
+        }
+
+        Ok(None)
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &ConsultationRecord) -> Result<()> {
+        let values = params![
+            &record.standard_id,
+            &record.title,
+            &record.url,
+            &record.start_date,
+            &record.end_date,
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO consultation
+            VALUES (?, ?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
+
+/// A guidance, use case or case study about a standard published elsewhere.
+#[derive(Debug, Clone)]
+pub struct ExternalResourceRecord {
+    pub(crate) standard_id: String,
+    /// One of `guidance`, `use-case` or `case-study`.
+    pub(crate) resource_type: String,
+    pub(crate) ordinal: u32,
+    pub(crate) title: String,
+    pub(crate) publisher_id: String,
+    pub(crate) url: String,
+}
+
+impl ExternalResourceRecord {
+    pub(crate) fn select(
+        tx: &Transaction,
+        standard_id: &str,
+        resource_type: &str,
+    ) -> Result<Vec<ExternalResourceRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                *
+            FROM
+                external_resource
+            WHERE
+                standard_id = ?
+                AND resource_type = ?
+            ORDER BY
+                ordinal;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![standard_id, resource_type])?;
+        let mut list = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let result = ExternalResourceRecord {
+                standard_id: row.get(0)?,
+                resource_type: row.get(1)?,
+                ordinal: row.get(2)?,
+                title: row.get(3)?,
+                publisher_id: row.get(4)?,
+                url: row.get(5)?,
+            };
+
+            list.push(result);
+        }
+
+        Ok(list)
+    }
+
+    pub(crate) fn insert(tx: &Transaction, record: &ExternalResourceRecord) -> Result<()> {
+        let values = params![
+            &record.standard_id,
+            &record.resource_type,
+            &record.ordinal,
+            &record.title,
+            &record.publisher_id,
+            &record.url,
+        ];
+        let mut stmt = tx.prepare(
+            r#"
+            INSERT INTO external_resource
+            VALUES (?, ?, ?, ?, ?, ?);
+        "#,
+        )?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
+
+/// A standard with the dates relevant to its endorsement review.
+#[derive(Debug, Clone)]
+pub struct StandardReviewRecord {
+    pub id: String,
+    pub name: String,
+    pub maintainer_id: String,
+    pub maintainer_name: Option<String>,
+    pub status: String,
+    pub review_date: String,
+    pub end_date: Option<String>,
+}
+
+impl StandardReviewRecord {
+    pub fn select_all(tx: &Transaction) -> Result<Vec<StandardReviewRecord>> {
+        let mut stmt = tx.prepare(
+            r#"
+            SELECT
+                standard.id,
+                standard.name,
+                standard.maintainer_id,
+                organisation.name,
+                endorsement_state.status,
+                endorsement_state.review_date,
+                endorsement_state.end_date
+            FROM
+                standard
+            INNER JOIN endorsement_state ON
+                standard.id = endorsement_state.standard_id
+            LEFT JOIN organisation ON
+                standard.maintainer_id = organisation.id
+            ORDER BY
+                standard.maintainer_id,
+                standard.id;
+        "#,
+        )?;
+        let mut rows = stmt.query(params![])?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let record = StandardReviewRecord {
+                id: row.get(0)?,
+                name: row.get(1)?,
+                maintainer_id: row.get(2)?,
+                maintainer_name: row.get(3)?,
+                status: row.get(4)?,
+                review_date: row.get(5)?,
+                end_date: row.get(6)?,
+            };
+            result.push(record);
+        }
+
+        Ok(result)
+    }
+}
@@ -0,0 +1,176 @@
+//! Case studies and use cases share the same physical data model, each kind in its own pair of tables.
+use anyhow::Result;
+use rusqlite::{params, Row, Transaction};
+
+#[derive(Debug, Clone)]
+pub struct StoryRecord {
+    pub(crate) id: String,
+    pub(crate) checksum: String,
+    pub(crate) description: Option<String>,
+    pub(crate) publisher_id: String,
+    pub(crate) creation_date: String,
+    pub(crate) update_date: String,
+    pub(crate) publication_date: Option<String>,
+    pub(crate) canonical_url: Option<String>,
+    pub(crate) content: String,
+}
+
+fn into_record(row: &Row) -> Result<StoryRecord> {
+    let record = StoryRecord {
+        id: row.get(0)?,
+        checksum: row.get(1)?,
+        description: row.get(2)?,
+        publisher_id: row.get(3)?,
+        creation_date: row.get(4)?,
+        update_date: row.get(5)?,
+        publication_date: row.get(6)?,
+        canonical_url: row.get(7)?,
+        content: row.get(8)?,
+    };
+
+    Ok(record)
+}
+
+impl StoryRecord {
+    pub(crate) fn select_all(tx: &Transaction, table: &str) -> Result<Vec<StoryRecord>> {
+        let mut stmt = tx.prepare(&format!(
+            r#"
+            SELECT
+                *
+            FROM
+                {table};
+        "#,
+            table = table
+        ))?;
+        let mut rows = stmt.query(params![])?;
+        let mut result = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let record = into_record(row)?;
+            result.push(record);
+        }
+
+        Ok(result)
+    }
+
+    pub(crate) fn select(tx: &Transaction, table: &str, id: &str) -> Result<Option<StoryRecord>> {
+        let mut stmt = tx.prepare(&format!(
+            r#"
+            SELECT
+                *
+            FROM
+                {table}
+            WHERE
+                id = ?;
+        "#,
+            table = table
+        ))?;
+        let mut rows = stmt.query(params![id])?;
+
+        if let Some(row) = rows.next()? {
+            let result = into_record(row)?;
+            return Ok(Some(result));
+        }
+
+        Ok(None)
+    }
+
+    pub(crate) fn delete(tx: &Transaction, table: &str, id: &str) -> Result<()> {
+        let mut stmt = tx.prepare(&format!(
+            r#"
+            DELETE FROM
+                {table}
+            WHERE
+                id = ?;
+        "#,
+            table = table
+        ))?;
+
+        stmt.execute(params![id])?;
+
+        Ok(())
+    }
+
+    pub(crate) fn insert(tx: &Transaction, table: &str, record: &StoryRecord) -> Result<()> {
+        let values = params![
+            &record.id,
+            &record.checksum,
+            &record.description,
+            &record.publisher_id,
+            &record.creation_date,
+            &record.update_date,
+            &record.publication_date,
+            &record.canonical_url,
+            &record.content,
+        ];
+        let mut stmt = tx.prepare(&format!(
+            r#"
+            INSERT INTO {table}
+            VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?);
+        "#,
+            table = table
+        ))?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct StoryStandardRecord {
+    pub(crate) story_id: String,
+    pub(crate) standard_id: String,
+}
+
+impl StoryStandardRecord {
+    pub(crate) fn select(
+        tx: &Transaction,
+        table: &str,
+        id: &str,
+    ) -> Result<Vec<StoryStandardRecord>> {
+        let mut stmt = tx.prepare(&format!(
+            r#"
+            SELECT
+                *
+            FROM
+                {table}_standard
+            WHERE
+                {table}_id = ?;
+        "#,
+            table = table
+        ))?;
+        let mut rows = stmt.query(params![id])?;
+        let mut list = Vec::new();
+
+        while let Some(row) = rows.next()? {
+            let result = StoryStandardRecord {
+                story_id: row.get(0)?,
+                standard_id: row.get(1)?,
+            };
+
+            list.push(result);
+        }
+
+        Ok(list)
+    }
+
+    pub(crate) fn insert(
+        tx: &Transaction,
+        table: &str,
+        record: &StoryStandardRecord,
+    ) -> Result<()> {
+        let values = params![&record.story_id, &record.standard_id];
+        let mut stmt = tx.prepare(&format!(
+            r#"
+            INSERT INTO {table}_standard
+            VALUES (?, ?);
+        "#,
+            table = table
+        ))?;
+
+        stmt.execute(values)?;
+
+        Ok(())
+    }
+}
@@ -36,7 +36,7 @@ impl ThemeRecord {
         let mut result = Vec::new();
 
         while let Some(row) = rows.next()? {
-            let record = into_record(&row)?;
+            let record = into_record(row)?;
             result.push(record);
         }
 
@@ -57,7 +57,7 @@ impl ThemeRecord {
         let mut rows = stmt.query(params![id])?;
 
         if let Some(row) = rows.next()? {
-            let result = into_record(&row)?;
+            let result = into_record(row)?;
             return Ok(Some(result));
         }
 
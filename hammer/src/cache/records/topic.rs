@@ -40,13 +40,14 @@ impl TopicRecord {
         let mut result = Vec::new();
 
         while let Some(row) = rows.next()? {
-            let record = into_record(&row)?;
+            let record = into_record(row)?;
             result.push(record);
         }
 
         Ok(result)
     }
 
+    #[allow(dead_code)]
     pub(crate) fn select_all(tx: &Transaction) -> Result<Vec<TopicRecord>> {
         let mut stmt = tx.prepare(
             r#"
@@ -60,7 +61,7 @@ impl TopicRecord {
         let mut result = Vec::new();
 
         while let Some(row) = rows.next()? {
-            let record = into_record(&row)?;
+            let record = into_record(row)?;
             result.push(record);
         }
 
@@ -81,7 +82,7 @@ impl TopicRecord {
         let mut rows = stmt.query(params![id])?;
 
         if let Some(row) = rows.next()? {
-            let result = into_record(&row)?;
+            let result = into_record(row)?;
             return Ok(Some(result));
         }
 
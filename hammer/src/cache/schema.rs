@@ -0,0 +1,202 @@
+//! This module keeps the cache physical data model up to date.
+//!
+//! The schema version lives in `PRAGMA user_version`. Version `n` means the first `n` migrations have been applied so a
+//! brand new cache starts at 0 and any new migration must be appended to [`MIGRATIONS`], never edited in place.
+use anyhow::Result;
+use rusqlite::{params, Connection};
+use std::fmt;
+use thiserror::Error;
+
+/// The ordered list of migrations. The schema version is the number of migrations applied.
+const MIGRATIONS: [&str; 12] = [
+    include_str!("../sql/migrations/001_baseline.sql"),
+    include_str!("../sql/migrations/002_origin.sql"),
+    include_str!("../sql/migrations/003_endorsement_transition.sql"),
+    include_str!("../sql/migrations/004_case_study_use_case.sql"),
+    include_str!("../sql/migrations/005_rich_standard.sql"),
+    include_str!("../sql/migrations/006_search_index.sql"),
+    include_str!("../sql/migrations/007_dataset.sql"),
+    include_str!("../sql/migrations/008_process.sql"),
+    include_str!("../sql/migrations/009_sink_file.sql"),
+    include_str!("../sql/migrations/010_guidance_editorial.sql"),
+    include_str!("../sql/migrations/011_drop_sink_file.sql"),
+    include_str!("../sql/migrations/012_guidance_editorial_tables.sql"),
+];
+
+/// The schema version this build of the workbench expects.
+pub const VERSION: u32 = MIGRATIONS.len() as u32;
+
+#[derive(Debug, Error, PartialEq)]
+pub enum SchemaError {
+    #[error("The cache has schema version {found} but this workbench only understands up to version {supported}. Upgrade the workbench or remove the cache to rebuild it from source.")]
+    Newer { found: u32, supported: u32 },
+}
+
+/// The outcome of bringing a cache up to date.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Migration {
+    pub from: u32,
+    pub to: u32,
+}
+
+impl Migration {
+    pub fn is_noop(&self) -> bool {
+        self.from == self.to
+    }
+}
+
+impl fmt::Display for Migration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_noop() {
+            write!(f, "cache schema is up to date at version {}", self.to)
+        } else {
+            write!(
+                f,
+                "cache schema migrated from version {} to {}",
+                self.from, self.to
+            )
+        }
+    }
+}
+
+pub fn version(conn: &Connection) -> Result<u32> {
+    let version = conn.query_row("PRAGMA user_version", params![], |row| row.get(0))?;
+
+    Ok(version)
+}
+
+/// Applies every pending migration, each one in its own transaction.
+///
+/// Fails with [`SchemaError::Newer`] when the cache was written by a more recent workbench.
+pub fn migrate(conn: &mut Connection) -> Result<Migration> {
+    let from = version(conn)?;
+
+    if from > VERSION {
+        return Err(SchemaError::Newer {
+            found: from,
+            supported: VERSION,
+        }
+        .into());
+    }
+
+    for (index, migration) in MIGRATIONS.iter().enumerate().skip(from as usize) {
+        let tx = conn.transaction()?;
+
+        tx.execute_batch(migration)?;
+        tx.pragma_update(None, "user_version", &(index as u32 + 1))?;
+        tx.commit()?;
+    }
+
+    Ok(Migration { from, to: VERSION })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn migrate_fresh() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+        let actual = migrate(&mut conn)?;
+
+        assert_eq!(
+            actual,
+            Migration {
+                from: 0,
+                to: VERSION
+            }
+        );
+        assert_eq!(version(&conn)?, VERSION);
+        assert!(migrate(&mut conn)?.is_noop());
+
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_unversioned_baseline() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+
+        conn.execute_batch(MIGRATIONS[0])?;
+        conn.execute(
+            "INSERT INTO standard VALUES ('vapour', 'x', 'Vapour', NULL, 'exchange', 'https://spec.vapour.org/', NULL, 'dsa', '# Vapour')",
+            params![],
+        )?;
+
+        let actual = migrate(&mut conn)?;
+        let conforms_to: Option<String> = conn.query_row(
+            "SELECT conforms_to FROM standard WHERE id = 'vapour'",
+            params![],
+            |row| row.get(0),
+        )?;
+
+        assert_eq!(
+            actual,
+            Migration {
+                from: 0,
+                to: VERSION
+            }
+        );
+        assert_eq!(conforms_to, None);
+
+        Ok(())
+    }
+
+    #[test]
+    fn migrate_guidance_editorial() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+
+        for migration in &MIGRATIONS[..11] {
+            conn.execute_batch(migration)?;
+        }
+
+        conn.pragma_update(None, "user_version", &11)?;
+        conn.execute(
+            r#"INSERT INTO guidance VALUES ('graphql', 'x', NULL, 'dsa', 'draft', '2021-06-01', '2021-06-01', NULL, NULL, '# GraphQL', '{"authors":["Ada","Grace"],"purpose":"Choose an API","tags":["api"]}')"#,
+            params![],
+        )?;
+        migrate(&mut conn)?;
+
+        let purpose: Option<String> = conn.query_row(
+            "SELECT purpose FROM guidance_editorial WHERE guidance_id = 'graphql'",
+            params![],
+            |row| row.get(0),
+        )?;
+        let mut stmt = conn.prepare(
+            "SELECT field, ordinal, value FROM guidance_editorial_item ORDER BY field, ordinal",
+        )?;
+        let items = stmt
+            .query_map(params![], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<(String, u32, String)>, _>>()?;
+
+        assert_eq!(purpose, Some("Choose an API".to_string()));
+        assert_eq!(
+            items,
+            vec![
+                ("author".to_string(), 0, "Ada".to_string()),
+                ("author".to_string(), 1, "Grace".to_string()),
+                ("tag".to_string(), 0, "api".to_string()),
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn refuse_newer() -> Result<()> {
+        let mut conn = Connection::open_in_memory()?;
+
+        conn.pragma_update(None, "user_version", &(VERSION + 1))?;
+
+        let actual = migrate(&mut conn).unwrap_err();
+
+        assert_eq!(
+            actual.downcast_ref::<SchemaError>(),
+            Some(&SchemaError::Newer {
+                found: VERSION + 1,
+                supported: VERSION
+            })
+        );
+
+        Ok(())
+    }
+}
@@ -1,4 +1,3 @@
-pub use rusqlite::Transaction;
 use std::fmt;
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
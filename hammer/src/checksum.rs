@@ -48,6 +48,12 @@ pub struct Hasher {
     inner: blake3::Hasher,
 }
 
+impl Default for Hasher {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Hasher {
     pub fn new() -> Hasher {
         Hasher {
@@ -115,7 +121,7 @@ impl<T: Digest> Digest for Option<T> {
             }
 
             Some(v) => {
-                &v.digest(hasher);
+                v.digest(hasher);
             }
         }
     }
@@ -1,10 +1,16 @@
 use anyhow::Result;
 use clap::Clap;
-use log::info;
-use std::path::PathBuf;
+use log::{error, info, warn};
+use notify::{watcher, DebouncedEvent, RecursiveMode, Watcher};
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+use std::sync::mpsc::channel;
+use std::time::Duration;
 
 use crate::cache::{Cache, Strategy};
-use crate::{source, zola};
+use crate::source::{asset, UnknownFields};
+use crate::{api, integrity, linked_data, source, tabular, zola};
 
 type Achievement = String;
 
@@ -20,17 +26,333 @@ pub struct Cmd {
     /// The path to the sink to build into.
     #[clap(long, short = 'o', value_name = "path")]
     output_path: PathBuf,
+    /// The Zola static directory to copy the assets into, under `assets/`.
+    #[clap(long, value_name = "path")]
+    static_path: Option<PathBuf>,
+    /// The path to write the static JSON API into.
+    #[clap(long, value_name = "path")]
+    api_path: Option<PathBuf>,
+    /// The path to write the CSV tables and their CSVW descriptors into.
+    #[clap(long, value_name = "path")]
+    csv_path: Option<PathBuf>,
+    /// The path to write the JSON-LD documents and the DCAT catalogue into.
+    #[clap(long, value_name = "path", requires = "base-url")]
+    linked_data_path: Option<PathBuf>,
+    /// The path to write the catalogue graph into, as Turtle and N-Triples.
+    #[clap(long, value_name = "path", requires = "base-url")]
+    rdf_path: Option<PathBuf>,
+    /// The URL the linked data is published under, used to mint the IRI of every resource.
+    #[clap(long, value_name = "url")]
+    base_url: Option<String>,
+    /// The path to write the site search index to, e.g. `static/search.json`.
+    #[clap(long, value_name = "path")]
+    search_index: Option<PathBuf>,
+    /// Refuses to write any output if a resource references an unknown resource.
+    #[clap(long)]
+    deny_dangling: bool,
+    /// What to do with source properties that are not part of the model: ignore, warn or deny.
+    #[clap(long, value_name = "mode", default_value = "ignore")]
+    unknown_fields: UnknownFields,
+    /// Keeps running, rebuilding the output every time the source changes.
+    #[clap(long)]
+    watch: bool,
 }
 
 impl Cmd {
     pub fn run(&self) -> Result<Achievement> {
         let mut cache = Cache::connect_with_strategy(self.cache_path.clone())?;
+        let outcome = self.build(&mut cache);
+
+        if !self.watch {
+            return outcome;
+        }
+
+        // A broken source should not stop the watch, the next change might fix it.
+        if let Err(err) = outcome {
+            eprintln!("{:?}", err);
+        }
+
+        self.watch(&mut cache)
+    }
+
+    fn build(&self, cache: &mut Cache) -> Result<Achievement> {
+        cache.start_session();
 
         info!("Reading source");
-        source::read(&self.input_path, &mut cache)?;
+        let diagnostics = source::read(&self.input_path, cache, self.unknown_fields)?;
+
+        if diagnostics.has_errors() {
+            return Err(diagnostics.into());
+        }
+
+        for diagnostic in diagnostics.iter() {
+            warn!("{}", diagnostic);
+        }
+
+        info!("Pruning stale resources");
+        let pruned = cache.prune()?;
+        cache.drain_trail()?;
+
+        info!("Checking references");
+        let dangling = integrity::check(cache)?;
+
+        if self.deny_dangling && !dangling.is_empty() {
+            return Err(dangling.into());
+        }
+
+        for diagnostic in dangling.iter() {
+            warn!("{}", diagnostic);
+        }
+
         info!("Writing zola");
-        zola::write(&self.output_path, &mut cache)?;
+        let summary = zola::write(&self.output_path, cache)?;
+        let mut lines = vec![summary.to_string()];
+
+        lines.extend(self.write_assets(cache)?);
+        lines.extend(self.write_api(cache)?);
+        lines.extend(self.write_csv(cache)?);
+        lines.extend(self.write_linked_data(cache)?);
+        lines.extend(self.write_rdf(cache)?);
+        lines.extend(self.write_search_index(cache)?);
+
+        for record in pruned {
+            lines.push(format!("pruned {}", record));
+        }
+
+        Ok(lines.join("\n"))
+    }
+
+    fn watch(&self, cache: &mut Cache) -> Result<Achievement> {
+        let (tx, rx) = channel();
+        let mut watcher = watcher(tx, Duration::from_millis(200))?;
+        let root = fs::canonicalize(&self.input_path)?;
+
+        watcher.watch(&self.input_path, RecursiveMode::Recursive)?;
+        println!("Watching {} for changes", self.input_path.display());
+
+        loop {
+            let mut touched = BTreeSet::new();
+            let mut rescan = false;
+
+            for event in Some(rx.recv()?).into_iter().chain(rx.try_iter()) {
+                match event {
+                    DebouncedEvent::Create(path)
+                    | DebouncedEvent::Write(path)
+                    | DebouncedEvent::Remove(path) => {
+                        touched.insert(self.source_path(&root, &path));
+                    }
+                    DebouncedEvent::Rename(from, to) => {
+                        touched.insert(self.source_path(&root, &from));
+                        touched.insert(self.source_path(&root, &to));
+                    }
+                    DebouncedEvent::Rescan => rescan = true,
+                    DebouncedEvent::Error(err, path) => {
+                        warn!("watch error {:?}: {}", path, err);
+                    }
+                    _ => (),
+                }
+            }
+
+            // Pages link to assets by checksum so a changed asset needs every page to be read again.
+            let outcome = if rescan || touched.iter().any(|path| asset::is_asset_path(path)) {
+                self.build(cache)
+            } else if touched.iter().any(|path| is_source(path)) {
+                self.rebuild(cache, &touched)
+            } else {
+                continue;
+            };
+
+            match outcome {
+                Ok(msg) => println!("{}", msg),
+                Err(err) => error!("{:?}", err),
+            }
+        }
+    }
+
+    /// Re-reads the touched source files and writes only the pages that changed as a result.
+    fn rebuild(&self, cache: &mut Cache, touched: &BTreeSet<PathBuf>) -> Result<Achievement> {
+        for path in touched.iter().filter(|path| is_source(path)) {
+            if path.exists() {
+                info!("Refreshing {}", path.display());
+                let diagnostics =
+                    source::refresh(&self.input_path, path, cache, self.unknown_fields)?;
+
+                if !diagnostics.is_empty() {
+                    eprintln!("{}", diagnostics);
+                }
+            } else {
+                info!("Forgetting {}", path.display());
+                source::forget(&self.input_path, path, cache)?;
+            }
+        }
+
+        let dangling = integrity::check(cache)?;
+
+        if self.deny_dangling && !dangling.is_empty() {
+            return Err(dangling.into());
+        }
+
+        for diagnostic in dangling.iter() {
+            warn!("{}", diagnostic);
+        }
+
+        let summary = zola::write(&self.output_path, cache)?;
+        let mut lines = vec![summary.to_string()];
+
+        lines.extend(self.write_assets(cache)?);
+        lines.extend(self.write_api(cache)?);
+        lines.extend(self.write_csv(cache)?);
+        lines.extend(self.write_linked_data(cache)?);
+        lines.extend(self.write_rdf(cache)?);
+        lines.extend(self.write_search_index(cache)?);
+
+        Ok(lines.join("\n"))
+    }
+
+    fn write_assets(&self, cache: &mut Cache) -> Result<Option<String>> {
+        let path = match &self.static_path {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        info!("Writing assets");
+        let summary = zola::write_assets(path, cache)?;
+
+        Ok(Some(format!("assets: {}", summary)))
+    }
+
+    fn write_api(&self, cache: &mut Cache) -> Result<Option<String>> {
+        let path = match &self.api_path {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        info!("Writing JSON API");
+        let summary = api::write(path, cache)?;
+
+        Ok(Some(format!("api: {}", summary)))
+    }
+
+    fn write_csv(&self, cache: &mut Cache) -> Result<Option<String>> {
+        let path = match &self.csv_path {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        info!("Writing CSV");
+        let summary = tabular::write(path, cache)?;
+
+        Ok(Some(format!("csv: {}", summary)))
+    }
+
+    fn write_linked_data(&self, cache: &mut Cache) -> Result<Option<String>> {
+        let (path, base_url) = match (&self.linked_data_path, &self.base_url) {
+            (Some(path), Some(base_url)) => (path, base_url),
+            _ => return Ok(None),
+        };
+
+        info!("Writing linked data");
+        let summary = linked_data::write(path, cache, base_url)?;
+
+        Ok(Some(format!("linked data: {}", summary)))
+    }
+
+    fn write_rdf(&self, cache: &mut Cache) -> Result<Option<String>> {
+        let (path, base_url) = match (&self.rdf_path, &self.base_url) {
+            (Some(path), Some(base_url)) => (path, base_url),
+            _ => return Ok(None),
+        };
+
+        info!("Writing RDF");
+        let summary = linked_data::write_rdf(path, cache, base_url)?;
+
+        Ok(Some(format!("rdf: {}", summary)))
+    }
+
+    fn write_search_index(&self, cache: &mut Cache) -> Result<Option<String>> {
+        let path = match &self.search_index {
+            Some(path) => path,
+            None => return Ok(None),
+        };
+
+        info!("Indexing for search");
+        let outcome = if zola::search_index::write(path, cache)? {
+            "written"
+        } else {
+            "unchanged"
+        };
+
+        Ok(Some(format!("search index {}", outcome)))
+    }
+
+    /// Maps a path reported by the watcher to the path the source was originally read from.
+    fn source_path(&self, root: &Path, path: &Path) -> PathBuf {
+        match path.strip_prefix(root) {
+            Ok(relative) => self.input_path.join(relative),
+            Err(_) => path.to_path_buf(),
+        }
+    }
+}
+
+fn is_source(path: &Path) -> bool {
+    let is_hidden = path.components().any(|component| {
+        matches!(component, Component::Normal(name) if name.to_string_lossy().starts_with('.'))
+    });
+    let is_known = path
+        .extension()
+        .map(|ext| ext == "md" || ext == "json" || ext == "csv")
+        .unwrap_or(false);
+
+    !is_hidden && (is_known || asset::is_asset_path(path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use temp_testdir::TempDir;
+
+    #[test]
+    fn prune_between_builds() -> Result<()> {
+        let temp = TempDir::default();
+        let input_path = temp.as_ref().join("source");
+        let topic = |id: &str| {
+            format!(
+                "---\ntype: topic\nidentifier: {}\nname: {}\ntheme: other\nordinal: 1\n---\n",
+                id, id
+            )
+        };
+
+        fs::create_dir_all(&input_path)?;
+        fs::write(input_path.join("exchange.md"), topic("exchange"))?;
+        fs::write(input_path.join("steam.md"), topic("steam"))?;
+
+        let cmd = Cmd {
+            cache_path: Strategy::Memory,
+            input_path: input_path.clone(),
+            output_path: temp.as_ref().join("content"),
+            static_path: None,
+            api_path: None,
+            csv_path: None,
+            linked_data_path: None,
+            rdf_path: None,
+            base_url: None,
+            search_index: None,
+            deny_dangling: false,
+            unknown_fields: UnknownFields::Ignore,
+            watch: false,
+        };
+        let mut cache = Cache::connect_with_strategy(Strategy::Memory)?;
+
+        let first = cmd.build(&mut cache)?;
+        assert!(!first.contains("pruned"));
+
+        fs::remove_file(input_path.join("steam.md"))?;
+
+        let second = cmd.build(&mut cache)?;
+        assert!(second.contains("pruned topic `steam`"));
+        assert!(!second.contains("pruned topic `exchange`"));
 
-        Ok("".into())
+        Ok(())
     }
 }
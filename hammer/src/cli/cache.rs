@@ -0,0 +1,44 @@
+use anyhow::Result;
+use clap::Clap;
+
+use crate::cache::{Cache, Strategy};
+
+type Achievement = String;
+
+/// Manages the cache.
+#[derive(Debug, Clap)]
+pub struct Cmd {
+    #[clap(subcommand)]
+    subcommand: Subcommand,
+}
+
+#[derive(Debug, Clap)]
+enum Subcommand {
+    Migrate(MigrateCmd),
+}
+
+/// Brings the cache schema up to the version expected by this workbench.
+#[derive(Debug, Clap)]
+pub struct MigrateCmd {
+    /// Cache path.
+    #[clap(long, value_name = "path")]
+    cache_path: Strategy,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<Achievement> {
+        match &self.subcommand {
+            Subcommand::Migrate(cmd) => cmd.run(),
+        }
+    }
+}
+
+impl MigrateCmd {
+    pub fn run(&self) -> Result<Achievement> {
+        let cache = Cache::connect_with_strategy(self.cache_path.clone())?;
+
+        cache.disconnect()?;
+
+        Ok(cache.migration.to_string())
+    }
+}
@@ -0,0 +1,43 @@
+use anyhow::Result;
+use clap::Clap;
+use log::info;
+use std::path::PathBuf;
+
+use crate::cache::{Cache, Strategy};
+use crate::source::UnknownFields;
+use crate::{integrity, source};
+
+type Achievement = String;
+
+/// Checks the source is valid without writing any output.
+#[derive(Debug, Clap)]
+pub struct Cmd {
+    /// The path to the source to check.
+    #[clap(long, short = 'i', value_name = "path")]
+    input_path: PathBuf,
+    /// What to do with source properties that are not part of the model: ignore, warn or deny.
+    #[clap(long, value_name = "mode", default_value = "ignore")]
+    unknown_fields: UnknownFields,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<Achievement> {
+        let mut cache = Cache::connect_with_strategy(Strategy::Memory)?;
+
+        info!("Checking source");
+        let mut diagnostics = source::read(&self.input_path, &mut cache, self.unknown_fields)?;
+
+        info!("Checking references");
+        diagnostics.extend(integrity::check(&mut cache)?);
+
+        if diagnostics.has_errors() {
+            return Err(diagnostics.into());
+        }
+
+        if !diagnostics.is_empty() {
+            return Ok(diagnostics.to_string());
+        }
+
+        Ok("no problems found".into())
+    }
+}
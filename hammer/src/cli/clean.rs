@@ -20,10 +20,8 @@ pub struct Cmd {
 
 impl Cmd {
     pub fn run(&self) -> Result<Achievement> {
-        if let Some(ref cache) = self.cache_path {
-            if let Strategy::Disk(ref path) = cache {
-                fs::remove_file(path)?;
-            }
+        if let Some(Strategy::Disk(ref path)) = self.cache_path {
+            fs::remove_file(path)?;
         }
 
         fs::remove_dir_all(&self.output_path)?;
@@ -0,0 +1,35 @@
+use anyhow::Result;
+use clap::Clap;
+use log::info;
+use std::path::PathBuf;
+
+use crate::{import, sink};
+
+type Achievement = String;
+
+/// Imports a Zola content directory back into source Markdown.
+#[derive(Debug, Clap)]
+pub struct Cmd {
+    /// The path to the Zola content to import.
+    #[clap(long, short = 'i', value_name = "path")]
+    input_path: PathBuf,
+    /// The path to the source to write into. Existing files not imported are left untouched.
+    #[clap(long, short = 'o', value_name = "path")]
+    output_path: PathBuf,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<Achievement> {
+        info!("Reading zola");
+        let (pages, diagnostics) = import::read(&self.input_path)?;
+
+        info!("Writing source");
+        let summary = sink::merge(&self.output_path, &pages)?;
+
+        if diagnostics.has_errors() {
+            return Err(anyhow::Error::from(diagnostics).context(summary.to_string()));
+        }
+
+        Ok(summary.to_string())
+    }
+}
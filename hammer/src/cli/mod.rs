@@ -1,2 +1,7 @@
 pub mod build;
+pub mod cache;
+pub mod check;
 pub mod clean;
+pub mod import;
+pub mod reviews;
+pub mod search;
@@ -0,0 +1,47 @@
+use anyhow::Result;
+use chrono::{NaiveDate, Utc};
+use clap::Clap;
+use log::info;
+use std::path::PathBuf;
+
+use crate::cache::{Cache, Strategy};
+use crate::review::{self, Format};
+use crate::source::{self, UnknownFields};
+
+type Achievement = String;
+
+/// Reports the standards with an overdue or upcoming review, grouped by maintainer.
+#[derive(Debug, Clap)]
+pub struct Cmd {
+    /// The path to the source to read from.
+    #[clap(long, short = 'i', value_name = "path")]
+    input_path: PathBuf,
+    /// The date to compare against, as YYYY-MM-DD. Defaults to today.
+    #[clap(long, value_name = "date")]
+    date: Option<NaiveDate>,
+    /// The number of days ahead to consider a standard due.
+    #[clap(long, value_name = "days", default_value = "30")]
+    within: i64,
+    /// The output format: table, csv or json.
+    #[clap(long, short = 'f', value_name = "format", default_value = "table")]
+    format: Format,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<Achievement> {
+        let mut cache = Cache::connect_with_strategy(Strategy::Memory)?;
+        let today = self.date.unwrap_or_else(|| Utc::today().naive_utc());
+
+        info!("Reading source");
+        let diagnostics = source::read(&self.input_path, &mut cache, UnknownFields::Ignore)?;
+
+        if !diagnostics.is_empty() {
+            return Err(diagnostics.into());
+        }
+
+        info!("Collecting reviews due by {}", today);
+        let reviews = review::collect(&mut cache, today, self.within)?;
+
+        review::render(&reviews, &self.format)
+    }
+}
@@ -0,0 +1,52 @@
+use anyhow::Result;
+use clap::Clap;
+use log::info;
+use std::path::PathBuf;
+
+use crate::cache::{Cache, Strategy};
+use crate::search;
+use crate::source::{self, UnknownFields};
+
+type Achievement = String;
+
+/// Searches the body of standards, guidance, topics and themes.
+#[derive(Debug, Clap)]
+pub struct Cmd {
+    /// The terms to look for. All of them must match.
+    #[clap(value_name = "query")]
+    query: String,
+    /// The path to the source to read from. If not provided it searches the cache as it is.
+    #[clap(long, short = 'i', value_name = "path")]
+    input_path: Option<PathBuf>,
+    /// Cache path.
+    #[clap(long, value_name = "path", default_value = ":memory:")]
+    cache_path: Strategy,
+    /// Pass the query untouched to the full-text engine, e.g. `address OR postcode`.
+    #[clap(long)]
+    raw: bool,
+    /// The maximum number of matches to report.
+    #[clap(long, value_name = "number", default_value = "20")]
+    limit: u32,
+}
+
+impl Cmd {
+    pub fn run(&self) -> Result<Achievement> {
+        let mut cache = Cache::connect_with_strategy(self.cache_path.clone())?;
+
+        if let Some(input_path) = &self.input_path {
+            info!("Reading source");
+            let diagnostics = source::read(input_path, &mut cache, UnknownFields::Ignore)?;
+
+            if !diagnostics.is_empty() {
+                return Err(diagnostics.into());
+            }
+        }
+
+        info!("Searching for {}", &self.query);
+        let hits = search::search(&mut cache, &self.query, self.raw, self.limit)?;
+
+        cache.disconnect()?;
+
+        Ok(search::render(&hits))
+    }
+}
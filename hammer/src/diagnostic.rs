@@ -0,0 +1,297 @@
+//! This module contains the diagnostics collected whilst processing the source.
+//!
+//! Diagnostics are rendered in the style of a compiler: the message, the file and, when known, the line and column
+//! with a snippet of the offending source line.
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::fmt;
+use std::path::{Path, PathBuf};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+/// The position of a problem within a source file.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Location {
+    /// The 1-based line within the full source file.
+    pub line: usize,
+    /// The 1-based column within the line.
+    pub column: usize,
+    /// The path to the offending property, e.g. `endorsement_state.status`.
+    pub key: Option<String>,
+    /// The source line the problem was found in.
+    pub snippet: String,
+}
+
+impl Location {
+    /// Builds the location for the given 1-based line and column of the given source text.
+    pub fn new(source: &str, line: usize, column: usize, key: Option<String>) -> Self {
+        let snippet = source
+            .lines()
+            .nth(line.saturating_sub(1))
+            .unwrap_or_default()
+            .to_string();
+
+        Self {
+            line,
+            column,
+            key,
+            snippet,
+        }
+    }
+}
+
+/// An error that knows where in the source text it was found.
+#[derive(Debug, Clone)]
+pub struct LocatedError {
+    pub message: String,
+    pub location: Location,
+}
+
+impl fmt::Display for LocatedError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} at line {} column {}",
+            self.message, self.location.line, self.location.column
+        )
+    }
+}
+
+impl Error for LocatedError {}
+
+/// A problem found in a single source file.
+#[derive(Debug, Clone)]
+pub struct Diagnostic {
+    pub path: PathBuf,
+    pub message: String,
+    pub severity: Severity,
+    pub location: Option<Location>,
+}
+
+impl Diagnostic {
+    pub fn new(path: &Path, message: &str) -> Self {
+        Self {
+            path: path.into(),
+            message: message.into(),
+            severity: Severity::Error,
+            location: None,
+        }
+    }
+
+    pub fn warning(path: &Path, message: &str) -> Self {
+        Self {
+            severity: Severity::Warning,
+            ..Self::new(path, message)
+        }
+    }
+}
+
+impl fmt::Display for Diagnostic {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}: {}", self.path.display(), self.message)
+    }
+}
+
+/// The set of problems found whilst processing the source.
+#[derive(Debug, Clone, Default)]
+pub struct Diagnostics {
+    list: Vec<Diagnostic>,
+}
+
+impl Diagnostics {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn push(&mut self, diagnostic: Diagnostic) {
+        self.list.push(diagnostic);
+    }
+
+    /// Records the given error, including its chain of causes, against the given path.
+    ///
+    /// Errors carrying a [`LocatedError`] keep their location.
+    pub fn push_error(&mut self, path: &Path, error: &anyhow::Error) {
+        match error.downcast_ref::<LocatedError>() {
+            Some(located) => self.push(Diagnostic {
+                location: Some(located.location.clone()),
+                ..Diagnostic::new(path, &located.message)
+            }),
+            None => self.push(Diagnostic::new(path, &format!("{:#}", error))),
+        }
+    }
+
+    pub fn extend(&mut self, other: Diagnostics) {
+        self.list.extend(other.list);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.list.is_empty()
+    }
+
+    /// Whether any of the diagnostics is an error as opposed to a warning.
+    pub fn has_errors(&self) -> bool {
+        self.list.iter().any(|d| d.severity == Severity::Error)
+    }
+
+    pub fn len(&self) -> usize {
+        self.list.len()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Diagnostic> {
+        self.list.iter()
+    }
+
+    /// Groups the diagnostics by the file they were found in.
+    pub fn by_path(&self) -> BTreeMap<&Path, Vec<&Diagnostic>> {
+        let mut groups: BTreeMap<&Path, Vec<&Diagnostic>> = BTreeMap::new();
+
+        for diagnostic in &self.list {
+            groups
+                .entry(diagnostic.path.as_path())
+                .or_default()
+                .push(diagnostic);
+        }
+
+        groups
+    }
+}
+
+impl fmt::Display for Diagnostics {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let groups = self.by_path();
+
+        for list in groups.values() {
+            for diagnostic in list {
+                render(f, diagnostic)?;
+                writeln!(f)?;
+            }
+        }
+
+        write!(
+            f,
+            "{} {} found in {} {}.",
+            self.len(),
+            if self.len() == 1 {
+                "problem"
+            } else {
+                "problems"
+            },
+            groups.len(),
+            if groups.len() == 1 { "file" } else { "files" },
+        )
+    }
+}
+
+/// Renders a single diagnostic in the style of a compiler.
+///
+/// ```text
+/// error: unknown variant `endorsd`
+///   --> standards/vapour.md:9:13
+///    |
+///  9 |     status: endorsd
+///    |             ^ in `endorsement_state.status`
+/// ```
+fn render(f: &mut fmt::Formatter<'_>, diagnostic: &Diagnostic) -> fmt::Result {
+    writeln!(f, "{}: {}", diagnostic.severity, diagnostic.message)?;
+
+    match &diagnostic.location {
+        None => writeln!(f, "  --> {}", diagnostic.path.display()),
+        Some(location) => {
+            let gutter = " ".repeat(location.line.to_string().len());
+
+            writeln!(
+                f,
+                "{}--> {}:{}:{}",
+                gutter,
+                diagnostic.path.display(),
+                location.line,
+                location.column
+            )?;
+            writeln!(f, "{} |", gutter)?;
+            writeln!(f, "{} | {}", location.line, location.snippet)?;
+            write!(
+                f,
+                "{} | {}^",
+                gutter,
+                " ".repeat(location.column.saturating_sub(1))
+            )?;
+
+            match &location.key {
+                Some(key) => writeln!(f, " in `{}`", key),
+                None => writeln!(f),
+            }
+        }
+    }
+}
+
+impl Error for Diagnostics {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn group_by_path() {
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push(Diagnostic::new(Path::new("b.md"), "missing field `name`"));
+        diagnostics.push(Diagnostic::new(Path::new("a.md"), "missing frontmatter"));
+        diagnostics.push(Diagnostic::new(Path::new("b.md"), "unknown status"));
+        diagnostics.push(Diagnostic::warning(
+            Path::new("b.md"),
+            "unknown field `reveiw_date`",
+        ));
+
+        let expected = r#"error: missing frontmatter
+  --> a.md
+
+error: missing field `name`
+  --> b.md
+
+error: unknown status
+  --> b.md
+
+warning: unknown field `reveiw_date`
+  --> b.md
+
+4 problems found in 2 files."#;
+
+        assert_eq!(diagnostics.to_string(), expected);
+        assert!(diagnostics.has_errors());
+    }
+
+    #[test]
+    fn located_error() {
+        let source = "---\ntype: standard\nendorsement_state:\n    status: endorsd\n---\n";
+        let error = LocatedError {
+            message: "unknown variant `endorsd`".into(),
+            location: Location::new(source, 4, 13, Some("endorsement_state.status".into())),
+        };
+        let mut diagnostics = Diagnostics::new();
+        diagnostics.push_error(Path::new("vapour.md"), &error.into());
+
+        let expected = r#"error: unknown variant `endorsd`
+ --> vapour.md:4:13
+  |
+4 |     status: endorsd
+  |             ^ in `endorsement_state.status`
+
+1 problem found in 1 file."#;
+
+        assert_eq!(diagnostics.to_string(), expected);
+    }
+}
@@ -0,0 +1,453 @@
+//! This module turns a Zola content directory back into source Markdown.
+//!
+//! Pages keep the identifiers found in the source, even those not resolved to a resource, so every resource can be
+//! recovered. Each is rendered with a canonical YAML frontmatter and read back to make sure it keeps its checksum.
+use anyhow::{bail, Result};
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+use serde_yaml::{Mapping, Value};
+use std::convert::TryFrom;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use walkdir::WalkDir;
+
+use crate::checksum::Checksum;
+use crate::diagnostic::Diagnostics;
+use crate::resource::ResourceType;
+use crate::sink::Page;
+use crate::source::{self, parse_frontmatter};
+use crate::zola;
+
+/// The part of a Zola page needed to tell which resource it holds.
+#[derive(Debug, Deserialize)]
+struct Probe {
+    #[serde(default)]
+    template: Option<String>,
+}
+
+/// Reads every page in the given Zola content directory and renders the source file for each, relative to the
+/// source directory.
+///
+/// A page that can't be imported, e.g. a standard whose topic was never published, is reported without stopping the
+/// rest.
+pub fn read(content_dir: &Path) -> Result<(Vec<Page>, Diagnostics)> {
+    let mut pages = Vec::new();
+    let mut diagnostics = Diagnostics::new();
+
+    for entry in WalkDir::new(content_dir).sort_by_file_name() {
+        let entry = entry?;
+        let path = entry.path();
+
+        if !entry.file_type().is_file() || path.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+
+        match import_page(path, &fs::read_to_string(path)?) {
+            Ok(Some(page)) => pages.push(page),
+            Ok(None) => (),
+            Err(err) => diagnostics.push_error(path, &err),
+        }
+    }
+
+    Ok((pages, diagnostics))
+}
+
+/// Renders the source file for the given Zola page, if it holds a resource that can be imported.
+fn import_page(path: &Path, blob: &str) -> Result<Option<Page>> {
+    let (probe, _): (Probe, &str) = parse_frontmatter(blob)?;
+
+    let page = match probe.template.as_deref() {
+        Some("standard.html") => {
+            let page = zola::Standard::from_str(blob)?;
+            let resource = source::Standard::try_from(&page)?;
+            let text = render(
+                ResourceType::Standard,
+                &resource.metadata,
+                &resource.content,
+            )?;
+
+            verify(resource.id(), &resource.checksum(), &text, |text| {
+                Ok(source::Standard::from_str(text)?.checksum())
+            })?;
+
+            Page::new(
+                PathBuf::from(format!("standards/{}.md", resource.id())),
+                text,
+            )
+        }
+        Some("guidance.html") => {
+            let page = zola::Guidance::from_str(blob)?;
+            let resource = source::Guidance::from(&page);
+            let text = render(
+                ResourceType::Guidance,
+                &resource.metadata,
+                &resource.content,
+            )?;
+
+            verify(resource.id(), &resource.checksum(), &text, |text| {
+                Ok(source::Guidance::from_str(text)?.checksum())
+            })?;
+
+            Page::new(
+                PathBuf::from(format!("guidance/{}.md", resource.id())),
+                text,
+            )
+        }
+        Some("theme.html") => {
+            let page = zola::Theme::from_str(blob)?;
+            let resource = source::Theme::from(&page);
+            let text = render(ResourceType::Theme, &resource.metadata, &resource.content)?;
+
+            verify(resource.id(), &resource.checksum(), &text, |text| {
+                Ok(source::Theme::from_str(text)?.checksum())
+            })?;
+
+            Page::new(PathBuf::from(format!("themes/{}.md", resource.id())), text)
+        }
+        Some("topic.html") => {
+            // Topics are published inside the directory of their theme.
+            let theme_id = match path.parent().and_then(|parent| parent.file_name()) {
+                Some(name) => name.to_string_lossy(),
+                None => bail!("the topic at {} is not within a theme", path.display()),
+            };
+            let page = zola::Topic::from_str(blob)?;
+            let resource = page.to_source(&theme_id);
+            let text = render(ResourceType::Topic, &resource.metadata, &resource.content)?;
+
+            verify(resource.id(), &resource.checksum(), &text, |text| {
+                Ok(source::Topic::from_str(text)?.checksum())
+            })?;
+
+            Page::new(
+                PathBuf::from(format!("themes/topics/{}.md", resource.id())),
+                text,
+            )
+        }
+        Some(template) => {
+            warn!(
+                "Skipping {} as '{}' is not an importable template",
+                path.display(),
+                template
+            );
+
+            return Ok(None);
+        }
+        None => return Ok(None),
+    };
+
+    info!("Imported {}", path.display());
+
+    Ok(Some(page))
+}
+
+/// Renders a source file with the given metadata as a YAML frontmatter, led by the resource type.
+fn render<T: Serialize>(
+    resource_type: ResourceType,
+    metadata: &T,
+    content: &str,
+) -> Result<String> {
+    let mut frontmatter = String::new();
+
+    match serde_yaml::to_value(metadata)? {
+        Value::Mapping(mapping) => write_mapping(&mapping, 0, &mut frontmatter)?,
+        _ => bail!("the metadata for a {} is not a mapping", resource_type),
+    }
+
+    Ok(format!(
+        "---\ntype: {}\n{}---\n{}",
+        resource_type, frontmatter, content
+    ))
+}
+
+/// Writes one `key: value` line per entry, nesting collections in the block style used across the source.
+fn write_mapping(mapping: &Mapping, indent: usize, out: &mut String) -> Result<()> {
+    for (key, value) in mapping {
+        out.push_str(&" ".repeat(indent));
+        out.push_str(&scalar(key)?);
+        out.push(':');
+        write_value(value, indent, out)?;
+    }
+
+    Ok(())
+}
+
+fn write_sequence(sequence: &[Value], indent: usize, out: &mut String) -> Result<()> {
+    for item in sequence {
+        out.push_str(&" ".repeat(indent));
+        out.push('-');
+
+        match item {
+            // The first entry goes on the same line as the dash.
+            Value::Mapping(mapping) if !mapping.is_empty() => {
+                let mut nested = String::new();
+
+                write_mapping(mapping, indent + 2, &mut nested)?;
+                out.push(' ');
+                out.push_str(&nested[indent + 2..]);
+            }
+            _ => write_value(item, indent, out)?,
+        }
+    }
+
+    Ok(())
+}
+
+/// Writes the value following a `key:` or a `-`, with any block on the lines after.
+fn write_value(value: &Value, indent: usize, out: &mut String) -> Result<()> {
+    match value {
+        Value::Mapping(mapping) if !mapping.is_empty() => {
+            out.push('\n');
+            write_mapping(mapping, indent + 2, out)
+        }
+        Value::Sequence(sequence) if !sequence.is_empty() => {
+            out.push('\n');
+            write_sequence(sequence, indent + 2, out)
+        }
+        Value::String(text) if is_literal(text) => {
+            out.push_str(" |\n");
+            out.push_str(&literal(text, indent + 2));
+            Ok(())
+        }
+        _ => {
+            out.push(' ');
+            out.push_str(&scalar(value)?);
+            out.push('\n');
+            Ok(())
+        }
+    }
+}
+
+/// Renders a scalar plain when it reads back as the same value, leaving the quoting to `serde_yaml` otherwise.
+fn scalar(value: &Value) -> Result<String> {
+    if let Value::String(text) = value {
+        if serde_yaml::from_str::<Value>(text).ok().as_ref() == Some(value) {
+            return Ok(text.clone());
+        }
+    }
+
+    let text = serde_yaml::to_string(value)?;
+
+    Ok(text.trim_start_matches("---").trim().to_string())
+}
+
+/// Indents every line of the text to be the body of a literal block.
+fn literal(text: &str, indent: usize) -> String {
+    text.lines()
+        .map(|line| {
+            if line.is_empty() {
+                "\n".to_string()
+            } else {
+                format!("{}{}\n", " ".repeat(indent), line)
+            }
+        })
+        .collect()
+}
+
+/// Whether the text reads back the same from a literal block, e.g. a Markdown list spanning a few lines.
+fn is_literal(text: &str) -> bool {
+    if !text.contains('\n') {
+        return false;
+    }
+
+    let block = format!("value: |\n{}", literal(text, 2));
+
+    match serde_yaml::from_str::<Value>(&block) {
+        Ok(Value::Mapping(mapping)) => {
+            mapping.get(&Value::from("value")) == Some(&Value::from(text))
+        }
+        _ => false,
+    }
+}
+
+/// Reads back the rendered source text to check it matches the resource it was rendered from.
+fn verify<F>(id: &str, expected: &Checksum, text: &str, read: F) -> Result<()>
+where
+    F: Fn(&str) -> Result<Checksum>,
+{
+    let actual = read(text)?;
+
+    if &actual != expected {
+        bail!(
+            "`{}` doesn't survive the round trip: its checksum changed from {} to {}",
+            id,
+            expected,
+            actual
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cache::Cache;
+    use crate::resource::Resource;
+    use crate::sink;
+    use crate::source::UnknownFields;
+    use std::collections::BTreeMap;
+    use temp_testdir::TempDir;
+
+    static STANDARD: &str = r#"---
+type: standard
+identifier: vapour
+name: Vapour
+topic: exchange
+specification: https://spec.vapour.org/
+licence: ogl
+maintainer: data-standards-authority
+endorsement_state:
+  status: identified
+  start_date: 2021-06-01
+  review_date: 2021-06-01
+---
+This standard will give you no overhead."#;
+
+    static GUIDANCE: &str = r#"---
+type: guidance
+identifier: steam-in-practice
+description: Steam without the heat.
+maintainer: data-standards-authority
+status: draft
+creation_date: 2021-04-01
+update_date: 2021-05-14
+standards:
+  - vapour
+authors:
+  - Ada Lovelace
+purpose: |
+  - when to use steam
+  - how to keep it warm
+---
+# Steam in practice
+
+How to make the most of it.
+"#;
+
+    #[test]
+    fn round_trip() -> Result<()> {
+        let licence =
+            r#"{"id": "ogl", "name": "Open Government Licence", "url": "https://ogl.gov.uk"}"#;
+        let org = r#"{"id": "data-standards-authority", "name": "Data Standards Authority", "url": "https://dsa.gov.uk"}"#;
+        let topic = "---\ntype: topic\nidentifier: exchange\nname: Exchange\ntheme: other\nordinal: 1\n---\nMoving data around.";
+        let mut cache = Cache::connect(":memory:")?;
+        let standard = source::Standard::from_str(STANDARD)?;
+        let guidance = source::Guidance::from_str(GUIDANCE)?;
+        let topic = source::Topic::from_str(topic)?;
+
+        cache.add(&source::Organisation::from_str(org)?)?;
+        cache.add(&source::Licence::from_str(licence)?)?;
+        cache.add(&standard)?;
+        cache.add(&guidance)?;
+        cache.add(&topic)?;
+
+        let standard_page: zola::Standard = cache.get(standard.id())?.unwrap();
+        let guidance_page: zola::Guidance = cache.get(guidance.id())?.unwrap();
+        let topic_page: zola::Topic = cache.get(topic.id())?.unwrap();
+
+        let actual = import_page(Path::new("standards/vapour.md"), &standard_page.to_string())?
+            .expect("standard to be imported");
+
+        assert_eq!(actual.path, PathBuf::from("standards/vapour.md"));
+        assert_eq!(actual.content, STANDARD);
+
+        let actual = import_page(
+            Path::new("guidance/steam-in-practice.md"),
+            &guidance_page.to_string(),
+        )?
+        .expect("guidance to be imported");
+
+        assert_eq!(actual.content, GUIDANCE);
+
+        let actual = import_page(
+            Path::new("themes/other/exchange.md"),
+            &topic_page.to_string(),
+        )?
+        .expect("topic to be imported");
+
+        assert_eq!(actual.path, PathBuf::from("themes/topics/exchange.md"));
+        assert_eq!(
+            source::Topic::from_str(&actual.content)?.checksum(),
+            topic.checksum()
+        );
+
+        Ok(())
+    }
+
+    /// The checksum of every resource that can be imported, keyed by resource type and identifier.
+    fn checksums(cache: &mut Cache) -> Result<BTreeMap<(String, String), String>> {
+        let tx = cache.transaction()?;
+        let mut result = BTreeMap::new();
+
+        for table in &["standard", "guidance", "theme", "topic"] {
+            let mut stmt = tx.prepare(&format!("SELECT id, checksum FROM {}", table))?;
+            let mut rows = stmt.query([])?;
+
+            while let Some(row) = rows.next()? {
+                result.insert((table.to_string(), row.get(0)?), row.get(1)?);
+            }
+        }
+
+        Ok(result)
+    }
+
+    /// Builds the source into the given Zola content directory, returning the cache it was built from.
+    fn build(source_dir: &Path, content_dir: &Path) -> Result<Cache> {
+        let mut cache = Cache::connect(":memory:")?;
+        let diagnostics = source::read(source_dir, &mut cache, UnknownFields::Ignore)?;
+
+        assert!(!diagnostics.has_errors(), "{}", diagnostics);
+
+        zola::write(content_dir, &mut cache)?;
+
+        Ok(cache)
+    }
+
+    #[test]
+    fn corpus_round_trip() -> Result<()> {
+        let temp = TempDir::default();
+        let corpus = Path::new(env!("CARGO_MANIFEST_DIR")).join("../corpus");
+        let source_dir = temp.as_ref().join("source");
+        let mut original = build(&corpus, &temp.as_ref().join("content"))?;
+
+        // Everything not imported comes from the corpus as is.
+        for entry in WalkDir::new(&corpus) {
+            let entry = entry?;
+            let relative = entry.path().strip_prefix(&corpus)?;
+            let is_imported = ["standards", "guidance", "themes"]
+                .iter()
+                .any(|dir| relative.starts_with(dir));
+
+            if entry.file_type().is_file() && !is_imported {
+                let path = source_dir.join(relative);
+
+                fs::create_dir_all(path.parent().expect("path to have a parent"))?;
+                fs::copy(entry.path(), path)?;
+            }
+        }
+
+        let (pages, diagnostics) = read(&temp.as_ref().join("content"))?;
+
+        assert!(diagnostics.is_empty(), "{}", diagnostics);
+
+        sink::merge(&source_dir, &pages)?;
+
+        let mut rebuilt = build(&source_dir, &temp.as_ref().join("rebuilt"))?;
+        let expected = checksums(&mut original)?;
+
+        assert!(expected.contains_key(&("guidance".into(), "api-management".into())));
+        assert_eq!(checksums(&mut rebuilt)?, expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn skip_sections() -> Result<()> {
+        let section = "+++\ntitle = \"Standards\"\ntemplate = \"standards.html\"\n+++\n";
+
+        assert!(import_page(Path::new("standards/_index.md"), section)?.is_none());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,129 @@
+//! This module checks the referential integrity of the cache once the source has been read.
+//!
+//! The cache tables don't enforce foreign keys between resources given that the source can be read in any order.
+//! Instead, this pass reports every reference to a resource that never made it to the cache.
+use anyhow::Result;
+use log::warn;
+use std::path::PathBuf;
+
+use crate::cache::{Cache, DanglingReferenceRecord, OriginRecord, Transaction};
+use crate::diagnostic::{Diagnostic, Diagnostics};
+
+/// Finds every reference to an unknown topic, theme, licence, organisation or standard.
+pub fn check(cache: &mut Cache) -> Result<Diagnostics> {
+    let tx = cache.transaction()?;
+    let records = DanglingReferenceRecord::select_all(&tx)?;
+    let mut diagnostics = Diagnostics::new();
+
+    tx.commit()?;
+
+    for record in records {
+        let path = origin_path(record.path, &record.resource_type, &record.resource_id);
+        let message = format!(
+            "{} `{}` references an unknown {} `{}` in `{}`",
+            record.resource_type,
+            record.resource_id,
+            record.reference_type,
+            record.reference_id,
+            record.field
+        );
+
+        diagnostics.push(Diagnostic::new(&path, &message));
+    }
+
+    Ok(diagnostics)
+}
+
+/// Warns that the given resource is left out of the output because it references an unknown organisation.
+///
+/// The writers call this instead of failing so a dangling reference only costs the page that holds it.
+/// The warning points at the file the resource was read from, like the diagnostics from [`check`].
+pub(crate) fn skip_unknown_organisation(
+    tx: &Transaction,
+    resource_type: &str,
+    resource_id: &str,
+    field: &str,
+    organisation_id: &str,
+) -> Result<()> {
+    let origin = OriginRecord::select(tx, resource_type, resource_id)?;
+    let path = origin_path(origin.map(|x| x.path), resource_type, resource_id);
+    let message = format!(
+        "{} `{}` references an unknown organisation `{}` in `{}`, skipping it",
+        resource_type, resource_id, organisation_id, field
+    );
+
+    warn!("{}", Diagnostic::warning(&path, &message));
+
+    Ok(())
+}
+
+/// The path of the file the resource was read from, or a placeholder naming the resource when unknown.
+fn origin_path(path: Option<String>, resource_type: &str, resource_id: &str) -> PathBuf {
+    match path {
+        Some(path) => PathBuf::from(path),
+        None => PathBuf::from(format!("<{} {}>", resource_type, resource_id)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source;
+    use std::str::FromStr;
+
+    #[test]
+    fn dangling_references() -> Result<()> {
+        let standard_raw = r#"---
+type: standard
+identifier: vapour
+name: Vapour
+topic: exchange
+specification: https://spec.vapour.org/
+licence: ogl
+maintainer: data-standards-authority
+endorsement_state:
+    status: identified
+    start_date: 2021-06-01
+    review_date: 2021-06-01
+related:
+    - steam
+---
+This standard will give you no overhead."#;
+        let topic_raw = r#"---
+type: topic
+identifier: exchange
+name: Exchange
+theme: other
+ordinal: 1
+---"#;
+        let org_raw = r#"{
+            "id": "data-standards-authority",
+            "name": "Data Standards Authority",
+            "url": "https://dsa.gov.uk"
+        }"#;
+        let mut cache = Cache::connect(":memory:")?;
+        let standard = source::Standard::from_str(standard_raw)?;
+        let topic = source::Topic::from_str(topic_raw)?;
+        let org = source::Organisation::from_str(org_raw)?;
+
+        cache.add(&standard)?;
+        cache.insert_origin("standard", standard.id(), "standards/vapour.md")?;
+        cache.add(&topic)?;
+        cache.add(&org)?;
+
+        let diagnostics = check(&mut cache)?;
+        let messages: Vec<_> = diagnostics.iter().map(|d| d.to_string()).collect();
+
+        assert_eq!(
+            messages,
+            vec![
+                "<topic exchange>: topic `exchange` references an unknown theme `other` in `theme`",
+                "standards/vapour.md: standard `vapour` references an unknown licence `ogl` in `licence`",
+                "standards/vapour.md: standard `vapour` references an unknown standard `steam` in `related`",
+            ]
+        );
+
+        Ok(())
+    }
+}
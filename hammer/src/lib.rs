@@ -1,12 +1,23 @@
+pub mod api;
 pub mod cache;
 pub mod checksum;
 pub mod cli;
+pub mod diagnostic;
+pub mod import;
+pub mod integrity;
+pub mod linked_data;
 pub mod markdown;
+pub mod mermaid;
 pub mod report;
 pub mod resource;
+pub mod review;
+pub mod search;
+pub mod sink;
 pub mod source;
 pub mod status;
+pub mod tabular;
 pub mod zola;
 
 pub use cache::Cache;
+pub use diagnostic::{Diagnostic, Diagnostics};
 pub use status::{Status, StatusError};
@@ -0,0 +1,208 @@
+//! This module describes the catalogue resources as linked data nodes.
+use super::{Node, Term};
+use crate::api::{Licence, Organisation, Standard, Theme, Topic, TopicReference};
+
+/// Mints the IRIs of the catalogue resources under a base URL.
+#[derive(Debug, Clone)]
+pub struct Iris {
+    base: String,
+}
+
+impl Iris {
+    pub fn new(base_url: &str) -> Self {
+        let mut base = base_url.to_string();
+
+        if !base.ends_with('/') {
+            base.push('/');
+        }
+
+        Iris { base }
+    }
+
+    pub fn taxonomy(&self) -> String {
+        format!("{}taxonomy", self.base)
+    }
+
+    pub fn catalogue(&self) -> String {
+        format!("{}catalogue", self.base)
+    }
+
+    pub fn standard(&self, id: &str) -> String {
+        format!("{}standards/{}", self.base, id)
+    }
+
+    pub fn organisation(&self, id: &str) -> String {
+        format!("{}organisations/{}", self.base, id)
+    }
+
+    pub fn licence(&self, id: &str) -> String {
+        format!("{}licences/{}", self.base, id)
+    }
+
+    pub fn topic(&self, id: &str) -> String {
+        format!("{}topics/{}", self.base, id)
+    }
+
+    pub fn theme(&self, id: &str) -> String {
+        format!("{}themes/{}", self.base, id)
+    }
+}
+
+/// Describes the catalogue as a `dcat:Catalog` with every standard as one of its resources.
+pub fn catalogue(iris: &Iris, standards: &[Standard]) -> Node {
+    let mut node = Node::new(iris.catalogue())
+        .with_type("dcat:Catalog")
+        .with("dct:title", Term::literal("Data Standards Catalogue"));
+
+    for standard in standards {
+        node = node.with("dcat:resource", Term::Node(self::standard(iris, standard)));
+    }
+
+    node
+}
+
+/// Describes a standard as a `schema:CreativeWork`, a `dct:Standard` and a `dcat:Resource`.
+pub fn standard(iris: &Iris, standard: &Standard) -> Node {
+    let mut node = Node::new(iris.standard(&standard.id))
+        .with_type("schema:CreativeWork")
+        .with_type("dct:Standard")
+        .with_type("dcat:Resource")
+        .with("schema:identifier", Term::literal(&standard.id))
+        .with("dct:identifier", Term::literal(&standard.id))
+        .with("schema:name", Term::literal(&standard.name))
+        .with("dct:title", Term::literal(&standard.name))
+        .with_opt(
+            "schema:alternateName",
+            standard.acronym.as_deref().map(Term::literal),
+        )
+        .with("schema:url", Term::Iri(standard.specification.clone()))
+        .with(
+            "dcat:landingPage",
+            Term::Iri(standard.specification.clone()),
+        )
+        .with(
+            "schema:creativeWorkStatus",
+            Term::literal(&standard.endorsement_state.status),
+        )
+        .with(
+            "dct:issued",
+            Term::date(&standard.endorsement_state.start_date),
+        )
+        .with("schema:text", Term::literal(&standard.content));
+
+    for subject in &standard.subjects {
+        node = node
+            .with("schema:keywords", Term::literal(subject))
+            .with("dcat:keyword", Term::literal(subject));
+    }
+
+    if let Some(topic) = &standard.topic {
+        node = node
+            .with("schema:about", Term::Node(self::topic(iris, topic)))
+            .with("dcat:theme", Term::Iri(iris.topic(&topic.id)));
+    }
+
+    if let Some(conforms_to) = &standard.conforms_to {
+        let external = Node::blank()
+            .with_type("dct:Standard")
+            .with("schema:identifier", Term::literal(conforms_to));
+
+        node = node.with("dct:conformsTo", Term::Node(external));
+    }
+
+    if let Some(licence) = &standard.licence {
+        node = node.with("dct:license", Term::Node(self::licence(iris, licence)));
+    }
+
+    let maintainer = organisation(iris, &standard.maintainer);
+
+    node = node.with("dct:publisher", Term::Node(maintainer)).with(
+        "schema:maintainer",
+        Term::Iri(iris.organisation(&standard.maintainer.id)),
+    );
+
+    for related in &standard.related {
+        node = node
+            .with("dct:relation", Term::Iri(iris.standard(&related.id)))
+            .with("skos:related", Term::Iri(iris.standard(&related.id)));
+    }
+
+    node
+}
+
+/// Describes a topic as a `schema:DefinedTerm` in the set of terms of its theme.
+fn topic(iris: &Iris, topic: &TopicReference) -> Node {
+    let theme = Node::new(iris.theme(&topic.theme.id))
+        .with_type("schema:DefinedTermSet")
+        .with("schema:identifier", Term::literal(&topic.theme.id))
+        .with("schema:name", Term::literal(&topic.theme.name));
+
+    Node::new(iris.topic(&topic.id))
+        .with_type("schema:DefinedTerm")
+        .with("schema:identifier", Term::literal(&topic.id))
+        .with("schema:name", Term::literal(&topic.name))
+        .with("schema:inDefinedTermSet", Term::Node(theme))
+}
+
+/// Describes the themes and topics as a SKOS concept scheme with the themes as its top concepts.
+pub fn taxonomy(iris: &Iris, themes: &[Theme]) -> Node {
+    let mut node = Node::new(iris.taxonomy())
+        .with_type("skos:ConceptScheme")
+        .with("skos:prefLabel", Term::literal("Data Standards Taxonomy"));
+
+    for theme in themes {
+        node = node.with("skos:hasTopConcept", Term::Iri(iris.theme(&theme.id)));
+    }
+
+    node
+}
+
+pub fn theme(iris: &Iris, theme: &Theme) -> Node {
+    let mut node = Node::new(iris.theme(&theme.id))
+        .with_type("skos:Concept")
+        .with("skos:notation", Term::literal(&theme.id))
+        .with("skos:prefLabel", Term::literal(&theme.name))
+        .with("skos:definition", Term::literal(&theme.description))
+        .with("schema:position", Term::integer(theme.ordinal))
+        .with("skos:inScheme", Term::Iri(iris.taxonomy()))
+        .with("skos:topConceptOf", Term::Iri(iris.taxonomy()));
+
+    for topic in &theme.topics {
+        node = node.with("skos:narrower", Term::Iri(iris.topic(&topic.id)));
+    }
+
+    node
+}
+
+pub fn topic_concept(iris: &Iris, topic: &Topic) -> Node {
+    Node::new(iris.topic(&topic.id))
+        .with_type("skos:Concept")
+        .with("skos:notation", Term::literal(&topic.id))
+        .with("skos:prefLabel", Term::literal(&topic.name))
+        .with("skos:definition", Term::literal(&topic.description))
+        .with("schema:position", Term::integer(topic.ordinal))
+        .with("skos:inScheme", Term::Iri(iris.taxonomy()))
+        .with("skos:broader", Term::Iri(iris.theme(&topic.theme.id)))
+}
+
+pub fn organisation(iris: &Iris, organisation: &Organisation) -> Node {
+    Node::new(iris.organisation(&organisation.id))
+        .with_type("schema:Organization")
+        .with_type("foaf:Agent")
+        .with("schema:identifier", Term::literal(&organisation.id))
+        .with("schema:name", Term::literal(&organisation.name))
+        .with("schema:url", Term::Iri(organisation.url.clone()))
+}
+
+pub fn licence(iris: &Iris, licence: &Licence) -> Node {
+    Node::new(iris.licence(&licence.id))
+        .with_type("dct:LicenseDocument")
+        .with_type("schema:CreativeWork")
+        .with("schema:identifier", Term::literal(&licence.id))
+        .with("schema:name", Term::literal(&licence.name))
+        .with_opt(
+            "schema:alternateName",
+            licence.acronym.as_deref().map(Term::literal),
+        )
+        .with("schema:url", Term::Iri(licence.url.clone()))
+}
@@ -0,0 +1,193 @@
+//! This module flattens [`Node`]s into a set of RDF triples.
+//!
+//! Nested nodes with an IRI are described once no matter how many times they are referenced and blank nodes get a
+//! label unique to the graph.
+use std::collections::HashSet;
+
+use super::{expand, Node, Term};
+
+const RDF_TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Subject {
+    Iri(String),
+    Blank(String),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Object {
+    Iri(String),
+    Blank(String),
+    Literal(String),
+    /// A literal with the full IRI of its datatype.
+    Typed(String, String),
+}
+
+/// A statement where every IRI is in full.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Triple {
+    pub subject: Subject,
+    pub predicate: String,
+    pub object: Object,
+}
+
+impl Triple {
+    pub fn is_type(&self) -> bool {
+        self.predicate == RDF_TYPE
+    }
+}
+
+/// An ordered set of triples.
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    triples: Vec<Triple>,
+    seen: HashSet<Triple>,
+    blanks: usize,
+}
+
+impl Graph {
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Adds the triples describing the node and any node nested in it.
+    pub fn insert(&mut self, node: &Node) -> Subject {
+        let subject = match &node.id {
+            Some(iri) => Subject::Iri(iri.clone()),
+            None => {
+                self.blanks += 1;
+                Subject::Blank(format!("b{}", self.blanks))
+            }
+        };
+
+        for kind in &node.types {
+            self.push(&subject, RDF_TYPE.into(), Object::Iri(expand(kind)));
+        }
+
+        for (property, term) in &node.properties {
+            let object = match term {
+                Term::Iri(iri) => Object::Iri(iri.clone()),
+                Term::Literal(value) => Object::Literal(value.clone()),
+                Term::Typed(value, datatype) => Object::Typed(value.clone(), expand(datatype)),
+                Term::Node(node) => match self.insert(node) {
+                    Subject::Iri(iri) => Object::Iri(iri),
+                    Subject::Blank(label) => Object::Blank(label),
+                },
+            };
+
+            self.push(&subject, expand(property), object);
+        }
+
+        subject
+    }
+
+    pub fn triples(&self) -> &[Triple] {
+        &self.triples
+    }
+
+    fn push(&mut self, subject: &Subject, predicate: String, object: Object) {
+        let triple = Triple {
+            subject: subject.clone(),
+            predicate,
+            object,
+        };
+
+        if self.seen.insert(triple.clone()) {
+            self.triples.push(triple);
+        }
+    }
+}
+
+/// Escapes a string to be used as a literal in Turtle or N-Triples.
+pub fn escape(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '\\' => result.push_str("\\\\"),
+            '"' => result.push_str("\\\""),
+            '\n' => result.push_str("\\n"),
+            '\r' => result.push_str("\\r"),
+            '\t' => result.push_str("\\t"),
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+/// Escapes an IRI to be written between angle brackets in Turtle or N-Triples.
+///
+/// Characters an IRI reference can't hold, such as spaces or `>`, are percent-encoded.
+pub fn escape_iri(value: &str) -> String {
+    let mut result = String::with_capacity(value.len());
+
+    for c in value.chars() {
+        match c {
+            '\u{00}'..='\u{20}' | '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' => {
+                let mut buffer = [0; 4];
+
+                for byte in c.encode_utf8(&mut buffer).bytes() {
+                    result.push_str(&format!("%{:02X}", byte));
+                }
+            }
+            c => result.push(c),
+        }
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn flatten_shared_nodes() {
+        let geoplace = Node::new("https://example.org/organisations/geoplace".into())
+            .with_type("schema:Organization")
+            .with("schema:name", Term::literal("GeoPlace"));
+        let mut graph = Graph::new();
+
+        for id in &["uprn", "usrn"] {
+            graph.insert(
+                &Node::new(format!("https://example.org/standards/{}", id))
+                    .with("dct:publisher", Term::Node(geoplace.clone()))
+                    .with(
+                        "dct:conformsTo",
+                        Term::Node(
+                            Node::blank().with("schema:identifier", Term::literal("BS7666")),
+                        ),
+                    ),
+            );
+        }
+
+        let triples = graph.triples();
+
+        assert_eq!(triples.len(), 8);
+        assert_eq!(triples.iter().filter(|triple| triple.is_type()).count(), 1);
+        assert_eq!(
+            triples[0].object,
+            Object::Iri("https://schema.org/Organization".into())
+        );
+        assert_eq!(triples[3].subject, Subject::Blank("b1".into()));
+        assert_eq!(triples[7].object, Object::Blank("b2".into()));
+    }
+
+    #[test]
+    fn escape_literals() {
+        assert_eq!(escape("a \"b\"\n\\c"), "a \\\"b\\\"\\n\\\\c");
+    }
+
+    #[test]
+    fn escape_iris() {
+        assert_eq!(
+            escape_iri("https://example.org/standards/a b>\"c\""),
+            "https://example.org/standards/a%20b%3E%22c%22"
+        );
+        assert_eq!(
+            escape_iri("https://example.org/standards/café"),
+            "https://example.org/standards/café"
+        );
+    }
+}
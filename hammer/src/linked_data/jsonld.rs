@@ -0,0 +1,107 @@
+//! This module serialises a [`Node`] as a JSON-LD document.
+//!
+//! Compact IRIs are kept as they are and resolved by the `@context`, repeated properties become arrays.
+use serde_json::{json, Map, Value};
+
+use super::{Node, Term, PREFIXES};
+
+/// Builds a standalone document with the `@context` for every prefix in use.
+pub fn document(node: &Node) -> Value {
+    let mut context = Map::new();
+
+    for (prefix, namespace) in &PREFIXES {
+        context.insert(prefix.to_string(), Value::String(namespace.to_string()));
+    }
+
+    let mut value = node_value(node);
+
+    if let Value::Object(map) = &mut value {
+        map.insert("@context".into(), Value::Object(context));
+    }
+
+    value
+}
+
+fn node_value(node: &Node) -> Value {
+    let mut map = Map::new();
+
+    if let Some(id) = &node.id {
+        map.insert("@id".into(), Value::String(id.clone()));
+    }
+
+    match node.types.as_slice() {
+        [] => (),
+        [kind] => {
+            map.insert("@type".into(), Value::String(kind.to_string()));
+        }
+        kinds => {
+            map.insert("@type".into(), json!(kinds));
+        }
+    }
+
+    for (property, term) in &node.properties {
+        let value = term_value(term);
+
+        match map.get_mut(*property) {
+            None => {
+                map.insert(property.to_string(), value);
+            }
+            Some(Value::Array(values)) => values.push(value),
+            Some(previous) => {
+                let first = previous.take();
+                *previous = Value::Array(vec![first, value]);
+            }
+        }
+    }
+
+    Value::Object(map)
+}
+
+fn term_value(term: &Term) -> Value {
+    match term {
+        Term::Iri(iri) => json!({ "@id": iri }),
+        Term::Literal(value) => Value::String(value.clone()),
+        Term::Typed(value, datatype) => json!({ "@value": value, "@type": datatype }),
+        Term::Node(node) => node_value(node),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeated_properties() {
+        let node = Node::new("https://example.org/standards/uprn".into())
+            .with_type("schema:CreativeWork")
+            .with_type("dct:Standard")
+            .with("schema:keywords", Term::literal("addresses"))
+            .with("schema:keywords", Term::literal("property"))
+            .with("schema:keywords", Term::literal("location"))
+            .with("dct:issued", Term::date("2020-12-16"))
+            .with(
+                "dct:conformsTo",
+                Term::Node(Node::blank().with("schema:identifier", Term::literal("BS7666"))),
+            );
+        let value = document(&node);
+
+        assert_eq!(value["@context"]["dct"], "http://purl.org/dc/terms/");
+        assert_eq!(value["@id"], "https://example.org/standards/uprn");
+        assert_eq!(
+            value["@type"],
+            json!(["schema:CreativeWork", "dct:Standard"])
+        );
+        assert_eq!(
+            value["schema:keywords"],
+            json!(["addresses", "property", "location"])
+        );
+        assert_eq!(
+            value["dct:issued"],
+            json!({"@value": "2020-12-16", "@type": "xsd:date"})
+        );
+        assert_eq!(
+            value["dct:conformsTo"],
+            json!({"schema:identifier": "BS7666"})
+        );
+    }
+}
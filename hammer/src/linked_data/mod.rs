@@ -0,0 +1,319 @@
+//! This module publishes the catalogue as linked data.
+//!
+//! The catalogue is first described as a graph of [`Node`]s using compact IRIs (e.g. `schema:name`) from the
+//! [`PREFIXES`] table and then serialised. Every resource is identified by an extension-less IRI under the base URL
+//! (e.g. `{base}standards/uprn`) so the same graph can be published in any serialisation.
+
+mod catalogue;
+pub mod graph;
+pub mod jsonld;
+pub mod ntriples;
+pub mod turtle;
+
+pub use catalogue::Iris;
+pub use graph::Graph;
+
+use anyhow::Result;
+use std::path::{Path, PathBuf};
+
+use crate::api;
+use crate::cache::Cache;
+use crate::sink::{self, Page, Summary};
+
+/// The vocabularies used to describe the catalogue.
+pub const PREFIXES: [(&str, &str); 7] = [
+    ("dcat", "http://www.w3.org/ns/dcat#"),
+    ("dct", "http://purl.org/dc/terms/"),
+    ("foaf", "http://xmlns.com/foaf/0.1/"),
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("schema", "https://schema.org/"),
+    ("skos", "http://www.w3.org/2004/02/skos/core#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+];
+
+/// A resource described by a set of properties.
+///
+/// Blank nodes have no `id`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub id: Option<String>,
+    pub types: Vec<&'static str>,
+    pub properties: Vec<(&'static str, Term)>,
+}
+
+impl Node {
+    pub fn new(id: String) -> Self {
+        Node {
+            id: Some(id),
+            types: Vec::new(),
+            properties: Vec::new(),
+        }
+    }
+
+    pub fn blank() -> Self {
+        Node {
+            id: None,
+            types: Vec::new(),
+            properties: Vec::new(),
+        }
+    }
+
+    pub fn with_type(mut self, kind: &'static str) -> Self {
+        self.types.push(kind);
+        self
+    }
+
+    pub fn with(mut self, property: &'static str, term: Term) -> Self {
+        self.properties.push((property, term));
+        self
+    }
+
+    /// Adds the property only when there is a value for it.
+    pub fn with_opt(self, property: &'static str, term: Option<Term>) -> Self {
+        match term {
+            Some(term) => self.with(property, term),
+            None => self,
+        }
+    }
+}
+
+/// The value of a property.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Iri(String),
+    Literal(String),
+    /// A literal with a datatype, e.g. `xsd:date`.
+    Typed(String, &'static str),
+    Node(Node),
+}
+
+impl Term {
+    pub fn literal(value: &str) -> Self {
+        Term::Literal(value.into())
+    }
+
+    pub fn date(value: &str) -> Self {
+        Term::Typed(value.into(), "xsd:date")
+    }
+
+    pub fn integer(value: u32) -> Self {
+        Term::Typed(value.to_string(), "xsd:integer")
+    }
+}
+
+/// Expands a compact IRI such as `schema:name` with the [`PREFIXES`] table.
+pub fn expand(compact: &str) -> String {
+    for (prefix, namespace) in &PREFIXES {
+        if let Some(name) = compact
+            .strip_prefix(prefix)
+            .and_then(|rest| rest.strip_prefix(':'))
+        {
+            return format!("{}{}", namespace, name);
+        }
+    }
+
+    compact.to_string()
+}
+
+/// Writes the JSON-LD documents that differ from the ones in the sink and removes any file no longer rendered.
+pub fn write(sink_dir: &Path, cache: &mut Cache, base_url: &str) -> Result<Summary> {
+    let pages = render(cache, base_url)?;
+
+    sink::write(sink_dir, &pages)
+}
+
+/// Renders a JSON-LD document for each standard, organisation and licence plus the DCAT catalogue.
+pub fn render(cache: &mut Cache, base_url: &str) -> Result<Vec<Page>> {
+    let iris = Iris::new(base_url);
+    let standards = api::standards(cache)?;
+    let mut pages = vec![jsonld_page(
+        "catalogue.jsonld",
+        &catalogue::catalogue(&iris, &standards),
+    )?];
+
+    for standard in &standards {
+        let path = format!("standards/{}.jsonld", standard.id);
+        pages.push(jsonld_page(&path, &catalogue::standard(&iris, standard))?);
+    }
+
+    for organisation in api::organisations(cache)? {
+        let path = format!("organisations/{}.jsonld", organisation.id);
+        pages.push(jsonld_page(
+            &path,
+            &catalogue::organisation(&iris, &organisation),
+        )?);
+    }
+
+    for licence in api::licences(cache)? {
+        let path = format!("licences/{}.jsonld", licence.id);
+        pages.push(jsonld_page(&path, &catalogue::licence(&iris, &licence))?);
+    }
+
+    Ok(pages)
+}
+
+/// Writes the RDF documents that differ from the ones in the sink and removes any file no longer rendered.
+pub fn write_rdf(sink_dir: &Path, cache: &mut Cache, base_url: &str) -> Result<Summary> {
+    let pages = render_rdf(cache, base_url)?;
+
+    sink::write(sink_dir, &pages)
+}
+
+/// Renders the whole catalogue graph as `catalogue.ttl` and `catalogue.nt`.
+pub fn render_rdf(cache: &mut Cache, base_url: &str) -> Result<Vec<Page>> {
+    let graph = graph(cache, &Iris::new(base_url))?;
+
+    Ok(vec![
+        Page::new(PathBuf::from("catalogue.ttl"), turtle::to_string(&graph)),
+        Page::new(PathBuf::from("catalogue.nt"), ntriples::to_string(&graph)),
+    ])
+}
+
+/// Describes the taxonomy as a SKOS concept scheme followed by the catalogue, organisations and licences.
+pub fn graph(cache: &mut Cache, iris: &Iris) -> Result<Graph> {
+    let mut graph = Graph::new();
+    let themes = api::themes(cache)?;
+    let topics = api::topics(cache)?;
+
+    graph.insert(&catalogue::taxonomy(iris, &themes));
+
+    for theme in &themes {
+        graph.insert(&catalogue::theme(iris, theme));
+    }
+
+    for topic in &topics {
+        graph.insert(&catalogue::topic_concept(iris, topic));
+    }
+
+    graph.insert(&catalogue::catalogue(iris, &api::standards(cache)?));
+
+    for organisation in api::organisations(cache)? {
+        graph.insert(&catalogue::organisation(iris, &organisation));
+    }
+
+    for licence in api::licences(cache)? {
+        graph.insert(&catalogue::licence(iris, &licence));
+    }
+
+    Ok(graph)
+}
+
+fn jsonld_page(path: &str, node: &Node) -> Result<Page> {
+    let mut content = serde_json::to_string_pretty(&jsonld::document(node))?;
+    content.push('\n');
+
+    Ok(Page::new(PathBuf::from(path), content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source;
+    use std::str::FromStr;
+
+    #[test]
+    fn standard_documents() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&source::Organisation::from_str(
+            r#"{"id": "geoplace", "name": "GeoPlace", "url": "https://www.geoplace.co.uk/"}"#,
+        )?)?;
+        cache.add(&source::Licence::from_str(
+            r#"{"id": "ogl-3", "name": "Open Government Licence", "acronym": "OGL", "url": "https://ogl.gov.uk"}"#,
+        )?)?;
+        cache.add(&source::Standard::from_str(
+            r#"---
+type: standard
+identifier: uprn
+name: Unique Property Reference Number
+topic: geospatial
+specification: https://www.geoplace.co.uk/addresses/uprn
+licence: ogl-3
+maintainer: geoplace
+conforms_to: BS7666
+endorsement_state:
+    status: endorsed
+    start_date: 2020-12-16
+    review_date: 2021-12-16
+---
+The UPRN."#,
+        )?)?;
+
+        let pages = render(&mut cache, "https://example.org/ld")?;
+        let paths: Vec<_> = pages
+            .iter()
+            .map(|page| page.path.display().to_string())
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "catalogue.jsonld",
+                "standards/uprn.jsonld",
+                "organisations/geoplace.jsonld",
+                "licences/ogl-3.jsonld",
+            ]
+        );
+
+        let catalogue: serde_json::Value = serde_json::from_str(&pages[0].content)?;
+
+        assert_eq!(catalogue["@type"], "dcat:Catalog");
+        assert_eq!(
+            catalogue["dcat:resource"]["@id"],
+            "https://example.org/ld/standards/uprn"
+        );
+
+        let uprn: serde_json::Value = serde_json::from_str(&pages[1].content)?;
+
+        assert_eq!(
+            uprn["dct:publisher"]["@id"],
+            "https://example.org/ld/organisations/geoplace"
+        );
+        assert_eq!(uprn["dct:license"]["schema:alternateName"], "OGL");
+        assert_eq!(uprn["dct:conformsTo"]["schema:identifier"], "BS7666");
+
+        Ok(())
+    }
+
+    #[test]
+    fn taxonomy_graph() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&source::Theme::from_str(
+            "---\ntype: theme\nidentifier: reference-data\nname: Reference Data\nordinal: 1\n---\nShared codes.",
+        )?)?;
+        cache.add(&source::Topic::from_str(
+            "---\ntype: topic\nidentifier: geospatial\nname: Geospatial\ntheme: reference-data\nordinal: 2\n---\nPlaces.",
+        )?)?;
+
+        let pages = render_rdf(&mut cache, "https://example.org/ld/")?;
+        let turtle = &pages[0].content;
+        let ntriples = &pages[1].content;
+
+        assert!(turtle.contains(
+            r#"
+<https://example.org/ld/topics/geospatial>
+    a skos:Concept ;
+    skos:notation "geospatial" ;
+    skos:prefLabel "Geospatial" ;
+    skos:definition "Places." ;
+    schema:position "2"^^xsd:integer ;
+    skos:inScheme <https://example.org/ld/taxonomy> ;
+    skos:broader <https://example.org/ld/themes/reference-data> .
+"#
+        ));
+        assert!(ntriples.contains(
+            "<https://example.org/ld/themes/reference-data> <http://www.w3.org/2004/02/skos/core#narrower> <https://example.org/ld/topics/geospatial> .\n"
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn expand_compact_iris() {
+        assert_eq!(expand("schema:name"), "https://schema.org/name");
+        assert_eq!(expand("dct:license"), "http://purl.org/dc/terms/license");
+        assert_eq!(expand("https://example.org/x"), "https://example.org/x");
+    }
+}
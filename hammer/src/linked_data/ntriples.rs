@@ -0,0 +1,74 @@
+//! This module serialises a [`Graph`] as N-Triples, one statement per line.
+use super::graph::{escape, escape_iri, Graph, Object, Subject};
+
+pub fn to_string(graph: &Graph) -> String {
+    let mut result = String::new();
+
+    for triple in graph.triples() {
+        let subject = match &triple.subject {
+            Subject::Iri(value) => iri(value),
+            Subject::Blank(label) => format!("_:{}", label),
+        };
+        let object = match &triple.object {
+            Object::Iri(value) => iri(value),
+            Object::Blank(label) => format!("_:{}", label),
+            Object::Literal(value) => format!("\"{}\"", escape(value)),
+            Object::Typed(value, datatype) => format!("\"{}\"^^{}", escape(value), iri(datatype)),
+        };
+
+        result.push_str(&format!(
+            "{} {} {} .\n",
+            subject,
+            iri(&triple.predicate),
+            object
+        ));
+    }
+
+    result
+}
+
+fn iri(value: &str) -> String {
+    format!("<{}>", escape_iri(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linked_data::{Node, Term};
+
+    #[test]
+    fn statements() {
+        let mut graph = Graph::new();
+
+        graph.insert(
+            &Node::new("https://example.org/standards/uprn".into())
+                .with_type("dct:Standard")
+                .with("dct:issued", Term::date("2020-12-16"))
+                .with("schema:text", Term::literal("The \"UPRN\".")),
+        );
+
+        assert_eq!(
+            to_string(&graph),
+            r#"<https://example.org/standards/uprn> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://purl.org/dc/terms/Standard> .
+<https://example.org/standards/uprn> <http://purl.org/dc/terms/issued> "2020-12-16"^^<http://www.w3.org/2001/XMLSchema#date> .
+<https://example.org/standards/uprn> <https://schema.org/text> "The \"UPRN\"." .
+"#
+        );
+    }
+
+    #[test]
+    fn escape_unsafe_iris() {
+        let mut graph = Graph::new();
+
+        graph.insert(
+            &Node::new(r#"https://example.org/standards/a b>"c""#.into())
+                .with("dct:identifier", Term::literal(r#"a b>"c""#)),
+        );
+
+        assert_eq!(
+            to_string(&graph),
+            r#"<https://example.org/standards/a%20b%3E%22c%22> <http://purl.org/dc/terms/identifier> "a b>\"c\"" .
+"#
+        );
+    }
+}
@@ -0,0 +1,141 @@
+//! This module serialises a [`Graph`] as Turtle.
+//!
+//! Statements are grouped by subject in the order they were added and IRIs in a known vocabulary are written as
+//! prefixed names.
+use super::graph::{escape, escape_iri, Graph, Object, Subject, Triple};
+use super::PREFIXES;
+
+pub fn to_string(graph: &Graph) -> String {
+    let mut result = String::new();
+    let mut subjects: Vec<(&Subject, Vec<&Triple>)> = Vec::new();
+
+    for (prefix, namespace) in &PREFIXES {
+        result.push_str(&format!("@prefix {}: <{}> .\n", prefix, namespace));
+    }
+
+    for triple in graph.triples() {
+        match subjects
+            .iter_mut()
+            .find(|(subject, _)| *subject == &triple.subject)
+        {
+            Some((_, triples)) => triples.push(triple),
+            None => subjects.push((&triple.subject, vec![triple])),
+        }
+    }
+
+    for (subject, triples) in subjects {
+        let mut statements: Vec<(String, Vec<String>)> = Vec::new();
+
+        for triple in triples {
+            let predicate = if triple.is_type() {
+                "a".to_string()
+            } else {
+                iri(&triple.predicate)
+            };
+
+            match statements.iter_mut().find(|(p, _)| p == &predicate) {
+                Some((_, objects)) => objects.push(object(&triple.object)),
+                None => statements.push((predicate, vec![object(&triple.object)])),
+            }
+        }
+
+        let statements: Vec<String> = statements
+            .into_iter()
+            .map(|(predicate, objects)| format!("    {} {}", predicate, objects.join(", ")))
+            .collect();
+
+        result.push_str(&format!(
+            "\n{}\n{} .\n",
+            self::subject(subject),
+            statements.join(" ;\n")
+        ));
+    }
+
+    result
+}
+
+fn subject(subject: &Subject) -> String {
+    match subject {
+        Subject::Iri(value) => iri(value),
+        Subject::Blank(label) => format!("_:{}", label),
+    }
+}
+
+fn object(object: &Object) -> String {
+    match object {
+        Object::Iri(value) => iri(value),
+        Object::Blank(label) => format!("_:{}", label),
+        Object::Literal(value) => format!("\"{}\"", escape(value)),
+        Object::Typed(value, datatype) => format!("\"{}\"^^{}", escape(value), iri(datatype)),
+    }
+}
+
+/// Writes the IRI as a prefixed name when possible, escaping it otherwise.
+fn iri(value: &str) -> String {
+    for (prefix, namespace) in &PREFIXES {
+        if let Some(name) = value.strip_prefix(namespace) {
+            let is_simple = !name.is_empty()
+                && name
+                    .chars()
+                    .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+
+            if is_simple {
+                return format!("{}:{}", prefix, name);
+            }
+        }
+    }
+
+    format!("<{}>", escape_iri(value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::linked_data::{Node, Term};
+
+    #[test]
+    fn grouped_statements() {
+        let mut graph = Graph::new();
+
+        graph.insert(
+            &Node::new("https://example.org/standards/uprn".into())
+                .with_type("schema:CreativeWork")
+                .with_type("dct:Standard")
+                .with("schema:keywords", Term::literal("addresses"))
+                .with("schema:keywords", Term::literal("property"))
+                .with(
+                    "schema:url",
+                    Term::Iri("https://www.geoplace.co.uk/".into()),
+                ),
+        );
+
+        let turtle = to_string(&graph);
+
+        assert!(turtle.starts_with("@prefix dcat: <http://www.w3.org/ns/dcat#> .\n"));
+        assert!(turtle.ends_with(
+            r#"
+<https://example.org/standards/uprn>
+    a schema:CreativeWork, dct:Standard ;
+    schema:keywords "addresses", "property" ;
+    schema:url <https://www.geoplace.co.uk/> .
+"#
+        ));
+    }
+
+    #[test]
+    fn escape_unsafe_iris() {
+        let mut graph = Graph::new();
+
+        graph.insert(
+            &Node::new(r#"https://example.org/standards/a b>"c""#.into())
+                .with("dct:identifier", Term::literal("a b")),
+        );
+
+        assert!(to_string(&graph).ends_with(
+            r#"
+<https://example.org/standards/a%20b%3E%22c%22>
+    dct:identifier "a b" .
+"#
+        ));
+    }
+}
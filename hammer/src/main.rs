@@ -1,6 +1,6 @@
 use clap::{AppSettings, Clap};
-use env_logger::fmt::Color;
 use std::io::Write;
+use std::process;
 
 use hammer::cli;
 
@@ -8,7 +8,12 @@ use hammer::cli;
 enum Subcommand {
     #[clap(alias = "b")]
     Build(cli::build::Cmd),
+    Cache(cli::cache::Cmd),
+    Check(cli::check::Cmd),
     Clean(cli::clean::Cmd),
+    Import(cli::import::Cmd),
+    Reviews(cli::reviews::Cmd),
+    Search(cli::search::Cmd),
 }
 
 #[derive(Debug, Clap)]
@@ -29,22 +34,23 @@ fn main() {
 
     let cli: Cli = Cli::parse();
 
-    match cli.subcommand {
-        Subcommand::Build(cmd) => match cmd.run() {
-            Ok(msg) => {
-                println!("{}", msg);
-            }
-            Err(err) => {
-                eprintln!("{:?}", err);
-            }
-        },
-        Subcommand::Clean(cmd) => match cmd.run() {
-            Ok(msg) => {
-                println!("{}", msg);
-            }
-            Err(err) => {
-                eprintln!("{:?}", err);
-            }
-        },
+    let result = match cli.subcommand {
+        Subcommand::Build(cmd) => cmd.run(),
+        Subcommand::Cache(cmd) => cmd.run(),
+        Subcommand::Check(cmd) => cmd.run(),
+        Subcommand::Clean(cmd) => cmd.run(),
+        Subcommand::Import(cmd) => cmd.run(),
+        Subcommand::Reviews(cmd) => cmd.run(),
+        Subcommand::Search(cmd) => cmd.run(),
+    };
+
+    match result {
+        Ok(msg) => {
+            println!("{}", msg);
+        }
+        Err(err) => {
+            eprintln!("{:?}", err);
+            process::exit(1);
+        }
     }
 }
@@ -32,6 +32,7 @@ where
         }
     }
 
+    #[allow(clippy::should_implement_trait)]
     pub fn next(&mut self) -> Option<Event<'a>> {
         self.iter.next()
     }
@@ -72,7 +73,7 @@ pub enum ExtractError {
 }
 
 /// Extracts the title (i.e. first h1) from a markdown text.
-pub fn take_title<'a>(text: &str) -> Result<String> {
+pub fn take_title(text: &str) -> Result<String> {
     let parser = Parser::new(text);
     let mut recipient = String::new();
     let mut extract = Extract::new(parser, &mut recipient);
@@ -86,29 +87,21 @@ pub fn take_title<'a>(text: &str) -> Result<String> {
                 extract.activate();
             }
             Event::End(Tag::Heading(1)) => {
-                &extract.finish();
+                extract.finish();
             }
-            Event::Start(Tag::Emphasis) | Event::End(Tag::Emphasis) => {
-                if extract.is_active() {
-                    extract.append("_")?;
-                }
+            Event::Start(Tag::Emphasis) | Event::End(Tag::Emphasis) if extract.is_active() => {
+                extract.append("_")?;
             }
-            Event::Start(Tag::Strong) | Event::End(Tag::Strong) => {
-                if extract.is_active() {
-                    extract.append("**")?;
-                }
+            Event::Start(Tag::Strong) | Event::End(Tag::Strong) if extract.is_active() => {
+                extract.append("**")?;
             }
-            Event::Code(ref text) => {
-                if extract.is_active() {
-                    extract.append("`")?;
-                    extract.append(text)?;
-                    extract.append("`")?;
-                }
+            Event::Code(ref text) if extract.is_active() => {
+                extract.append("`")?;
+                extract.append(text)?;
+                extract.append("`")?;
             }
-            Event::Text(ref text) => {
-                if extract.is_active() {
-                    extract.append(text)?;
-                }
+            Event::Text(ref text) if extract.is_active() => {
+                extract.append(text)?;
             }
             _ => (),
         }
@@ -122,15 +115,52 @@ pub fn take_title<'a>(text: &str) -> Result<String> {
 }
 
 pub fn split_title(input: &str) -> Result<(String, String)> {
+    let (title, rest) = split_title_verbatim(input)?;
+
+    Ok((title, rest.trim().into()))
+}
+
+/// Splits the title out like [`split_title`] but keeps the whitespace around the rest, so `# {title}{rest}` gives
+/// the text back.
+pub fn split_title_verbatim(input: &str) -> Result<(String, String)> {
     let title = take_title(input)?;
 
     if let Some((_, rest)) = input.split_once(&format!("# {}", &title)) {
-        return Ok((title, rest.trim().into()));
+        return Ok((title, rest.into()));
     }
 
     Err(ExtractError::NotFound.into())
 }
 
+/// Flattens a markdown text into plain text, e.g. to be indexed for search.
+pub fn plain_text(input: &str) -> String {
+    let mut recipient = String::new();
+
+    for event in Parser::new(input) {
+        match event {
+            Event::Text(text) | Event::Code(text) => recipient.push_str(&text),
+            Event::SoftBreak | Event::HardBreak => recipient.push(' '),
+            Event::End(tag) if is_block(&tag) => recipient.push(' '),
+            _ => (),
+        }
+    }
+
+    recipient.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+fn is_block(tag: &Tag) -> bool {
+    matches!(
+        tag,
+        Tag::Paragraph
+            | Tag::Heading(_)
+            | Tag::BlockQuote
+            | Tag::CodeBlock(_)
+            | Tag::Item
+            | Tag::TableCell
+            | Tag::FootnoteDefinition(_)
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -181,4 +211,13 @@ It was a bright cold day in April, and the clocks were striking thirteen."#;
         assert_eq!(actual, expected);
         Ok(())
     }
+
+    #[test]
+    fn flatten_to_plain_text() {
+        let text =
+            "# Vapour\n\nA _steam_, format\nfor `json`.\n\n- [One](https://example.org)\n- Two";
+        let actual = plain_text(text);
+
+        assert_eq!(actual, "Vapour A steam, format for json. One Two");
+    }
 }
@@ -0,0 +1,265 @@
+//! This module parses the statements of a `flowchart` (or `graph`).
+//!
+//! Statements are chains of nodes joined by links, e.g. `A[Start] -->|go| B{Ready?} -.-> C & D`. Subgraphs are not
+//! supported.
+use lazy_static::lazy_static;
+use regex::Regex;
+
+use super::{label_lines, Diagram, MermaidError, Shape, Stroke};
+
+lazy_static! {
+    static ref ID_RE: Regex = Regex::new(r"^\w+").unwrap();
+    static ref LINK_RE: Regex =
+        Regex::new(r"^(-{2,}>|-{3,}|={2,}>|={3,}|-\.+->|-\.+-)(?:\|([^|]*)\|)?").unwrap();
+    static ref LABELLED_LINK_RE: Regex =
+        Regex::new(r"^(?:--|==|-\.)\s*([^|]+?)\s*(-{2,}>|-{3,}|={2,}>|={3,}|\.-+>|\.-+)").unwrap();
+}
+
+/// The delimiters of every shape, longest first so `([` is not taken for `(`.
+const SHAPES: [(&str, &str, Shape); 10] = [
+    ("([", "])", Shape::Stadium),
+    ("((", "))", Shape::Circle),
+    ("[[", "]]", Shape::Rectangle),
+    ("[(", ")]", Shape::Rectangle),
+    ("[/", "/]", Shape::Rectangle),
+    ("{{", "}}", Shape::Rectangle),
+    ("[", "]", Shape::Rectangle),
+    ("(", ")", Shape::Rounded),
+    ("{", "}", Shape::Diamond),
+    (">", "]", Shape::Rectangle),
+];
+
+pub(super) fn parse(diagram: &mut Diagram, lines: &[(usize, &str)]) -> Result<(), MermaidError> {
+    for &(number, line) in lines {
+        let keyword = line.split_whitespace().next().unwrap_or_default();
+
+        match keyword {
+            "subgraph" | "end" => {
+                return Err(MermaidError::syntax(number, "subgraphs are not supported"))
+            }
+            "direction" => continue,
+            _ => (),
+        }
+
+        for statement in line.split(';').map(str::trim).filter(|s| !s.is_empty()) {
+            chain(diagram, number, statement)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Parses a chain of node groups joined by links.
+fn chain(diagram: &mut Diagram, number: usize, statement: &str) -> Result<(), MermaidError> {
+    let (mut previous, mut rest) = group(diagram, number, statement)?;
+
+    while !rest.is_empty() {
+        let (link, after) = link(number, rest)?;
+        let (current, after) = group(diagram, number, after)?;
+
+        for &from in &previous {
+            for &to in &current {
+                diagram.edge(from, to, link.label.clone(), link.stroke, link.arrow);
+            }
+        }
+
+        previous = current;
+        rest = after;
+    }
+
+    Ok(())
+}
+
+/// Parses nodes joined by `&`, returning their indices and the rest of the statement.
+fn group<'a>(
+    diagram: &mut Diagram,
+    number: usize,
+    text: &'a str,
+) -> Result<(Vec<usize>, &'a str), MermaidError> {
+    let mut result = Vec::new();
+    let mut rest = text.trim_start();
+
+    loop {
+        let (index, after) = node(diagram, number, rest)?;
+        result.push(index);
+        rest = after.trim_start();
+
+        match rest.strip_prefix('&') {
+            Some(after) => rest = after.trim_start(),
+            None => return Ok((result, rest)),
+        }
+    }
+}
+
+fn node<'a>(
+    diagram: &mut Diagram,
+    number: usize,
+    text: &'a str,
+) -> Result<(usize, &'a str), MermaidError> {
+    let id = ID_RE
+        .find(text)
+        .ok_or_else(|| MermaidError::syntax(number, format!("expected a node at `{}`", text)))?
+        .as_str();
+    let mut rest = &text[id.len()..];
+    let mut shape = None;
+
+    for (open, close, candidate) in SHAPES.iter() {
+        if let Some(after) = rest.strip_prefix(open) {
+            let end = closing(after, close).ok_or_else(|| {
+                MermaidError::syntax(number, format!("missing `{}` for node `{}`", close, id))
+            })?;
+
+            shape = Some((*candidate, label_lines(&after[..end])));
+            rest = &after[end + close.len()..];
+            break;
+        }
+    }
+
+    if let Some(after) = rest.strip_prefix(":::") {
+        rest = &after[ID_RE.find(after).map_or(0, |m| m.end())..];
+    }
+
+    let index = diagram.node(id, Shape::Rectangle);
+
+    if let Some((shape, label)) = shape {
+        diagram.nodes[index].shape = shape;
+        diagram.nodes[index].label = label;
+    }
+
+    Ok((index, rest))
+}
+
+/// Finds the closing delimiter, skipping over a quoted label.
+fn closing(text: &str, close: &str) -> Option<usize> {
+    let start = match text.strip_prefix('"') {
+        Some(quoted) => quoted.find('"')? + 2,
+        None => 0,
+    };
+
+    text[start..].find(close).map(|end| start + end)
+}
+
+#[derive(Debug, Clone, PartialEq)]
+struct Link {
+    label: Option<String>,
+    stroke: Stroke,
+    arrow: bool,
+}
+
+fn link(number: usize, text: &str) -> Result<(Link, &str), MermaidError> {
+    let (arrow, label, end) = if let Some(groups) = LINK_RE.captures(text) {
+        (
+            groups.get(1).expect("link").as_str(),
+            groups.get(2).map(|m| m.as_str().trim().to_string()),
+            groups.get(0).expect("match").end(),
+        )
+    } else if let Some(groups) = LABELLED_LINK_RE.captures(text) {
+        (
+            groups.get(2).expect("link").as_str(),
+            groups.get(1).map(|m| m.as_str().trim().to_string()),
+            groups.get(0).expect("match").end(),
+        )
+    } else {
+        return Err(MermaidError::syntax(
+            number,
+            format!("expected a link at `{}`", text),
+        ));
+    };
+    let stroke = if arrow.contains('.') {
+        Stroke::Dotted
+    } else if arrow.contains('=') {
+        Stroke::Thick
+    } else {
+        Stroke::Solid
+    };
+    let link = Link {
+        label,
+        stroke,
+        arrow: arrow.ends_with('>'),
+    };
+
+    Ok((link, &text[end..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse, Diagram, Edge};
+    use super::*;
+
+    fn edges(diagram: &Diagram) -> Vec<(&str, &str, Option<&str>)> {
+        diagram
+            .edges
+            .iter()
+            .map(
+                |Edge {
+                     from, to, label, ..
+                 }| {
+                    (
+                        diagram.nodes[*from].id.as_str(),
+                        diagram.nodes[*to].id.as_str(),
+                        label.as_deref(),
+                    )
+                },
+            )
+            .collect()
+    }
+
+    #[test]
+    fn parse_shapes() -> Result<(), MermaidError> {
+        let diagram = parse(
+            "flowchart TD\n  A([Start]) --> B{\"Ready?\"}\n  B --> C((Done))\n  B --> D(Wait<br>a bit)\n  D --> E[Retry]:::warning",
+        )?;
+        let shapes: Vec<_> = diagram.nodes.iter().map(|node| node.shape).collect();
+
+        assert_eq!(
+            shapes,
+            vec![
+                Shape::Stadium,
+                Shape::Diamond,
+                Shape::Circle,
+                Shape::Rounded,
+                Shape::Rectangle
+            ]
+        );
+        assert_eq!(diagram.nodes[1].label, vec!["Ready?"]);
+        assert_eq!(diagram.nodes[3].label, vec!["Wait", "a bit"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn parse_links() -> Result<(), MermaidError> {
+        let diagram = parse(
+            "graph LR\n  A -->|yes| B -.-> C & D\n  A -- no --> E; E === A\n  E -. maybe .-> F\n  classDef warning fill:#f96",
+        )?;
+
+        assert_eq!(
+            edges(&diagram),
+            vec![
+                ("A", "B", Some("yes")),
+                ("B", "C", None),
+                ("B", "D", None),
+                ("A", "E", Some("no")),
+                ("E", "A", None),
+                ("E", "F", Some("maybe")),
+            ]
+        );
+        assert_eq!(diagram.edges[1].stroke, Stroke::Dotted);
+        assert_eq!(diagram.edges[4].stroke, Stroke::Thick);
+        assert!(!diagram.edges[4].arrow);
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_invalid_statements() {
+        assert_eq!(
+            parse("flowchart\nA --> B\nsubgraph one\nend"),
+            Err(MermaidError::syntax(3, "subgraphs are not supported"))
+        );
+        assert_eq!(
+            parse("flowchart\nA ~~> B"),
+            Err(MermaidError::syntax(2, "expected a link at `~~> B`"))
+        );
+    }
+}
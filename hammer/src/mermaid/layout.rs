@@ -0,0 +1,728 @@
+//! This module places the nodes of a diagram and routes the edges between them.
+//!
+//! It is a simplified layered (Sugiyama) layout: cycles are broken by reversing back edges, nodes are ranked by their
+//! longest path from a source, edges spanning several ranks bend through dummy points and the order within each rank
+//! is refined with barycenters. Good enough for the small diagrams in the corpus, not a general purpose engine.
+use super::{Diagram, Direction, Node, Shape};
+
+/// The estimated width of a character of label text.
+const CHAR_WIDTH: f64 = 8.0;
+const LINE_HEIGHT: f64 = 18.0;
+const PADDING_X: f64 = 16.0;
+const PADDING_Y: f64 = 10.0;
+const NODE_GAP: f64 = 40.0;
+const RANK_GAP: f64 = 60.0;
+const DUMMY_SIZE: f64 = 20.0;
+const LOOP_SIZE: f64 = 30.0;
+/// How far apart edges between the same pair of nodes bend from each other.
+const BEND: f64 = 24.0;
+const MARGIN: f64 = 10.0;
+const SWEEPS: usize = 4;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Point {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Point {
+    fn new(x: f64, y: f64) -> Self {
+        Point { x, y }
+    }
+
+    fn lerp(self, other: Point, t: f64) -> Point {
+        Point::new(
+            self.x + (other.x - self.x) * t,
+            self.y + (other.y - self.y) * t,
+        )
+    }
+
+    fn distance(self, other: Point) -> f64 {
+        (other.x - self.x).hypot(other.y - self.y)
+    }
+
+    fn shift(&mut self, dx: f64, dy: f64) {
+        self.x += dx;
+        self.y += dy;
+    }
+}
+
+/// A box given by its center and size.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Frame {
+    pub center: Point,
+    pub width: f64,
+    pub height: f64,
+}
+
+impl Frame {
+    pub fn left(&self) -> f64 {
+        self.center.x - self.width / 2.0
+    }
+
+    pub fn top(&self) -> f64 {
+        self.center.y - self.height / 2.0
+    }
+
+    pub fn right(&self) -> f64 {
+        self.center.x + self.width / 2.0
+    }
+
+    pub fn bottom(&self) -> f64 {
+        self.center.y + self.height / 2.0
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Path {
+    Line(Vec<Point>),
+    Quadratic(Point, Point, Point),
+    Cubic(Point, Point, Point, Point),
+}
+
+impl Path {
+    fn points_mut(&mut self) -> Vec<&mut Point> {
+        match self {
+            Path::Line(points) => points.iter_mut().collect(),
+            Path::Quadratic(a, b, c) => vec![a, b, c],
+            Path::Cubic(a, b, c, d) => vec![a, b, c, d],
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Route {
+    pub path: Path,
+    pub label: Option<Frame>,
+}
+
+/// The position of every node and the route of every edge, in the same order as in the diagram.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Layout {
+    pub width: f64,
+    pub height: f64,
+    pub nodes: Vec<Frame>,
+    pub edges: Vec<Route>,
+}
+
+/// The estimated size of the given lines of text.
+pub fn text_size(lines: &[String]) -> (f64, f64) {
+    let longest = lines
+        .iter()
+        .map(|line| line.chars().count())
+        .max()
+        .unwrap_or(0);
+
+    (
+        longest as f64 * CHAR_WIDTH,
+        lines.len() as f64 * LINE_HEIGHT,
+    )
+}
+
+fn node_size(node: &Node) -> (f64, f64) {
+    let (width, height) = text_size(&node.label);
+
+    match node.shape {
+        Shape::Start | Shape::End => (16.0, 16.0),
+        Shape::Diamond if node.label.is_empty() => (28.0, 28.0),
+        Shape::Diamond => {
+            let width = width + height + 2.0 * PADDING_X;
+
+            (width, (width * 0.6).max(height + 2.0 * PADDING_Y))
+        }
+        Shape::Circle => {
+            let diameter = width.max(height) + 2.0 * PADDING_X;
+
+            (diameter, diameter)
+        }
+        Shape::Stadium => (width + height + 2.0 * PADDING_X, height + 2.0 * PADDING_Y),
+        Shape::Rectangle | Shape::Rounded => (width + 2.0 * PADDING_X, height + 2.0 * PADDING_Y),
+    }
+}
+
+/// The point where the line from the center of the node towards the given point leaves the node.
+fn boundary(frame: &Frame, shape: Shape, towards: Point) -> Point {
+    let dx = towards.x - frame.center.x;
+    let dy = towards.y - frame.center.y;
+
+    if dx == 0.0 && dy == 0.0 {
+        return frame.center;
+    }
+
+    let (a, b) = (frame.width / 2.0, frame.height / 2.0);
+    let t = match shape {
+        Shape::Circle | Shape::Start | Shape::End => a / dx.hypot(dy),
+        Shape::Diamond => 1.0 / (dx.abs() / a + dy.abs() / b),
+        _ => (a / dx.abs()).min(b / dy.abs()),
+    };
+
+    frame.center.lerp(towards, t)
+}
+
+/// Lays out the given diagram.
+pub fn layout(diagram: &Diagram) -> Layout {
+    let horizontal = matches!(
+        diagram.direction,
+        Direction::LeftRight | Direction::RightLeft
+    );
+    let sizes: Vec<(f64, f64)> = diagram.nodes.iter().map(node_size).collect();
+    let reversed = back_edges(diagram);
+    // Edges pointing down the ranks, self loops aside.
+    let downward: Vec<Option<(usize, usize)>> = diagram
+        .edges
+        .iter()
+        .zip(&reversed)
+        .map(|(edge, &reversed)| match (edge.from == edge.to, reversed) {
+            (true, _) => None,
+            (false, true) => Some((edge.to, edge.from)),
+            (false, false) => Some((edge.from, edge.to)),
+        })
+        .collect();
+    let ranks = rank(diagram.nodes.len(), &downward);
+
+    // Every node and dummy point is an item with a rank and a size across and along the ranks.
+    let mut item_ranks = ranks.clone();
+    let mut extents: Vec<(f64, f64)> = sizes
+        .iter()
+        .map(|&(width, height)| {
+            if horizontal {
+                (height, width)
+            } else {
+                (width, height)
+            }
+        })
+        .collect();
+    let mut chains: Vec<Option<Vec<usize>>> = Vec::new();
+
+    for edge in &downward {
+        chains.push(edge.map(|(from, to)| {
+            let mut chain = vec![from];
+
+            for rank in ranks[from] + 1..ranks[to] {
+                item_ranks.push(rank);
+                extents.push((DUMMY_SIZE, 0.0));
+                chain.push(item_ranks.len() - 1);
+            }
+
+            chain.push(to);
+            chain
+        }));
+    }
+
+    let count = item_ranks.len();
+    let mut upper = vec![Vec::new(); count];
+    let mut lower = vec![Vec::new(); count];
+
+    for chain in chains.iter().flatten() {
+        for pair in chain.windows(2) {
+            lower[pair[0]].push(pair[1]);
+            upper[pair[1]].push(pair[0]);
+        }
+    }
+
+    let layers = order(&item_ranks, &upper, &lower);
+    let across = position_across(&layers, &extents, &upper, &lower);
+    let along = position_along(&layers, &extents);
+
+    let centers: Vec<Point> = (0..count)
+        .map(|item| {
+            let (across, along) = (across[item], along[item_ranks[item]]);
+
+            match diagram.direction {
+                Direction::TopBottom => Point::new(across, along),
+                Direction::BottomTop => Point::new(across, -along),
+                Direction::LeftRight => Point::new(along, across),
+                Direction::RightLeft => Point::new(-along, across),
+            }
+        })
+        .collect();
+    let nodes: Vec<Frame> = sizes
+        .iter()
+        .zip(&centers)
+        .map(|(&(width, height), &center)| Frame {
+            center,
+            width,
+            height,
+        })
+        .collect();
+    let edges = route(diagram, &nodes, &centers, &chains, &reversed);
+
+    fit(nodes, edges)
+}
+
+/// Finds the edges to reverse for the diagram to have no cycles, walking depth first from the nodes nothing points
+/// to.
+fn back_edges(diagram: &Diagram) -> Vec<bool> {
+    fn visit(diagram: &Diagram, node: usize, state: &mut [u8], reversed: &mut [bool]) {
+        state[node] = 1;
+
+        for (index, edge) in diagram.edges.iter().enumerate() {
+            if edge.from != node || edge.to == node {
+                continue;
+            }
+
+            match state[edge.to] {
+                0 => visit(diagram, edge.to, state, reversed),
+                1 => reversed[index] = true,
+                _ => (),
+            }
+        }
+
+        state[node] = 2;
+    }
+
+    let count = diagram.nodes.len();
+    let mut state = vec![0; count];
+    let mut reversed = vec![false; diagram.edges.len()];
+    let is_source = |node: usize| {
+        !diagram
+            .edges
+            .iter()
+            .any(|edge| edge.to == node && edge.from != node)
+    };
+    let roots = (0..count)
+        .filter(|&node| is_source(node))
+        .chain((0..count).filter(|&node| !is_source(node)));
+
+    for root in roots.collect::<Vec<_>>() {
+        if state[root] == 0 {
+            visit(diagram, root, &mut state, &mut reversed);
+        }
+    }
+
+    reversed
+}
+
+/// Ranks every node by its longest path from a node nothing points to.
+fn rank(count: usize, edges: &[Option<(usize, usize)>]) -> Vec<usize> {
+    let mut ranks = vec![0; count];
+    let mut incoming = vec![0; count];
+
+    for &(_, to) in edges.iter().flatten() {
+        incoming[to] += 1;
+    }
+
+    let mut queue: Vec<usize> = (0..count).filter(|&node| incoming[node] == 0).collect();
+    let mut next = 0;
+
+    while next < queue.len() {
+        let node = queue[next];
+        next += 1;
+
+        for &(from, to) in edges.iter().flatten() {
+            if from == node {
+                ranks[to] = ranks[to].max(ranks[node] + 1);
+                incoming[to] -= 1;
+
+                if incoming[to] == 0 {
+                    queue.push(to);
+                }
+            }
+        }
+    }
+
+    ranks
+}
+
+/// Orders the items within each rank to reduce crossings.
+fn order(ranks: &[usize], upper: &[Vec<usize>], lower: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let depth = ranks.iter().max().map_or(0, |rank| rank + 1);
+    let mut layers = vec![Vec::new(); depth];
+    let mut positions = vec![0.0; ranks.len()];
+
+    for (item, &rank) in ranks.iter().enumerate() {
+        positions[item] = layers[rank].len() as f64;
+        layers[rank].push(item);
+    }
+
+    for _ in 0..SWEEPS {
+        for layer in layers.iter_mut().skip(1) {
+            sort_by_barycenter(layer, upper, &mut positions);
+        }
+
+        for layer in layers.iter_mut().rev().skip(1) {
+            sort_by_barycenter(layer, lower, &mut positions);
+        }
+    }
+
+    layers
+}
+
+fn sort_by_barycenter(layer: &mut Vec<usize>, neighbours: &[Vec<usize>], positions: &mut [f64]) {
+    let barycenters: Vec<(usize, f64)> = layer
+        .iter()
+        .map(|&item| {
+            (
+                item,
+                barycenter(&neighbours[item], positions, positions[item]),
+            )
+        })
+        .collect();
+    let mut sorted = barycenters;
+
+    sorted.sort_by(|a, b| a.1.partial_cmp(&b.1).expect("comparable barycenters"));
+
+    *layer = sorted.into_iter().map(|(item, _)| item).collect();
+
+    for (index, &item) in layer.iter().enumerate() {
+        positions[item] = index as f64;
+    }
+}
+
+fn barycenter(neighbours: &[usize], positions: &[f64], default: f64) -> f64 {
+    if neighbours.is_empty() {
+        return default;
+    }
+
+    neighbours.iter().map(|&item| positions[item]).sum::<f64>() / neighbours.len() as f64
+}
+
+/// Positions the items across the ranks, pulling each towards its neighbours while keeping them apart.
+fn position_across(
+    layers: &[Vec<usize>],
+    extents: &[(f64, f64)],
+    upper: &[Vec<usize>],
+    lower: &[Vec<usize>],
+) -> Vec<f64> {
+    let mut across = vec![0.0; extents.len()];
+
+    for layer in layers {
+        pack(layer, extents, &mut across, None);
+    }
+
+    for _ in 0..SWEEPS {
+        for layer in layers.iter().skip(1) {
+            pack(layer, extents, &mut across, Some(upper));
+        }
+
+        for layer in layers.iter().rev().skip(1) {
+            pack(layer, extents, &mut across, Some(lower));
+        }
+    }
+
+    across
+}
+
+/// Places the items of a layer as close as possible to the barycenter of their neighbours, if any, in order.
+fn pack(
+    layer: &[usize],
+    extents: &[(f64, f64)],
+    across: &mut [f64],
+    neighbours: Option<&[Vec<usize>]>,
+) {
+    let desired: Vec<f64> = layer
+        .iter()
+        .map(|&item| match neighbours {
+            Some(neighbours) => barycenter(&neighbours[item], across, across[item]),
+            None => 0.0,
+        })
+        .collect();
+    let mut placed: Vec<f64> = Vec::with_capacity(layer.len());
+
+    for (index, &item) in layer.iter().enumerate() {
+        let position = match index {
+            0 => desired[0],
+            _ => {
+                let previous = layer[index - 1];
+                let gap = if extents[previous].1 == 0.0 || extents[item].1 == 0.0 {
+                    NODE_GAP / 2.0
+                } else {
+                    NODE_GAP
+                };
+                let minimum =
+                    placed[index - 1] + (extents[previous].0 + extents[item].0) / 2.0 + gap;
+
+                desired[index].max(minimum)
+            }
+        };
+
+        placed.push(position);
+    }
+
+    // Pushing items apart only moves them one way so the layer is shifted back to balance it.
+    let shift = desired
+        .iter()
+        .zip(&placed)
+        .map(|(desired, placed)| desired - placed)
+        .sum::<f64>()
+        / layer.len().max(1) as f64;
+
+    for (&item, position) in layer.iter().zip(placed) {
+        across[item] = position + shift;
+    }
+}
+
+/// Positions the ranks one after the other, each as deep as its deepest item.
+fn position_along(layers: &[Vec<usize>], extents: &[(f64, f64)]) -> Vec<f64> {
+    let mut along = Vec::with_capacity(layers.len());
+    let mut previous: Option<(f64, f64)> = None;
+
+    for layer in layers {
+        let depth = layer
+            .iter()
+            .map(|&item| extents[item].1)
+            .fold(0.0, f64::max);
+        let center = match previous {
+            Some((center, previous_depth)) => center + (previous_depth + depth) / 2.0 + RANK_GAP,
+            None => depth / 2.0,
+        };
+
+        along.push(center);
+        previous = Some((center, depth));
+    }
+
+    along
+}
+
+fn route(
+    diagram: &Diagram,
+    nodes: &[Frame],
+    centers: &[Point],
+    chains: &[Option<Vec<usize>>],
+    reversed: &[bool],
+) -> Vec<Route> {
+    let mut routes = Vec::new();
+
+    for (index, edge) in diagram.edges.iter().enumerate() {
+        let (source, target) = (&nodes[edge.from], &nodes[edge.to]);
+        let (source_shape, target_shape) =
+            (diagram.nodes[edge.from].shape, diagram.nodes[edge.to].shape);
+        let label = edge.label.as_ref().map(|label| {
+            let (width, height) = text_size(std::slice::from_ref(label));
+
+            (width + 8.0, height + 4.0)
+        });
+        // Edges sharing the same ends are told apart by bending them by their position among their siblings.
+        let siblings: Vec<usize> = diagram
+            .edges
+            .iter()
+            .enumerate()
+            .filter(|(_, other)| {
+                (other.from, other.to) == (edge.from, edge.to)
+                    || (other.from, other.to) == (edge.to, edge.from)
+            })
+            .map(|(index, _)| index)
+            .collect();
+        let rank = siblings
+            .iter()
+            .position(|&sibling| sibling == index)
+            .unwrap_or(0) as f64;
+
+        let route = match &chains[index] {
+            None => {
+                let size = LOOP_SIZE + rank * LOOP_SIZE / 2.0;
+                let right = source.right();
+                let start = boundary(
+                    source,
+                    source_shape,
+                    Point::new(right + source.width, source.center.y - source.height / 4.0),
+                );
+                let end = boundary(
+                    source,
+                    source_shape,
+                    Point::new(right + source.width, source.center.y + source.height / 4.0),
+                );
+                let path = Path::Cubic(
+                    start,
+                    Point::new(right + size, source.top()),
+                    Point::new(right + size, source.bottom()),
+                    end,
+                );
+                let label = label.map(|(width, height)| Frame {
+                    center: Point::new(right + size * 0.75 + width / 2.0 + 4.0, source.center.y),
+                    width,
+                    height,
+                });
+
+                Route { path, label }
+            }
+            Some(chain) if chain.len() == 2 && siblings.len() > 1 => {
+                // The perpendicular is taken from the lowest node so edges in opposite directions bend apart.
+                let (first, second) = if edge.from < edge.to {
+                    (source.center, target.center)
+                } else {
+                    (target.center, source.center)
+                };
+                let length = first.distance(second).max(1.0);
+                let offset = (rank - (siblings.len() - 1) as f64 / 2.0) * BEND * 2.0;
+                let middle = source.center.lerp(target.center, 0.5);
+                let control = Point::new(
+                    middle.x - (second.y - first.y) / length * offset,
+                    middle.y + (second.x - first.x) / length * offset,
+                );
+                let start = boundary(source, source_shape, control);
+                let end = boundary(target, target_shape, control);
+                let apex = start.lerp(control, 0.5).lerp(control.lerp(end, 0.5), 0.5);
+                let label = label.map(|(width, height)| Frame {
+                    center: apex,
+                    width,
+                    height,
+                });
+
+                Route {
+                    path: Path::Quadratic(start, control, end),
+                    label,
+                }
+            }
+            Some(chain) => {
+                let mut points: Vec<Point> = chain.iter().map(|&item| centers[item]).collect();
+
+                if reversed[index] {
+                    points.reverse();
+                }
+
+                let last = points.len() - 1;
+                points[0] = boundary(source, source_shape, points[1]);
+                points[last] = boundary(target, target_shape, points[last - 1]);
+
+                let label = label.map(|(width, height)| Frame {
+                    center: midpoint(&points),
+                    width,
+                    height,
+                });
+
+                Route {
+                    path: Path::Line(points),
+                    label,
+                }
+            }
+        };
+
+        routes.push(route);
+    }
+
+    routes
+}
+
+/// The point halfway along the given polyline.
+fn midpoint(points: &[Point]) -> Point {
+    let total: f64 = points
+        .windows(2)
+        .map(|pair| pair[0].distance(pair[1]))
+        .sum();
+    let mut remaining = total / 2.0;
+
+    for pair in points.windows(2) {
+        let length = pair[0].distance(pair[1]);
+
+        if length > 0.0 && remaining <= length {
+            return pair[0].lerp(pair[1], remaining / length);
+        }
+
+        remaining -= length;
+    }
+
+    points[0]
+}
+
+/// Moves everything so the drawing starts at the margin and measures it.
+fn fit(mut nodes: Vec<Frame>, mut edges: Vec<Route>) -> Layout {
+    let mut frames: Vec<Frame> = nodes.clone();
+    frames.extend(edges.iter().filter_map(|route| route.label));
+
+    let mut points: Vec<Point> = frames
+        .iter()
+        .flat_map(|frame| {
+            vec![
+                Point::new(frame.left(), frame.top()),
+                Point::new(frame.right(), frame.bottom()),
+            ]
+        })
+        .collect();
+
+    for route in edges.iter_mut() {
+        points.extend(route.path.points_mut().into_iter().map(|point| *point));
+    }
+
+    let min_x = points.iter().map(|p| p.x).fold(f64::INFINITY, f64::min);
+    let min_y = points.iter().map(|p| p.y).fold(f64::INFINITY, f64::min);
+    let max_x = points.iter().map(|p| p.x).fold(f64::NEG_INFINITY, f64::max);
+    let max_y = points.iter().map(|p| p.y).fold(f64::NEG_INFINITY, f64::max);
+
+    if points.is_empty() {
+        return Layout {
+            width: 2.0 * MARGIN,
+            height: 2.0 * MARGIN,
+            nodes,
+            edges,
+        };
+    }
+
+    let (dx, dy) = (MARGIN - min_x, MARGIN - min_y);
+
+    for node in nodes.iter_mut() {
+        node.center.shift(dx, dy);
+    }
+
+    for route in edges.iter_mut() {
+        for point in route.path.points_mut() {
+            point.shift(dx, dy);
+        }
+
+        if let Some(label) = route.label.as_mut() {
+            label.center.shift(dx, dy);
+        }
+    }
+
+    Layout {
+        width: max_x - min_x + 2.0 * MARGIN,
+        height: max_y - min_y + 2.0 * MARGIN,
+        nodes,
+        edges,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::parse;
+    use super::*;
+
+    fn overlap(a: &Frame, b: &Frame) -> bool {
+        a.left() < b.right() && b.left() < a.right() && a.top() < b.bottom() && b.top() < a.bottom()
+    }
+
+    #[test]
+    fn rank_state_machine() -> Result<(), super::super::MermaidError> {
+        let diagram = parse(
+            "stateDiagram-v2\n[*] --> Identified\nIdentified --> Proposed\nProposed --> Endorsed\nProposed --> Disavowed\nEndorsed --> Endorsed\nEndorsed --> Retired\nRetired --> [*]\nDisavowed --> Proposed",
+        )?;
+        let layout = layout(&diagram);
+        let y = |id: &str| {
+            let index = diagram.nodes.iter().position(|node| node.id == id).unwrap();
+            layout.nodes[index].center.y
+        };
+
+        assert!(y("Identified") < y("Proposed"));
+        assert!(
+            y("Proposed") < y("Disavowed"),
+            "back edges don't move ranks"
+        );
+        assert_eq!(y("Endorsed"), y("Disavowed"));
+        assert!(y("Retired") < y("[*] end"));
+
+        for (index, a) in layout.nodes.iter().enumerate() {
+            for b in &layout.nodes[index + 1..] {
+                assert!(!overlap(a, b), "{:?} overlaps {:?}", a, b);
+            }
+        }
+
+        assert!(matches!(layout.edges[4].path, Path::Cubic(..)));
+        assert!(matches!(layout.edges[3].path, Path::Quadratic(..)));
+        assert!(matches!(layout.edges[7].path, Path::Quadratic(..)));
+
+        Ok(())
+    }
+
+    #[test]
+    fn left_to_right() -> Result<(), super::super::MermaidError> {
+        let diagram = parse("flowchart LR\nA --> B --> C\nA --> C")?;
+        let layout = layout(&diagram);
+
+        assert!(layout.nodes[0].center.x < layout.nodes[1].center.x);
+        assert!(layout.nodes[1].center.x < layout.nodes[2].center.x);
+        assert!(matches!(&layout.edges[2].path, Path::Line(points) if points.len() == 3));
+        assert!(layout.nodes.iter().all(|node| node.left() >= MARGIN));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,262 @@
+//! This module renders [Mermaid] diagrams to SVG so diagrams never drift from their source.
+//!
+//! Only state diagrams (`stateDiagram-v2`) and flowcharts (`flowchart` or `graph`) are supported. Styling statements
+//! such as `classDef` are ignored and the diagram is drawn with a fixed look.
+//!
+//! [Mermaid]: https://mermaid-js.github.io/
+use anyhow::Result;
+use thiserror::Error;
+
+mod flowchart;
+mod layout;
+mod state;
+mod svg;
+
+pub use layout::Layout;
+
+#[derive(Debug, Clone, PartialEq, Error)]
+pub enum MermaidError {
+    #[error("The diagram is empty.")]
+    Empty,
+    #[error(
+        "`{0}` diagrams are not supported. Expected `stateDiagram-v2`, `flowchart` or `graph`."
+    )]
+    Unsupported(String),
+    #[error("line {line}: {message}")]
+    Syntax { line: usize, message: String },
+}
+
+impl MermaidError {
+    fn syntax(line: usize, message: impl Into<String>) -> Self {
+        MermaidError::Syntax {
+            line,
+            message: message.into(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Kind {
+    State,
+    Flowchart,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Direction {
+    TopBottom,
+    BottomTop,
+    LeftRight,
+    RightLeft,
+}
+
+impl Direction {
+    fn parse(s: &str) -> Option<Direction> {
+        match s {
+            "TB" | "TD" => Some(Direction::TopBottom),
+            "BT" => Some(Direction::BottomTop),
+            "LR" => Some(Direction::LeftRight),
+            "RL" => Some(Direction::RightLeft),
+            _ => None,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Shape {
+    Rectangle,
+    Rounded,
+    Stadium,
+    Circle,
+    Diamond,
+    /// The initial pseudo-state of a state diagram.
+    Start,
+    /// The final pseudo-state of a state diagram.
+    End,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Node {
+    pub id: String,
+    /// The lines of text to display.
+    pub label: Vec<String>,
+    pub shape: Shape,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Stroke {
+    Solid,
+    Dotted,
+    Thick,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Edge {
+    /// The index of the node the edge comes from.
+    pub from: usize,
+    /// The index of the node the edge goes to.
+    pub to: usize,
+    pub label: Option<String>,
+    pub stroke: Stroke,
+    pub arrow: bool,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagram {
+    pub kind: Kind,
+    pub direction: Direction,
+    /// The accessible title, from `accTitle`.
+    pub title: Option<String>,
+    pub nodes: Vec<Node>,
+    pub edges: Vec<Edge>,
+}
+
+impl Diagram {
+    fn new(kind: Kind, direction: Direction) -> Self {
+        Diagram {
+            kind,
+            direction,
+            title: None,
+            nodes: Vec::new(),
+            edges: Vec::new(),
+        }
+    }
+
+    /// Returns the index of the node with the given identifier, adding it if it doesn't exist yet.
+    fn node(&mut self, id: &str, shape: Shape) -> usize {
+        match self.nodes.iter().position(|node| node.id == id) {
+            Some(index) => index,
+            None => {
+                self.nodes.push(Node {
+                    id: id.to_string(),
+                    label: vec![id.to_string()],
+                    shape,
+                });
+                self.nodes.len() - 1
+            }
+        }
+    }
+
+    fn edge(&mut self, from: usize, to: usize, label: Option<String>, stroke: Stroke, arrow: bool) {
+        self.edges.push(Edge {
+            from,
+            to,
+            label: label.filter(|label| !label.is_empty()),
+            stroke,
+            arrow,
+        });
+    }
+}
+
+/// Parses the given Mermaid source.
+pub fn parse(source: &str) -> Result<Diagram, MermaidError> {
+    let mut lines = statements(source);
+    let (_, header) = lines.next().ok_or(MermaidError::Empty)?;
+    let mut words = header.split_whitespace();
+    let keyword = words.next().unwrap_or_default();
+    let (mut diagram, lines): (Diagram, Vec<_>) = match keyword {
+        "stateDiagram-v2" | "stateDiagram" => (
+            Diagram::new(Kind::State, Direction::TopBottom),
+            lines.collect(),
+        ),
+        "flowchart" | "graph" => {
+            let direction = match words.next() {
+                Some(s) => Direction::parse(s)
+                    .ok_or_else(|| MermaidError::syntax(1, format!("unknown direction `{}`", s)))?,
+                None => Direction::TopBottom,
+            };
+
+            (Diagram::new(Kind::Flowchart, direction), lines.collect())
+        }
+        _ => return Err(MermaidError::Unsupported(keyword.to_string())),
+    };
+    let mut body = Vec::new();
+
+    for (number, line) in lines {
+        if let Some(title) = line.strip_prefix("accTitle:") {
+            diagram.title = Some(title.trim().to_string());
+        } else if !is_ignored(line) {
+            body.push((number, line));
+        }
+    }
+
+    match diagram.kind {
+        Kind::State => state::parse(&mut diagram, &body)?,
+        Kind::Flowchart => flowchart::parse(&mut diagram, &body)?,
+    }
+
+    Ok(diagram)
+}
+
+/// Renders the given Mermaid source as an SVG document.
+pub fn render(source: &str) -> Result<String> {
+    let diagram = parse(source)?;
+    let layout = layout::layout(&diagram);
+
+    Ok(svg::render(&diagram, &layout))
+}
+
+/// Yields the meaningful lines with their line number, skipping blank lines and comments.
+fn statements(source: &str) -> impl Iterator<Item = (usize, &str)> {
+    source
+        .lines()
+        .enumerate()
+        .map(|(index, line)| (index + 1, line.trim()))
+        .filter(|(_, line)| !line.is_empty() && !line.starts_with("%%"))
+}
+
+/// Whether the statement only affects the look of the diagram.
+fn is_ignored(line: &str) -> bool {
+    let keyword = line.split_whitespace().next().unwrap_or_default();
+
+    matches!(
+        keyword,
+        "accDescr" | "accDescr:" | "class" | "classDef" | "click" | "linkStyle" | "style"
+    )
+}
+
+/// Splits a label into lines on `<br>` tags.
+fn label_lines(text: &str) -> Vec<String> {
+    let text = text.trim();
+    let text = text
+        .strip_prefix('"')
+        .and_then(|text| text.strip_suffix('"'))
+        .unwrap_or(text);
+
+    text.replace("<br/>", "<br>")
+        .replace("<br />", "<br>")
+        .split("<br>")
+        .map(|line| line.trim().to_string())
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_headers() -> Result<()> {
+        let state = parse("%% The lifecycle\nstateDiagram-v2\naccTitle: Lifecycle\n[*] --> Draft")?;
+        let flowchart = parse("graph LR\nA --> B")?;
+
+        assert_eq!(state.kind, Kind::State);
+        assert_eq!(state.title.as_deref(), Some("Lifecycle"));
+        assert_eq!(flowchart.kind, Kind::Flowchart);
+        assert_eq!(flowchart.direction, Direction::LeftRight);
+        assert_eq!(parse(" \n"), Err(MermaidError::Empty));
+        assert_eq!(
+            parse("sequenceDiagram\nA->>B: Hi"),
+            Err(MermaidError::Unsupported("sequenceDiagram".into()))
+        );
+        assert_eq!(
+            parse("flowchart XY\nA --> B"),
+            Err(MermaidError::syntax(1, "unknown direction `XY`"))
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn split_label_lines() {
+        assert_eq!(label_lines("\"One<br/>Two\""), vec!["One", "Two"]);
+    }
+}
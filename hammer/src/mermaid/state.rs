@@ -0,0 +1,143 @@
+//! This module parses the statements of a `stateDiagram-v2`.
+//!
+//! Composite states, concurrency and notes are not supported.
+use super::{label_lines, Diagram, Direction, MermaidError, Shape, Stroke};
+
+const START: &str = "[*] start";
+const END: &str = "[*] end";
+
+pub(super) fn parse(diagram: &mut Diagram, lines: &[(usize, &str)]) -> Result<(), MermaidError> {
+    for &(number, line) in lines {
+        if let Some(direction) = line.strip_prefix("direction ") {
+            diagram.direction = Direction::parse(direction.trim()).ok_or_else(|| {
+                MermaidError::syntax(number, format!("unknown direction `{}`", direction.trim()))
+            })?;
+        } else if line.starts_with("note ") || line == "end note" {
+            return Err(MermaidError::syntax(number, "notes are not supported"));
+        } else if line == "--" {
+            return Err(MermaidError::syntax(number, "concurrency is not supported"));
+        } else if line.ends_with('{') || line == "}" {
+            return Err(MermaidError::syntax(
+                number,
+                "composite states are not supported",
+            ));
+        } else if let Some(declaration) = line.strip_prefix("state ") {
+            declare(diagram, number, declaration.trim())?;
+        } else if let Some((from, rest)) = line.split_once("-->") {
+            let (to, label) = match rest.split_once(':') {
+                Some((to, label)) => (to, Some(label.trim().to_string())),
+                None => (rest, None),
+            };
+            let from = state(diagram, number, from, START)?;
+            let to = state(diagram, number, to, END)?;
+
+            diagram.edge(from, to, label, Stroke::Solid, true);
+        } else if let Some((id, description)) = line.split_once(':') {
+            let index = state(diagram, number, id, START)?;
+
+            diagram.nodes[index].label = label_lines(description);
+        } else {
+            state(diagram, number, line, START)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Handles `state "Description" as Id` and `state Id <<choice>>`.
+fn declare(diagram: &mut Diagram, number: usize, declaration: &str) -> Result<(), MermaidError> {
+    if let Some((description, id)) = declaration.split_once(" as ") {
+        let index = state(diagram, number, id, START)?;
+
+        diagram.nodes[index].label = label_lines(description);
+    } else if let Some((id, stereotype)) = declaration.split_once("<<") {
+        let index = state(diagram, number, id, START)?;
+
+        match stereotype.trim_end_matches(">>") {
+            "choice" => {
+                diagram.nodes[index].shape = Shape::Diamond;
+                diagram.nodes[index].label = Vec::new();
+            }
+            other => {
+                return Err(MermaidError::syntax(
+                    number,
+                    format!("`<<{}>>` states are not supported", other),
+                ))
+            }
+        }
+    } else {
+        state(diagram, number, declaration, START)?;
+    }
+
+    Ok(())
+}
+
+/// Returns the index of the given state. `[*]` is the start or the end state depending on the given `pseudo` state.
+fn state(
+    diagram: &mut Diagram,
+    number: usize,
+    id: &str,
+    pseudo: &str,
+) -> Result<usize, MermaidError> {
+    // Classes such as `Draft:::muted` only affect the look.
+    let id = id.split(":::").next().unwrap_or_default().trim();
+
+    if id == "[*]" {
+        let shape = if pseudo == START {
+            Shape::Start
+        } else {
+            Shape::End
+        };
+        let index = diagram.node(pseudo, shape);
+        diagram.nodes[index].label = Vec::new();
+
+        return Ok(index);
+    }
+
+    if id.is_empty() || id.contains(char::is_whitespace) {
+        return Err(MermaidError::syntax(
+            number,
+            format!("`{}` is not a valid state identifier", id),
+        ));
+    }
+
+    Ok(diagram.node(id, Shape::Rounded))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::{parse, Kind};
+    use super::*;
+
+    #[test]
+    fn parse_transitions() -> Result<(), MermaidError> {
+        let diagram = parse(
+            "stateDiagram-v2\n    direction LR\n    state \"Under review\" as Review\n    [*] --> Draft\n    Draft --> Review: submit\n    Review --> Draft: reject\n    Review --> [*]\n    Draft: A draft",
+        )?;
+        let ids: Vec<_> = diagram.nodes.iter().map(|node| node.id.as_str()).collect();
+
+        assert_eq!(diagram.kind, Kind::State);
+        assert_eq!(diagram.direction, Direction::LeftRight);
+        assert_eq!(ids, vec!["Review", START, "Draft", END]);
+        assert_eq!(diagram.nodes[0].label, vec!["Under review"]);
+        assert_eq!(diagram.nodes[2].label, vec!["A draft"]);
+        assert_eq!(diagram.nodes[1].shape, Shape::Start);
+        assert_eq!(diagram.nodes[3].shape, Shape::End);
+        assert_eq!(diagram.edges.len(), 4);
+        assert_eq!(diagram.edges[1].label.as_deref(), Some("submit"));
+        assert_eq!((diagram.edges[2].from, diagram.edges[2].to), (0, 2));
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_composite_states() {
+        assert_eq!(
+            parse("stateDiagram-v2\n[*] --> Active\nstate Active {\n[*] --> On\n}"),
+            Err(MermaidError::syntax(
+                3,
+                "composite states are not supported"
+            ))
+        );
+    }
+}
@@ -0,0 +1,296 @@
+//! This module draws a laid out diagram as SVG.
+//!
+//! The output has one element per line and no blank lines so it can be embedded in Markdown as an HTML block.
+use std::fmt::Write;
+
+use super::layout::{Frame, Layout, Path, Point, Route};
+use super::{Diagram, Edge, Kind, Node, Shape, Stroke};
+
+const FONT: &str = r#"font-family="trebuchet ms,verdana,arial,sans-serif" font-size="14""#;
+const FILL: &str = "#ECECFF";
+const LINE: &str = "#9370DB";
+const INK: &str = "#333333";
+
+pub fn render(diagram: &Diagram, layout: &Layout) -> String {
+    let mut svg = String::new();
+    let class = match diagram.kind {
+        Kind::State => "statediagram",
+        Kind::Flowchart => "flowchart",
+    };
+
+    line(
+        &mut svg,
+        format!(
+            r#"<svg xmlns="http://www.w3.org/2000/svg" class="{}" width="{}" height="{}" viewBox="0 0 {} {}" role="img">"#,
+            class,
+            number(layout.width),
+            number(layout.height),
+            number(layout.width),
+            number(layout.height)
+        ),
+    );
+
+    if let Some(title) = &diagram.title {
+        line(&mut svg, format!("<title>{}</title>", escape(title)));
+    }
+
+    line(&mut svg, "<defs>");
+    line(
+        &mut svg,
+        format!(
+            r#"<marker id="arrow" viewBox="0 0 10 10" refX="9" refY="5" markerWidth="8" markerHeight="8" orient="auto-start-reverse"><path d="M 0 0 L 10 5 L 0 10 z" fill="{}"/></marker>"#,
+            INK
+        ),
+    );
+    line(&mut svg, "</defs>");
+
+    line(&mut svg, r#"<g class="edges">"#);
+    for (edge, route) in diagram.edges.iter().zip(&layout.edges) {
+        line(&mut svg, draw_edge(edge, route));
+    }
+    line(&mut svg, "</g>");
+
+    line(&mut svg, r#"<g class="nodes">"#);
+    for (node, frame) in diagram.nodes.iter().zip(&layout.nodes) {
+        draw_node(&mut svg, node, frame);
+    }
+    line(&mut svg, "</g>");
+
+    line(&mut svg, r#"<g class="edge-labels">"#);
+    for (edge, route) in diagram.edges.iter().zip(&layout.edges) {
+        if let (Some(label), Some(frame)) = (&edge.label, &route.label) {
+            line(
+                &mut svg,
+                format!(
+                    r#"<rect x="{}" y="{}" width="{}" height="{}" fill="white" opacity="0.8"/>"#,
+                    number(frame.left()),
+                    number(frame.top()),
+                    number(frame.width),
+                    number(frame.height)
+                ),
+            );
+            draw_text(&mut svg, std::slice::from_ref(label), frame.center, INK);
+        }
+    }
+    line(&mut svg, "</g>");
+
+    line(&mut svg, "</svg>");
+
+    svg
+}
+
+fn line(svg: &mut String, text: impl AsRef<str>) {
+    svg.push_str(text.as_ref());
+    svg.push('\n');
+}
+
+fn draw_edge(edge: &Edge, route: &Route) -> String {
+    let data = match &route.path {
+        Path::Line(points) => points
+            .iter()
+            .enumerate()
+            .map(|(index, point)| {
+                let command = if index == 0 { "M" } else { "L" };
+
+                format!("{} {}", command, coordinates(point))
+            })
+            .collect::<Vec<_>>()
+            .join(" "),
+        Path::Quadratic(start, control, end) => format!(
+            "M {} Q {} {}",
+            coordinates(start),
+            coordinates(control),
+            coordinates(end)
+        ),
+        Path::Cubic(start, first, second, end) => format!(
+            "M {} C {} {} {}",
+            coordinates(start),
+            coordinates(first),
+            coordinates(second),
+            coordinates(end)
+        ),
+    };
+    let (width, dash) = match edge.stroke {
+        Stroke::Solid => ("1.5", ""),
+        Stroke::Dotted => ("1.5", r#" stroke-dasharray="3""#),
+        Stroke::Thick => ("3", ""),
+    };
+    let marker = if edge.arrow {
+        r#" marker-end="url(#arrow)""#
+    } else {
+        ""
+    };
+
+    format!(
+        r#"<path d="{}" fill="none" stroke="{}" stroke-width="{}"{}{}/>"#,
+        data, INK, width, dash, marker
+    )
+}
+
+fn draw_node(svg: &mut String, node: &Node, frame: &Frame) {
+    let (x, y) = (frame.left(), frame.top());
+    let (width, height) = (frame.width, frame.height);
+    let style = format!(r#"fill="{}" stroke="{}" stroke-width="1""#, FILL, LINE);
+    let rect = |radius: f64| {
+        format!(
+            r#"<rect x="{}" y="{}" width="{}" height="{}" rx="{}" {}/>"#,
+            number(x),
+            number(y),
+            number(width),
+            number(height),
+            number(radius),
+            style
+        )
+    };
+
+    match node.shape {
+        Shape::Start => line(
+            svg,
+            format!(
+                r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                number(frame.center.x),
+                number(frame.center.y),
+                number(width / 2.0),
+                INK
+            ),
+        ),
+        Shape::End => {
+            line(
+                svg,
+                format!(
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="white" stroke="{}" stroke-width="1.5"/>"#,
+                    number(frame.center.x),
+                    number(frame.center.y),
+                    number(width / 2.0),
+                    INK
+                ),
+            );
+            line(
+                svg,
+                format!(
+                    r#"<circle cx="{}" cy="{}" r="{}" fill="{}"/>"#,
+                    number(frame.center.x),
+                    number(frame.center.y),
+                    number(width / 2.0 - 3.0),
+                    INK
+                ),
+            );
+        }
+        Shape::Circle => line(
+            svg,
+            format!(
+                r#"<circle cx="{}" cy="{}" r="{}" {}/>"#,
+                number(frame.center.x),
+                number(frame.center.y),
+                number(width / 2.0),
+                style
+            ),
+        ),
+        Shape::Diamond => {
+            let points = [
+                Point {
+                    x: frame.center.x,
+                    y,
+                },
+                Point {
+                    x: frame.right(),
+                    y: frame.center.y,
+                },
+                Point {
+                    x: frame.center.x,
+                    y: frame.bottom(),
+                },
+                Point {
+                    x,
+                    y: frame.center.y,
+                },
+            ];
+            let points: Vec<String> = points.iter().map(coordinates).collect();
+
+            line(
+                svg,
+                format!(r#"<polygon points="{}" {}/>"#, points.join(" "), style),
+            );
+        }
+        Shape::Rectangle => line(svg, rect(0.0)),
+        Shape::Rounded => line(svg, rect(5.0)),
+        Shape::Stadium => line(svg, rect(height / 2.0)),
+    }
+
+    draw_text(svg, &node.label, frame.center, INK);
+}
+
+fn draw_text(svg: &mut String, lines: &[String], center: Point, colour: &str) {
+    if lines.is_empty() {
+        return;
+    }
+
+    let first = center.y - (lines.len() - 1) as f64 * 9.0;
+    let mut text = format!(
+        r#"<text x="{}" y="{}" {} fill="{}" text-anchor="middle" dominant-baseline="central">"#,
+        number(center.x),
+        number(first),
+        FONT,
+        colour
+    );
+
+    for (index, content) in lines.iter().enumerate() {
+        if index == 0 {
+            write!(text, "<tspan>{}</tspan>", escape(content)).expect("write to string");
+        } else {
+            write!(
+                text,
+                r#"<tspan x="{}" dy="18">{}</tspan>"#,
+                number(center.x),
+                escape(content)
+            )
+            .expect("write to string");
+        }
+    }
+
+    text.push_str("</text>");
+    line(svg, text);
+}
+
+fn coordinates(point: &Point) -> String {
+    format!("{},{}", number(point.x), number(point.y))
+}
+
+/// Formats a coordinate with one decimal at most.
+fn number(value: f64) -> String {
+    let rounded = (value * 10.0).round() / 10.0;
+
+    if rounded == rounded.trunc() {
+        format!("{}", rounded as i64)
+    } else {
+        format!("{:.1}", rounded)
+    }
+}
+
+fn escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::super::render;
+
+    #[test]
+    fn render_state_diagram() -> anyhow::Result<()> {
+        let source = "stateDiagram-v2\naccTitle: Drafts & reviews\n[*] --> Draft\nDraft --> Review: submit\nReview --> Draft: reject\nReview --> [*]";
+        let svg = render(source)?;
+
+        assert!(svg.starts_with("<svg xmlns=\"http://www.w3.org/2000/svg\" class=\"statediagram\""));
+        assert!(svg.contains("<title>Drafts &amp; reviews</title>"));
+        assert_eq!(svg.matches("<path d=").count(), 5, "4 edges and the marker");
+        assert_eq!(svg.matches("<tspan>submit</tspan>").count(), 1);
+        assert!(!svg.contains("\n\n"), "no blank lines");
+        assert!(!svg.contains("NaN"));
+        assert_eq!(svg, render(source)?, "rendering is deterministic");
+
+        Ok(())
+    }
+}
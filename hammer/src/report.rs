@@ -34,11 +34,14 @@ pub enum Action {
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub enum Entity {
+    Asset,
     Cache,
     Casestudy,
+    Dataset,
     Guidance,
     Licence,
     Organisation,
+    Process,
     Section,
     Standard,
     Theme,
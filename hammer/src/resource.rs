@@ -5,6 +5,7 @@ use std::fmt;
 use std::str::FromStr;
 
 use crate::checksum::Digest;
+use crate::report;
 
 /// A trait to be implemented by cachable resources.
 pub trait Resource<Item: Digest> {
@@ -24,46 +25,63 @@ pub trait Resource<Item: Digest> {
     // fn mass(&mut self) -> Result<Vec<Item>>;
 }
 
-/// Markdown based resource types.
+/// The kind of a story, a resource telling how standards are put to use such as a case study or a use case.
+///
+/// Stories only differ in their kind so they share a single generic implementation.
+pub trait StoryKind: fmt::Debug + Clone {
+    /// The resource type declared by the source and used to name the Zola template.
+    const RESOURCE_TYPE: ResourceType;
+    /// The cache table holding the stories of this kind.
+    const TABLE: &'static str;
+    /// The entity reported when operating on the stories of this kind.
+    const ENTITY: report::Entity;
+}
+
+#[derive(Debug, Clone)]
+pub struct CaseStudyKind;
+
+impl StoryKind for CaseStudyKind {
+    const RESOURCE_TYPE: ResourceType = ResourceType::CaseStudy;
+    const TABLE: &'static str = "case_study";
+    const ENTITY: report::Entity = report::Entity::Casestudy;
+}
+
+#[derive(Debug, Clone)]
+pub struct UseCaseKind;
+
+impl StoryKind for UseCaseKind {
+    const RESOURCE_TYPE: ResourceType = ResourceType::UseCase;
+    const TABLE: &'static str = "use_case";
+    const ENTITY: report::Entity = report::Entity::Usecase;
+}
+
+/// Resource types that can be represented on their own, mostly Markdown based.
 ///
 /// Auxiliary types such as Licence or Organisation are not considered here as they are never represented on their own.
 #[derive(Debug, Clone)]
 pub enum ResourceType {
     CaseStudy,
+    /// A CSV table described by a CSVW metadata file. Never hinted by a Markdown source.
+    Dataset,
     Guidance,
+    /// A reference document such as a process, identified by its path under `reference/`.
+    Process,
     Section,
     Standard,
     Theme,
     Topic,
-    Unknown,
     UseCase,
 }
 
 impl ResourceType {
-    /// Peeks the first few characters expecting to find a type declaration as the first property of the frontmatter
-    /// Yaml.
-    pub fn from_hint(text: &str) -> ResourceType {
-        if text.starts_with("---\ntype: standard\n") {
-            return ResourceType::Standard;
-        }
-
-        if text.starts_with("---\ntype: topic\n") {
-            return ResourceType::Topic;
-        }
-
-        if text.starts_with("---\ntype: theme\n") {
-            return ResourceType::Theme;
+    /// The resource type declared by the `type` property of a Markdown source, regardless of its case.
+    ///
+    /// Datasets are described by CSVW metadata files so they are never declared this way.
+    pub fn from_declaration(name: &str) -> Option<ResourceType> {
+        match ResourceType::from_str(&name.trim().to_lowercase()) {
+            Ok(ResourceType::Dataset) | Err(_) => None,
+            Ok(resource_type) => Some(resource_type),
         }
-
-        if text.starts_with("---\ntype: guidance\n") {
-            return ResourceType::Guidance;
-        }
-
-        if text.starts_with("---\ntype: section\n") {
-            return ResourceType::Section;
-        }
-
-        ResourceType::Unknown
     }
 }
 
@@ -73,12 +91,13 @@ impl fmt::Display for ResourceType {
 
         let s = match self {
             CaseStudy => "case-study",
+            Dataset => "dataset",
             Guidance => "guidance",
+            Process => "process",
             Section => "section",
             Standard => "standard",
             Theme => "theme",
             Topic => "topic",
-            Unknown => "unknown",
             UseCase => "use-case",
         };
 
@@ -94,7 +113,9 @@ impl FromStr for ResourceType {
 
         match s {
             "case-study" => Ok(CaseStudy),
+            "dataset" => Ok(Dataset),
             "guidance" => Ok(Guidance),
+            "process" => Ok(Process),
             "section" => Ok(Section),
             "standard" => Ok(Standard),
             "theme" => Ok(Theme),
@@ -0,0 +1,325 @@
+//! This module reports the standards whose endorsement is due for review.
+//!
+//! Both the review date and the end date of the endorsement state are considered. Standards that reached a final
+//! status (retired or superseded) are never due.
+use anyhow::{anyhow, bail, Result};
+use chrono::NaiveDate;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+use crate::cache::{Cache, StandardReviewRecord};
+use crate::source::endorsement::EndorsementStatus;
+
+/// The endorsement date being reported.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Milestone {
+    Review,
+    End,
+}
+
+impl fmt::Display for Milestone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Milestone::Review => "review",
+            Milestone::End => "end",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Urgency {
+    /// The date has passed.
+    Overdue,
+    /// The date is within the given window.
+    Due,
+}
+
+impl fmt::Display for Urgency {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Urgency::Overdue => "overdue",
+            Urgency::Due => "due",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+/// A standard with an endorsement date that has passed or is about to.
+#[derive(Debug, Clone, Serialize)]
+pub struct Review {
+    pub standard_id: String,
+    pub name: String,
+    pub maintainer_id: String,
+    pub maintainer_name: String,
+    pub status: String,
+    pub milestone: Milestone,
+    pub date: String,
+    /// Days from the reference date to the milestone date. Negative when overdue.
+    pub days: i64,
+    pub urgency: Urgency,
+}
+
+/// Collects the standards with a review or end date before `today` or within the given number of days after it.
+pub fn collect(cache: &mut Cache, today: NaiveDate, within: i64) -> Result<Vec<Review>> {
+    let tx = cache.transaction()?;
+    let records = StandardReviewRecord::select_all(&tx)?;
+    let mut result = Vec::new();
+
+    tx.commit()?;
+
+    for record in records {
+        let status: EndorsementStatus = record.status.parse()?;
+
+        if matches!(
+            status,
+            EndorsementStatus::Retired | EndorsementStatus::Superseded
+        ) {
+            continue;
+        }
+
+        let mut milestones = vec![(Milestone::Review, record.review_date.clone())];
+
+        if let Some(end_date) = &record.end_date {
+            milestones.push((Milestone::End, end_date.clone()));
+        }
+
+        for (milestone, date) in milestones {
+            let days = (parse_date(&record, &date)? - today).num_days();
+            let urgency = if days < 0 {
+                Urgency::Overdue
+            } else if days <= within {
+                Urgency::Due
+            } else {
+                continue;
+            };
+
+            result.push(Review {
+                standard_id: record.id.clone(),
+                name: record.name.clone(),
+                maintainer_id: record.maintainer_id.clone(),
+                maintainer_name: record
+                    .maintainer_name
+                    .clone()
+                    .unwrap_or_else(|| record.maintainer_id.clone()),
+                status: record.status.clone(),
+                milestone,
+                date,
+                days,
+                urgency,
+            });
+        }
+    }
+
+    result.sort_by(|a, b| {
+        (&a.maintainer_id, a.days, &a.standard_id).cmp(&(&b.maintainer_id, b.days, &b.standard_id))
+    });
+
+    Ok(result)
+}
+
+fn parse_date(record: &StandardReviewRecord, date: &str) -> Result<NaiveDate> {
+    NaiveDate::from_str(date).map_err(|err| {
+        anyhow!(
+            "standard `{}` has an invalid date `{}`: {}",
+            record.id,
+            date,
+            err
+        )
+    })
+}
+
+/// The output format for a review report.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Format {
+    Table,
+    Csv,
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "table" => Ok(Format::Table),
+            "csv" => Ok(Format::Csv),
+            "json" => Ok(Format::Json),
+            _ => bail!("'{}' is not a valid format. Expected table, csv or json", s),
+        }
+    }
+}
+
+/// The reviews for a single maintainer.
+#[derive(Debug, Clone, Serialize)]
+struct MaintainerReviews<'a> {
+    id: &'a str,
+    name: &'a str,
+    reviews: Vec<&'a Review>,
+}
+
+fn group_by_maintainer(reviews: &[Review]) -> Vec<MaintainerReviews<'_>> {
+    let mut groups: BTreeMap<&str, MaintainerReviews> = BTreeMap::new();
+
+    for review in reviews {
+        groups
+            .entry(&review.maintainer_id)
+            .or_insert_with(|| MaintainerReviews {
+                id: &review.maintainer_id,
+                name: &review.maintainer_name,
+                reviews: Vec::new(),
+            })
+            .reviews
+            .push(review);
+    }
+
+    groups.into_values().collect()
+}
+
+pub fn render(reviews: &[Review], format: &Format) -> Result<String> {
+    match format {
+        Format::Table => Ok(render_table(reviews)),
+        Format::Csv => render_csv(reviews),
+        Format::Json => Ok(serde_json::to_string_pretty(&group_by_maintainer(reviews))?),
+    }
+}
+
+fn render_table(reviews: &[Review]) -> String {
+    if reviews.is_empty() {
+        return "No standards are due for review.".into();
+    }
+
+    let mut widths = [0; 5];
+
+    for row in reviews.iter().map(table_row) {
+        for (width, cell) in widths.iter_mut().zip(&row) {
+            *width = (*width).max(cell.len());
+        }
+    }
+
+    let mut sections = Vec::new();
+
+    for group in group_by_maintainer(reviews) {
+        let mut lines = vec![format!("{} ({})", group.name, group.id)];
+
+        for review in group.reviews {
+            let cells: Vec<String> = table_row(review)
+                .iter()
+                .zip(&widths)
+                .map(|(cell, width)| format!("{:width$}", cell, width = width))
+                .collect();
+
+            lines.push(format!("  {}", cells.join("  ").trim_end()));
+        }
+
+        sections.push(lines.join("\n"));
+    }
+
+    sections.join("\n\n")
+}
+
+fn table_row(review: &Review) -> [String; 5] {
+    let due = match review.urgency {
+        Urgency::Overdue => format!("overdue by {} days", -review.days),
+        Urgency::Due => format!("due in {} days", review.days),
+    };
+
+    [
+        review.standard_id.clone(),
+        review.status.clone(),
+        review.milestone.to_string(),
+        review.date.clone(),
+        due,
+    ]
+}
+
+fn render_csv(reviews: &[Review]) -> Result<String> {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    for review in reviews {
+        writer.serialize(review)?;
+    }
+
+    Ok(String::from_utf8(writer.into_inner()?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source;
+
+    fn standard(id: &str, status: &str, review_date: &str) -> Result<source::Standard> {
+        let raw = format!(
+            r#"---
+type: standard
+identifier: {id}
+name: {id}
+topic: exchange
+specification: https://spec.{id}.org/
+maintainer: data-standards-authority
+endorsement_state:
+    status: {status}
+    start_date: 2021-01-01
+    review_date: {review_date}
+---
+"#,
+            id = id,
+            status = status,
+            review_date = review_date
+        );
+
+        source::Standard::from_str(&raw)
+    }
+
+    #[test]
+    fn overdue_and_due() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+        let today = NaiveDate::from_ymd(2021, 6, 1);
+
+        cache.add(&standard("vapour", "endorsed", "2021-05-01")?)?;
+        cache.add(&standard("steam", "endorsed", "2021-06-15")?)?;
+        cache.add(&standard("mist", "endorsed", "2021-12-01")?)?;
+        cache.add(&standard("fog", "retired", "2021-05-01")?)?;
+
+        let reviews = collect(&mut cache, today, 30)?;
+        let actual: Vec<_> = reviews
+            .iter()
+            .map(|r| (r.standard_id.as_str(), r.days, r.urgency.clone()))
+            .collect();
+
+        assert_eq!(
+            actual,
+            vec![
+                ("vapour", -31, Urgency::Overdue),
+                ("steam", 14, Urgency::Due)
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn csv_report() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+        let today = NaiveDate::from_ymd(2021, 6, 1);
+
+        cache.add(&standard("vapour", "endorsed", "2021-05-01")?)?;
+
+        let reviews = collect(&mut cache, today, 30)?;
+        let actual = render(&reviews, &Format::Csv)?;
+        let expected = "standard_id,name,maintainer_id,maintainer_name,status,milestone,date,days,urgency
+vapour,vapour,data-standards-authority,data-standards-authority,endorsed,review,2021-05-01,-31,overdue
+";
+
+        assert_eq!(actual, expected);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,190 @@
+//! This module searches the body of standards, guidance, topics and themes via the cache full-text index.
+//!
+//! Queries are a list of terms that must all appear, in any order and any inflection (e.g. `address` finds
+//! `addresses`). Raw queries are passed as they are to the FTS5 engine and can use its syntax such as `OR`, `NEAR` or
+//! prefixes.
+use anyhow::{Context, Result};
+use serde::Serialize;
+
+use crate::cache::{Cache, SearchHitRecord};
+
+/// A resource matching a search query.
+#[derive(Debug, Clone, Serialize)]
+pub struct Hit {
+    pub resource_type: String,
+    pub id: String,
+    pub title: String,
+    /// The fragment around the matched terms, wrapped in `**`.
+    pub snippet: String,
+}
+
+impl From<SearchHitRecord> for Hit {
+    fn from(record: SearchHitRecord) -> Self {
+        Hit {
+            resource_type: record.resource_type,
+            id: record.resource_id,
+            title: record.title,
+            snippet: record.snippet,
+        }
+    }
+}
+
+/// Finds the resources matching the given query, best match first.
+pub fn search(cache: &mut Cache, query: &str, raw: bool, limit: u32) -> Result<Vec<Hit>> {
+    let expression = if raw { query.into() } else { quote(query) };
+
+    if expression.trim().is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let tx = cache.transaction()?;
+    let records = SearchHitRecord::select(&tx, &expression, limit)
+        .with_context(|| format!("Failed to search for `{}`", query))?;
+
+    tx.commit()?;
+
+    Ok(records.into_iter().map(Hit::from).collect())
+}
+
+/// Turns every term into an FTS5 string so punctuation such as `?` or `-` is never read as query syntax.
+fn quote(query: &str) -> String {
+    query
+        .split_whitespace()
+        .map(|term| format!("\"{}\"", term.replace('"', "\"\"")))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+pub fn render(hits: &[Hit]) -> String {
+    if hits.is_empty() {
+        return "No matches found.".into();
+    }
+
+    let type_width = hits.iter().map(|hit| hit.resource_type.len()).max();
+    let id_width = hits.iter().map(|hit| hit.id.len()).max();
+    let mut lines = Vec::new();
+
+    for hit in hits {
+        lines.push(format!(
+            "{:type_width$}  {:id_width$}  {}",
+            hit.resource_type,
+            hit.id,
+            hit.title,
+            type_width = type_width.unwrap_or_default(),
+            id_width = id_width.unwrap_or_default(),
+        ));
+        lines.push(format!(
+            "    {}",
+            hit.snippet.split_whitespace().collect::<Vec<_>>().join(" ")
+        ));
+    }
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source::{Guidance, Standard, Topic};
+    use std::str::FromStr;
+
+    fn standard(id: &str, content: &str) -> Result<Standard> {
+        Standard::from_str(&format!(
+            r#"---
+type: standard
+identifier: {id}
+name: {id}
+topic: exchange
+specification: https://example.org/{id}
+maintainer: data-standards-authority
+endorsement_state:
+    status: identified
+    start_date: 2021-06-01
+    review_date: 2021-06-01
+---
+{content}"#,
+            id = id,
+            content = content
+        ))
+    }
+
+    fn ids(hits: &[Hit]) -> Vec<String> {
+        hits.iter()
+            .map(|hit| format!("{} {}", hit.resource_type, hit.id))
+            .collect()
+    }
+
+    #[test]
+    fn ranked_hits() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&standard(
+            "bs7666",
+            "Spatial datasets for geographic referencing of addresses. An address is a place.",
+        )?)?;
+        cache.add(&standard(
+            "vapour",
+            "A steam format that may carry an address.",
+        )?)?;
+        cache.add(&standard("steam", "Nothing to see here.")?)?;
+        cache.add(&Guidance::from_str(
+            "---\ntype: guidance\nidentifier: addressing\nmaintainer: data-standards-authority\nstatus: draft\ncreation_date: 2021-06-01\nupdate_date: 2021-06-01\n---\n# Addressing guidance\n\nHow to publish data.",
+        )?)?;
+        cache.add(&Topic::from_str(
+            "---\ntype: topic\nidentifier: exchange\nname: Exchange\ntheme: other\nordinal: 1\n---\nExchanging addresses.",
+        )?)?;
+
+        let actual = search(&mut cache, "addresses?", false, 10)?;
+
+        assert_eq!(
+            ids(&actual),
+            vec![
+                "guidance addressing",
+                "topic exchange",
+                "standard bs7666",
+                "standard vapour"
+            ]
+        );
+        assert_eq!(actual[0].title, "Addressing guidance");
+        assert_eq!(actual[1].snippet, "Exchanging **addresses**.");
+
+        Ok(())
+    }
+
+    #[test]
+    fn dropped_resources_are_not_found() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&standard("vapour", "A steam format.")?)?;
+        cache.add(&standard("vapour", "A mist format.")?)?;
+
+        assert!(search(&mut cache, "steam", false, 10)?.is_empty());
+        assert_eq!(
+            ids(&search(&mut cache, "mist", false, 10)?),
+            vec!["standard vapour"]
+        );
+
+        Resource::<Standard>::drop(&mut cache, "vapour")?;
+
+        assert!(search(&mut cache, "mist", false, 10)?.is_empty());
+
+        Ok(())
+    }
+
+    #[test]
+    fn raw_queries() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&standard("vapour", "A steam format.")?)?;
+        cache.add(&standard("mist", "A mist format.")?)?;
+
+        let actual = search(&mut cache, "steam OR mist", true, 10)?;
+
+        assert_eq!(actual.len(), 2);
+        assert!(search(&mut cache, "steam OR mist", false, 10)?.is_empty());
+        assert!(search(&mut cache, "\"steam", true, 10).is_err());
+
+        Ok(())
+    }
+}
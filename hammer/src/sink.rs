@@ -0,0 +1,204 @@
+//! This module writes rendered output into a sink directory, touching only what changed.
+use anyhow::Result;
+use std::collections::BTreeSet;
+use std::fmt;
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use crate::checksum::{Checksum, Digest, Hasher};
+
+/// The file, at the root of a sink directory, listing the files the workbench wrote into it.
+///
+/// Keeping it in the sink lets any later run, even one with a brand new cache, tell stale output apart from files
+/// placed there by hand.
+pub const MANIFEST: &str = ".workbench-manifest";
+
+/// A rendered file, relative to the sink directory.
+#[derive(Debug, Clone)]
+pub struct Page {
+    pub path: PathBuf,
+    pub content: String,
+}
+
+impl Page {
+    pub fn new(path: PathBuf, content: String) -> Self {
+        Self { path, content }
+    }
+
+    /// The checksum of the rendered page.
+    ///
+    /// Resource checksums only cover the identifiers of the resources they reference so the rendered text is used
+    /// instead to catch changes in dependencies such as the name of a related standard.
+    pub fn checksum(&self) -> Checksum {
+        text_checksum(&self.content)
+    }
+}
+
+fn text_checksum(text: &str) -> Checksum {
+    let mut hasher = Hasher::new();
+    text.digest(&mut hasher);
+
+    hasher.finalize()
+}
+
+/// A binary file, relative to the sink directory, such as an image.
+#[derive(Debug, Clone)]
+pub struct Blob {
+    pub path: PathBuf,
+    pub content: Vec<u8>,
+}
+
+impl Blob {
+    pub fn new(path: PathBuf, content: Vec<u8>) -> Self {
+        Self { path, content }
+    }
+}
+
+/// The outcome of writing the pages into the sink.
+#[derive(Debug, Clone, Default)]
+pub struct Summary {
+    pub written: Vec<PathBuf>,
+    pub unchanged: Vec<PathBuf>,
+    pub removed: Vec<PathBuf>,
+}
+
+impl fmt::Display for Summary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{} written, {} unchanged, {} removed",
+            self.written.len(),
+            self.unchanged.len(),
+            self.removed.len()
+        )
+    }
+}
+
+/// Writes the pages that differ from the ones in the sink and removes any file a previous run wrote but this one
+/// didn't.
+///
+/// The files written are listed in the [`MANIFEST`] of the sink. Any other file in the sink, e.g. one placed by hand,
+/// is left untouched.
+pub fn write(sink_dir: &Path, pages: &[Page]) -> Result<Summary> {
+    let files: Vec<(&Path, &[u8])> = pages
+        .iter()
+        .map(|page| (page.path.as_path(), page.content.as_bytes()))
+        .collect();
+
+    write_tracked(sink_dir, &files)
+}
+
+/// Writes the pages that differ from the ones in the sink, leaving any other file untouched.
+pub fn merge(sink_dir: &Path, pages: &[Page]) -> Result<Summary> {
+    let files: Vec<(&Path, &[u8])> = pages
+        .iter()
+        .map(|page| (page.path.as_path(), page.content.as_bytes()))
+        .collect();
+
+    write_files(sink_dir, &files)
+}
+
+/// Writes the blobs that differ from the ones in the sink and removes any file a previous run wrote but this one
+/// didn't.
+pub fn write_blobs(sink_dir: &Path, blobs: &[Blob]) -> Result<Summary> {
+    let files: Vec<(&Path, &[u8])> = blobs
+        .iter()
+        .map(|blob| (blob.path.as_path(), blob.content.as_slice()))
+        .collect();
+
+    write_tracked(sink_dir, &files)
+}
+
+fn write_tracked(sink_dir: &Path, files: &[(&Path, &[u8])]) -> Result<Summary> {
+    let mut summary = write_files(sink_dir, files)?;
+    let manifest_path = sink_dir.join(MANIFEST);
+    let current: BTreeSet<&Path> = files.iter().map(|(path, _)| *path).collect();
+
+    for relative_path in read_manifest(&manifest_path)? {
+        if current.contains(relative_path.as_path()) {
+            continue;
+        }
+
+        let path = sink_dir.join(&relative_path);
+
+        if path.is_file() {
+            fs::remove_file(&path)?;
+            remove_empty_parents(sink_dir, &path)?;
+            summary.removed.push(relative_path);
+        }
+    }
+
+    let manifest: String = current
+        .iter()
+        .map(|path| format!("{}\n", path.display()))
+        .collect();
+
+    write_file(&manifest_path, manifest.as_bytes())?;
+
+    Ok(summary)
+}
+
+/// Reads the files listed in the given manifest, if any.
+///
+/// Entries that would reach outside of the sink directory are ignored so a tampered manifest can't remove anything
+/// else.
+fn read_manifest(path: &Path) -> Result<Vec<PathBuf>> {
+    if !path.is_file() {
+        return Ok(Vec::new());
+    }
+
+    let list = fs::read_to_string(path)?
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(PathBuf::from)
+        .filter(|path| {
+            path.components()
+                .all(|component| matches!(component, Component::Normal(_)))
+        })
+        .collect();
+
+    Ok(list)
+}
+
+fn write_files(sink_dir: &Path, files: &[(&Path, &[u8])]) -> Result<Summary> {
+    let mut summary = Summary::default();
+
+    fs::create_dir_all(sink_dir)?;
+
+    for (relative_path, content) in files {
+        let path = sink_dir.join(relative_path);
+
+        let current = fs::read(&path).ok().map(|bytes| Checksum::new(&bytes));
+
+        if current == Some(Checksum::new(content)) {
+            summary.unchanged.push(relative_path.to_path_buf());
+        } else {
+            write_file(&path, content)?;
+            summary.written.push(relative_path.to_path_buf());
+        }
+    }
+
+    Ok(summary)
+}
+
+/// Removes the directories left empty by removing the given file, up to the sink directory.
+fn remove_empty_parents(sink_dir: &Path, path: &Path) -> Result<()> {
+    for dir in path.ancestors().skip(1) {
+        if dir == sink_dir || !dir.starts_with(sink_dir) || fs::read_dir(dir)?.next().is_some() {
+            break;
+        }
+
+        fs::remove_dir(dir)?;
+    }
+
+    Ok(())
+}
+
+fn write_file(path: &Path, content: &[u8]) -> Result<()> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, content)?;
+
+    Ok(())
+}
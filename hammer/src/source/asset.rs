@@ -0,0 +1,529 @@
+//! This module covers the assets, images and other files referenced from the Markdown source.
+//!
+//! Assets are content addressed: the identifier derives from the checksum so a file copied under different names or
+//! referenced from many places is stored and published once.
+//!
+//! Mermaid diagrams, either `.mmd` files or fenced `mermaid` blocks in Markdown, are rendered to SVG assets.
+use anyhow::{anyhow, Context, Result};
+use pulldown_cmark::{CodeBlockKind, Event, Parser, Tag};
+use std::fs;
+use std::path::Path;
+use thiserror::Error;
+
+use super::split_content;
+use crate::cache::{AssetRecord, Cache};
+use crate::checksum::{Checksum, Digest, Hasher};
+use crate::mermaid;
+use crate::report;
+use crate::resource::Resource;
+
+pub type AssetId = String;
+
+/// The directory assets are published under, relative to the root of the site.
+pub const DIRECTORY: &str = "assets";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Asset {
+    id: AssetId,
+    pub content_type: String,
+    pub content: Vec<u8>,
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum AssetError {
+    #[error("The extension `{0}` is not a known asset type.")]
+    UnknownType(String),
+}
+
+impl Asset {
+    /// Creates an asset from the extension of its file and its content.
+    ///
+    /// Mermaid sources are rendered to SVG.
+    pub fn new(extension: &str, content: Vec<u8>) -> Result<Asset> {
+        let mut extension = extension.to_lowercase();
+        let mut content = content;
+
+        if extension == MERMAID {
+            content = mermaid::render(&String::from_utf8(content)?)?.into_bytes();
+            extension = "svg".to_string();
+        }
+
+        let content_type =
+            content_type(&extension).ok_or_else(|| AssetError::UnknownType(extension.clone()))?;
+        let mut asset = Asset {
+            id: String::new(),
+            content_type: content_type.to_string(),
+            content,
+        };
+        let checksum = asset.checksum().to_string();
+
+        asset.id = format!("{}.{}", &checksum[..16], extension);
+
+        Ok(asset)
+    }
+
+    pub fn from_path(path: &Path) -> Result<Asset> {
+        let extension = path.extension().unwrap_or_default().to_string_lossy();
+
+        Asset::new(&extension, fs::read(path)?)
+    }
+
+    pub fn id(&self) -> &AssetId {
+        &self.id
+    }
+
+    pub fn checksum(&self) -> Checksum {
+        self.into()
+    }
+
+    /// The URL the asset is published under.
+    pub fn url(&self) -> String {
+        format!("/{}/{}", DIRECTORY, &self.id)
+    }
+}
+
+impl From<&Asset> for Checksum {
+    fn from(asset: &Asset) -> Checksum {
+        let mut hasher = Hasher::new();
+        asset.digest(&mut hasher);
+
+        hasher.finalize()
+    }
+}
+
+impl Digest for Asset {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.content_type.digest(hasher);
+        self.content[..].digest(hasher);
+    }
+}
+
+/// The extension of Mermaid sources.
+const MERMAID: &str = "mmd";
+
+fn content_type(extension: &str) -> Option<&'static str> {
+    match extension {
+        "gif" => Some("image/gif"),
+        "jpeg" | "jpg" => Some("image/jpeg"),
+        "pdf" => Some("application/pdf"),
+        "png" => Some("image/png"),
+        "svg" => Some("image/svg+xml"),
+        "webp" => Some("image/webp"),
+        _ => None,
+    }
+}
+
+/// Whether the given path is a file to be read as an asset.
+pub fn is_asset_path(path: &Path) -> bool {
+    path.extension()
+        .map(|ext| {
+            let ext = ext.to_string_lossy().to_lowercase();
+
+            ext == MERMAID || content_type(&ext).is_some()
+        })
+        .unwrap_or(false)
+}
+
+/// The position where the Markdown content starts in the given source text.
+///
+/// Anything in the frontmatter is data, not Markdown.
+fn content_offset(blob: &str) -> usize {
+    match split_content(blob) {
+        Ok((_, _, content)) => content.as_ptr() as usize - blob.as_ptr() as usize,
+        Err(_) => 0,
+    }
+}
+
+/// Replaces the fenced `mermaid` blocks in the Markdown content of the given source text with an image of the
+/// diagram, returning the rendered diagrams.
+///
+/// The `accTitle` of the diagram, if any, is used as the alternative text.
+pub fn render_diagrams(blob: &str) -> Result<(String, Vec<Asset>)> {
+    let offset = content_offset(blob);
+    let content = &blob[offset..];
+    let mut result = String::with_capacity(blob.len());
+    let mut diagrams = Vec::new();
+    let mut cursor = 0;
+    let mut events = Parser::new(content).into_offset_iter();
+
+    result.push_str(&blob[..offset]);
+
+    while let Some((event, range)) = events.next() {
+        let is_mermaid = matches!(
+            &event,
+            Event::Start(Tag::CodeBlock(CodeBlockKind::Fenced(info)))
+                if info.split_whitespace().next() == Some(MERMAID_INFO)
+        );
+
+        if !is_mermaid || range.start < cursor {
+            continue;
+        }
+
+        let mut source = String::new();
+
+        for (event, _) in events.by_ref() {
+            match event {
+                Event::Text(text) => source.push_str(&text),
+                Event::End(_) => break,
+                _ => (),
+            }
+        }
+
+        let line = blob[..offset + range.start].matches('\n').count() + 1;
+        let title = mermaid::parse(&source)
+            .with_context(|| format!("Invalid Mermaid diagram at line {}", line))?
+            .title
+            .unwrap_or_else(|| "Diagram".to_string());
+        let diagram = Asset::new(MERMAID, source.into_bytes())?;
+
+        result.push_str(&content[cursor..range.start]);
+        result.push_str(&format!(
+            "![{}]({})",
+            title.replace(']', "\\]"),
+            diagram.url()
+        ));
+
+        // The block spans its closing fence and line ending.
+        if content[range.clone()].ends_with('\n') {
+            result.push('\n');
+        }
+
+        cursor = range.end;
+        diagrams.push(diagram);
+    }
+
+    result.push_str(&content[cursor..]);
+
+    Ok((result, diagrams))
+}
+
+/// The info string of fenced Mermaid blocks.
+const MERMAID_INFO: &str = "mermaid";
+
+/// Rewrites the relative links to assets in the Markdown content of the given source text so they point to the
+/// published asset.
+///
+/// Links are resolved relative to `base_dir`, the directory of the source file, or failing that relative to its
+/// nearest parent within `source_dir` holding the linked file, so `../assets/` from `reference/processes/` finds the
+/// top level assets. Files outside `source_dir` are never read as they are not published.
+///
+/// Links to files that are not assets are left untouched. Links to assets that can't be resolved are left untouched
+/// too and returned, with the reason, so they can be reported.
+pub fn rewrite_links(blob: &str, base_dir: &Path, source_dir: &Path) -> (String, Vec<String>) {
+    let offset = content_offset(blob);
+    let content = &blob[offset..];
+    let mut result = String::with_capacity(blob.len());
+    let mut replacements = Vec::new();
+    let mut unresolved = Vec::new();
+    let mut cursor = 0;
+
+    result.push_str(&blob[..offset]);
+
+    for (event, range) in Parser::new(content).into_offset_iter() {
+        let destination = match event {
+            Event::Start(Tag::Image(_, destination, _)) => destination,
+            Event::Start(Tag::Link(_, destination, _)) => destination,
+            _ => continue,
+        };
+        let url = match asset_url(&destination, base_dir, source_dir) {
+            Some(Ok(url)) => url,
+            Some(Err(err)) => {
+                unresolved.push(format!("asset link `{}` {:#}", destination, err));
+                continue;
+            }
+            None => continue,
+        };
+
+        // Reference links have their destination elsewhere so they are not found within the link.
+        if let Some(start) = destination_offset(&content[range.clone()], &destination) {
+            replacements.push((range.start + start, destination.len(), url));
+        }
+    }
+
+    // A link wrapping an image starts first but its destination comes after the image.
+    replacements.sort_by_key(|(start, _, _)| *start);
+
+    for (start, len, url) in replacements {
+        if start < cursor {
+            continue;
+        }
+
+        result.push_str(&content[cursor..start]);
+        result.push_str(&url);
+        cursor = start + len;
+    }
+
+    result.push_str(&content[cursor..]);
+
+    (result, unresolved)
+}
+
+/// Finds where the destination of an inline link or image starts within its span, e.g. `[text](destination)`.
+///
+/// The text may hold `](` itself, e.g. around a nested image, so the last `](` followed by the destination is taken.
+fn destination_offset(span: &str, destination: &str) -> Option<usize> {
+    span.rmatch_indices("](").find_map(|(position, opening)| {
+        let start = position + opening.len();
+        let rest = &span[start..];
+        let trimmed = rest.trim_start();
+        let start = start + rest.len() - trimmed.len();
+        let start = start + if trimmed.starts_with('<') { 1 } else { 0 };
+
+        span[start..].starts_with(destination).then_some(start)
+    })
+}
+
+/// Resolves the given link destination to the URL of the asset it points to.
+///
+/// Returns `None` when the destination is not a relative link to an asset.
+fn asset_url(destination: &str, base_dir: &Path, source_dir: &Path) -> Option<Result<String>> {
+    let is_relative = !destination.starts_with('/')
+        && !destination.starts_with('#')
+        && !destination.contains("://")
+        && !destination.starts_with("mailto:");
+    let file = destination
+        .split(&['#', '?'][..])
+        .next()
+        .unwrap_or_default();
+
+    if !is_relative || file.is_empty() || !is_asset_path(Path::new(file)) {
+        return None;
+    }
+
+    let root = fs::canonicalize(source_dir).ok()?;
+    let path = base_dir
+        .ancestors()
+        .take_while(|dir| dir.starts_with(source_dir))
+        .map(|dir| dir.join(file))
+        .find(|path| {
+            fs::canonicalize(path)
+                .map(|path| path.is_file() && path.starts_with(&root))
+                .unwrap_or(false)
+        });
+
+    Some(match path {
+        Some(path) => Asset::from_path(&path)
+            .map(|asset| asset.url())
+            .with_context(|| format!("fails to read `{}`", path.display())),
+        None => Err(anyhow!("doesn't resolve to a file within the source")),
+    })
+}
+
+impl Resource<Asset> for Cache {
+    fn get(&mut self, id: &str) -> Result<Option<Asset>> {
+        let tx = self.conn.transaction()?;
+        let mut result = None;
+
+        if let Some(record) = AssetRecord::select(&tx, id)? {
+            result = Some(Asset {
+                id: record.id,
+                content_type: record.content_type,
+                content: record.content,
+            });
+        }
+
+        self.report
+            .log(report::Action::Get, report::Entity::Asset, id, "");
+
+        tx.commit()?;
+
+        Ok(result)
+    }
+
+    fn add(&mut self, asset: &Asset) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        let checksum = asset.checksum().to_string();
+
+        // The identifier derives from the checksum so a cached asset is always up to date.
+        if AssetRecord::select(&tx, &asset.id)?.is_none() {
+            AssetRecord::insert(&tx, &asset.into())?;
+        }
+
+        Cache::insert_trailmark(&tx, &checksum, "asset", &self.timestamp)?;
+
+        self.report
+            .log(report::Action::Add, report::Entity::Asset, &asset.id, "");
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn drop(&mut self, id: &str) -> Result<Option<Asset>> {
+        let asset = self.get(id)?;
+        let tx = self.conn.transaction()?;
+
+        if asset.is_some() {
+            AssetRecord::delete(&tx, id)?;
+        }
+
+        self.report
+            .log(report::Action::Prune, report::Entity::Asset, id, "");
+
+        tx.commit()?;
+
+        Ok(asset)
+    }
+}
+
+impl From<&Asset> for AssetRecord {
+    fn from(asset: &Asset) -> Self {
+        AssetRecord {
+            id: asset.id.clone(),
+            checksum: asset.checksum().to_string(),
+            content_type: asset.content_type.clone(),
+            content: asset.content.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use temp_testdir::TempDir;
+
+    const SVG: &[u8] = b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+
+    #[test]
+    fn content_addressed() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+        let first = Asset::new("svg", SVG.to_vec())?;
+        let second = Asset::new("SVG", SVG.to_vec())?;
+
+        cache.add(&first)?;
+        cache.add(&second)?;
+
+        let cached: Option<Asset> = cache.get(first.id())?;
+
+        assert_eq!(first.id(), second.id());
+        assert!(first.id().ends_with(".svg"));
+        assert_eq!(cached, Some(first));
+        assert!(Asset::new("exe", Vec::new()).is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite_relative_links() -> Result<()> {
+        let temp = TempDir::default();
+        let dir = temp.as_ref();
+        fs::create_dir(dir.join("assets"))?;
+        fs::create_dir(dir.join("processes"))?;
+        fs::write(dir.join("assets/states.svg"), SVG)?;
+        let url = Asset::new("svg", SVG.to_vec())?.url();
+        let blob = "---\ntype: process\nimage: ../assets/states.svg\n---\n# Process\n\n![States](../assets/states.svg \"../assets/states.svg\")\n\n[Diagram](../assets/states.svg#top), [missing](../assets/missing.svg) and [elsewhere](https://example.org/states.svg).\n";
+        let expected = format!("---\ntype: process\nimage: ../assets/states.svg\n---\n# Process\n\n![States]({url} \"../assets/states.svg\")\n\n[Diagram]({url}), [missing](../assets/missing.svg) and [elsewhere](https://example.org/states.svg).\n", url = url);
+
+        let (actual, unresolved) = rewrite_links(blob, &dir.join("processes"), dir);
+
+        assert_eq!(actual, expected);
+        assert_eq!(
+            unresolved,
+            vec!["asset link `../assets/missing.svg` doesn't resolve to a file within the source"]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite_destination_not_text() -> Result<()> {
+        let temp = TempDir::default();
+        let dir = temp.as_ref();
+        fs::create_dir_all(dir.join("reference/processes"))?;
+        fs::create_dir(dir.join("assets"))?;
+        fs::write(dir.join("assets/states.svg"), SVG)?;
+        let url = Asset::new("svg", SVG.to_vec())?.url();
+        let blob = "---\ntype: process\n---\n[../assets/states.svg](../assets/states.svg) and [![](../assets/states.svg)](../assets/states.svg)\n";
+        let expected = format!(
+            "---\ntype: process\n---\n[../assets/states.svg]({url}) and [![]({url})]({url})\n",
+            url = url
+        );
+
+        assert_eq!(rewrite_links(blob, &dir.join("reference"), dir).0, expected);
+        // Written relative to the section, not the file, as in the corpus.
+        assert_eq!(
+            rewrite_links(blob, &dir.join("reference/processes"), dir).0,
+            expected
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn rewrite_within_source() -> Result<()> {
+        let temp = TempDir::default();
+        let dir = temp.as_ref();
+        let source_dir = dir.join("source");
+        fs::create_dir_all(source_dir.join("processes"))?;
+        fs::write(dir.join("leak.svg"), SVG)?;
+        let blob = "---\ntype: process\n---\n![](leak.svg) and ![](../../leak.svg)\n";
+        let (actual, unresolved) = rewrite_links(blob, &source_dir.join("processes"), &source_dir);
+
+        assert_eq!(actual, blob);
+        assert_eq!(
+            unresolved,
+            vec![
+                "asset link `leak.svg` doesn't resolve to a file within the source",
+                "asset link `../../leak.svg` doesn't resolve to a file within the source",
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_mermaid_source() -> Result<()> {
+        let asset = Asset::new("mmd", b"stateDiagram-v2\n[*] --> Draft".to_vec())?;
+
+        assert!(asset.id().ends_with(".svg"));
+        assert_eq!(asset.content_type, "image/svg+xml");
+        assert!(asset.content.starts_with(b"<svg"));
+        assert!(Asset::new("mmd", b"sequenceDiagram\nA->>B: Hi".to_vec()).is_err());
+        assert!(is_asset_path(Path::new("assets/states.mmd")));
+
+        Ok(())
+    }
+
+    #[test]
+    fn link_mermaid_source() -> Result<()> {
+        let temp = TempDir::default();
+        let dir = temp.as_ref();
+        let source = fs::read(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../corpus/assets/endorsement_state_machine.mmd"
+        ))?;
+        fs::create_dir(dir.join("assets"))?;
+        fs::write(dir.join("assets/states.mmd"), &source)?;
+        let asset = Asset::new("mmd", source)?;
+        let blob = "---\ntype: process\n---\n![](assets/states.mmd)\n";
+
+        assert_eq!(
+            rewrite_links(blob, dir, dir),
+            (
+                format!("---\ntype: process\n---\n![]({})\n", asset.url()),
+                Vec::new()
+            )
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn render_fenced_diagrams() -> Result<()> {
+        let source = "stateDiagram-v2\naccTitle: Lifecycle\n[*] --> Draft\n";
+        let url = Asset::new("mmd", source.as_bytes().to_vec())?.url();
+        let blob = format!("---\ntype: process\n---\n# Process\n\n```mermaid\n{}```\n\nAfter.\n\n```rust\nfn main() {{}}\n```\n", source);
+        let expected = format!("---\ntype: process\n---\n# Process\n\n![Lifecycle]({})\n\nAfter.\n\n```rust\nfn main() {{}}\n```\n", url);
+        let (content, diagrams) = render_diagrams(&blob)?;
+
+        assert_eq!(content, expected);
+        assert_eq!(diagrams.len(), 1);
+        assert_eq!(diagrams[0].url(), url);
+
+        let err = render_diagrams("# Broken\n\n```mermaid\npie\n```\n").unwrap_err();
+
+        assert_eq!(err.to_string(), "Invalid Mermaid diagram at line 3");
+
+        Ok(())
+    }
+}
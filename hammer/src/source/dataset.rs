@@ -0,0 +1,640 @@
+//! This module covers the dataset from an input point of view.
+//!
+//! A dataset is a CSV table described by a [CSVW] metadata file sitting next to it, e.g. `members.csv` and
+//! `members.csv-metadata.json`. Every row is validated against the `tableSchema` of the metadata.
+//!
+//! [CSVW]: https://www.w3.org/TR/tabular-metadata/
+use anyhow::Result;
+use lazy_static::lazy_static;
+use regex::Regex;
+use serde::{Deserialize, Deserializer, Serialize};
+use std::collections::HashSet;
+use std::fmt;
+use std::path::{Path, PathBuf};
+use thiserror::Error;
+
+use super::parse_json;
+use crate::cache::{Cache, DatasetColumnRecord, DatasetRecord, DatasetRowRecord, Transaction};
+use crate::checksum::{Checksum, Digest, Hasher};
+use crate::report;
+use crate::resource::Resource;
+
+pub type DatasetId = String;
+
+const CSVW_CONTEXT: &str = "http://www.w3.org/ns/csvw";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Dataset {
+    pub metadata: Metadata,
+    /// The cells of every row in column order. Missing values are empty strings.
+    pub rows: Vec<Vec<String>>,
+}
+
+impl Dataset {
+    /// Builds a dataset from its CSVW metadata and the CSV table it describes, validating every row.
+    pub fn new(metadata: Metadata, table: &str) -> Result<Dataset> {
+        let mut reader = csv::Reader::from_reader(table.as_bytes());
+        let titles: Vec<String> = metadata
+            .table_schema
+            .columns
+            .iter()
+            .map(|column| column.titles.clone())
+            .collect();
+        let header: Vec<String> = reader.headers()?.iter().map(Into::into).collect();
+
+        if header != titles {
+            return Err(DatasetError::Header {
+                expected: titles,
+                found: header,
+            }
+            .into());
+        }
+
+        let mut rows = Vec::new();
+        let mut lines = Vec::new();
+        let mut violations = Vec::new();
+
+        for record in reader.records() {
+            let record = record?;
+            let line = record.position().map_or(0, |position| position.line());
+            let row: Vec<String> = record.iter().map(Into::into).collect();
+
+            violations.extend(metadata.table_schema.validate(line, &row));
+            rows.push(row);
+            lines.push(line);
+        }
+
+        violations.extend(metadata.table_schema.validate_keys(&rows, &lines));
+
+        if !violations.is_empty() {
+            return Err(DatasetError::Invalid(Violations(violations)).into());
+        }
+
+        Ok(Dataset { metadata, rows })
+    }
+
+    /// Reads the dataset described by the given CSVW metadata blob, found at the given path.
+    pub fn from_source(metadata_path: &Path, blob: &str) -> Result<Dataset> {
+        let metadata: Metadata = parse_json(blob)?;
+        let table_path = metadata_path
+            .parent()
+            .unwrap_or_else(|| Path::new(""))
+            .join(&metadata.url);
+        let table = std::fs::read_to_string(&table_path).map_err(|err| {
+            anyhow::anyhow!(
+                "failed to read the table `{}`: {}",
+                table_path.display(),
+                err
+            )
+        })?;
+
+        Dataset::new(metadata, &table)
+    }
+
+    pub fn id(&self) -> DatasetId {
+        self.metadata.id()
+    }
+
+    pub fn checksum(&self) -> Checksum {
+        self.into()
+    }
+}
+
+impl Digest for Dataset {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.metadata.digest(hasher);
+        self.rows.digest(hasher);
+    }
+}
+
+impl From<&Dataset> for Checksum {
+    fn from(resource: &Dataset) -> Checksum {
+        let mut hasher = Hasher::new();
+        resource.digest(&mut hasher);
+
+        hasher.finalize()
+    }
+}
+
+/// The path to the CSVW metadata file describing the given CSV table, if any.
+pub fn metadata_path(table_path: &Path) -> Option<PathBuf> {
+    let mut name = table_path.file_name()?.to_os_string();
+    name.push("-metadata.json");
+    let path = table_path.with_file_name(name);
+    let is_csv = table_path.extension().is_some_and(|ext| ext == "csv");
+
+    if is_csv && path.exists() {
+        Some(path)
+    } else {
+        None
+    }
+}
+
+/// Whether the given JSON file is a CSVW metadata file, e.g. `members.csv-metadata.json`.
+pub fn is_metadata_path(path: &Path) -> bool {
+    path.file_name()
+        .map(|name| name.to_string_lossy().ends_with(".csv-metadata.json"))
+        .unwrap_or(false)
+}
+
+/// The subset of a CSVW table description the workbench understands.
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Metadata {
+    #[serde(rename = "@context")]
+    pub context: String,
+    /// The path to the CSV table, relative to the metadata file.
+    pub url: String,
+    #[serde(rename = "schema:name")]
+    pub name: String,
+    #[serde(
+        rename = "schema:description",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(
+        rename = "schema:maintainer",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub maintainer: Option<String>,
+    #[serde(rename = "tableSchema")]
+    pub table_schema: TableSchema,
+}
+
+impl Metadata {
+    /// The identifier of the dataset, derived from the name of the table, e.g. `members_list.csv` is
+    /// `members-list`.
+    pub fn id(&self) -> DatasetId {
+        let name = Path::new(&self.url)
+            .file_stem()
+            .unwrap_or_default()
+            .to_string_lossy();
+
+        name.replace('_', "-")
+    }
+}
+
+impl Digest for Metadata {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.url.digest(hasher);
+        self.name.digest(hasher);
+        self.description.digest(hasher);
+        self.maintainer.digest(hasher);
+        self.table_schema.primary_key.digest(hasher);
+
+        for column in &self.table_schema.columns {
+            column.name.digest(hasher);
+            column.titles.digest(hasher);
+            column.description.digest(hasher);
+            column.datatype.to_string().digest(hasher);
+            column.required.to_string().digest(hasher);
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct TableSchema {
+    /// The columns that uniquely identify a row. CSVW accepts either a single column name or a list of them.
+    #[serde(
+        rename = "primaryKey",
+        default,
+        deserialize_with = "one_or_many",
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    pub primary_key: Vec<String>,
+    pub columns: Vec<Column>,
+}
+
+impl TableSchema {
+    /// Checks the cells of a single row, located by its line in the CSV table.
+    fn validate(&self, line: u64, row: &[String]) -> Vec<Violation> {
+        let mut result = Vec::new();
+
+        for (column, value) in self.columns.iter().zip(row) {
+            if value.is_empty() {
+                if column.required {
+                    result.push(Violation {
+                        line,
+                        message: format!("missing value for the required column `{}`", column.name),
+                    });
+                }
+            } else if !column.datatype.accepts(value) {
+                result.push(Violation {
+                    line,
+                    message: format!(
+                        "`{}` in column `{}` is not a valid {}",
+                        value, column.name, column.datatype
+                    ),
+                });
+            }
+        }
+
+        result
+    }
+
+    /// Checks that the primary key is unique across the table.
+    fn validate_keys(&self, rows: &[Vec<String>], lines: &[u64]) -> Vec<Violation> {
+        let indices: Vec<usize> = self
+            .primary_key
+            .iter()
+            .filter_map(|name| self.columns.iter().position(|column| &column.name == name))
+            .collect();
+        let mut result = Vec::new();
+        let mut seen = HashSet::new();
+
+        for name in &self.primary_key {
+            if !self.columns.iter().any(|column| &column.name == name) {
+                result.push(Violation {
+                    line: 1,
+                    message: format!("the primary key refers to an unknown column `{}`", name),
+                });
+            }
+        }
+
+        if indices.is_empty() {
+            return result;
+        }
+
+        for (row, &line) in rows.iter().zip(lines) {
+            let key: Vec<&str> = indices.iter().map(|&i| row[i].as_str()).collect();
+
+            if !seen.insert(key.clone()) {
+                result.push(Violation {
+                    line,
+                    message: format!("duplicate primary key `{}`", key.join(", ")),
+                });
+            }
+        }
+
+        result
+    }
+}
+
+fn one_or_many<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match OneOrMany::deserialize(deserializer)? {
+        OneOrMany::One(name) => vec![name],
+        OneOrMany::Many(names) => names,
+    })
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct Column {
+    pub titles: String,
+    pub name: String,
+    #[serde(
+        rename = "schema:description",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub datatype: Datatype,
+    #[serde(default)]
+    pub required: bool,
+}
+
+/// The CSVW datatypes the workbench can validate.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Deserialize, Serialize)]
+pub enum Datatype {
+    #[default]
+    #[serde(rename = "string")]
+    String,
+    #[serde(rename = "number")]
+    Number,
+    #[serde(rename = "integer")]
+    Integer,
+    #[serde(rename = "boolean")]
+    Boolean,
+    #[serde(rename = "date")]
+    Date,
+    #[serde(rename = "anyURI")]
+    AnyUri,
+    #[serde(rename = "schema:URL")]
+    Url,
+}
+
+impl Datatype {
+    pub fn accepts(&self, value: &str) -> bool {
+        lazy_static! {
+            static ref DATE_RE: Regex =
+                Regex::new(r"^\d{4}-(0[1-9]|1[0-2])-(0[1-9]|[12]\d|3[01])$").unwrap();
+            static ref URL_RE: Regex = Regex::new(r"^https?://\S+$").unwrap();
+        }
+
+        match self {
+            Datatype::String => true,
+            Datatype::Number => value.parse::<f64>().is_ok(),
+            Datatype::Integer => value.parse::<i64>().is_ok(),
+            Datatype::Boolean => matches!(value, "true" | "false" | "1" | "0"),
+            Datatype::Date => DATE_RE.is_match(value),
+            Datatype::AnyUri => !value.chars().any(char::is_whitespace),
+            Datatype::Url => URL_RE.is_match(value),
+        }
+    }
+}
+
+impl fmt::Display for Datatype {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let s = match self {
+            Datatype::String => "string",
+            Datatype::Number => "number",
+            Datatype::Integer => "integer",
+            Datatype::Boolean => "boolean",
+            Datatype::Date => "date",
+            Datatype::AnyUri => "anyURI",
+            Datatype::Url => "schema:URL",
+        };
+
+        write!(f, "{}", s)
+    }
+}
+
+impl std::str::FromStr for Datatype {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Ok(serde_json::from_value(serde_json::Value::String(s.into()))?)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violation {
+    pub line: u64,
+    pub message: String,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Violations(pub Vec<Violation>);
+
+impl fmt::Display for Violations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let lines: Vec<String> = self
+            .0
+            .iter()
+            .map(|violation| format!("line {}: {}", violation.line, violation.message))
+            .collect();
+
+        write!(f, "{}", lines.join("\n"))
+    }
+}
+
+#[derive(Debug, Clone, Error, PartialEq)]
+pub enum DatasetError {
+    #[error("The CSV header {found:?} does not match the column titles {expected:?}.")]
+    Header {
+        expected: Vec<String>,
+        found: Vec<String>,
+    },
+    #[error("The table does not conform to its schema:\n{0}")]
+    Invalid(Violations),
+}
+
+impl Resource<Dataset> for Cache {
+    fn get(&mut self, id: &str) -> Result<Option<Dataset>> {
+        let tx = self.conn.transaction()?;
+        let mut result = None;
+
+        if let Some(record) = DatasetRecord::select(&tx, id)? {
+            let mut columns = Vec::new();
+            let mut rows = Vec::new();
+
+            for column in DatasetColumnRecord::select(&tx, id)? {
+                columns.push(Column {
+                    titles: column.title,
+                    name: column.name,
+                    description: column.description,
+                    datatype: column.datatype.parse()?,
+                    required: column.required,
+                });
+            }
+
+            for row in DatasetRowRecord::select(&tx, id)? {
+                rows.push(serde_json::from_str(&row.cells)?);
+            }
+
+            let metadata = Metadata {
+                context: CSVW_CONTEXT.into(),
+                url: record.url,
+                name: record.name,
+                description: record.description,
+                maintainer: record.maintainer,
+                table_schema: TableSchema {
+                    primary_key: record
+                        .primary_key
+                        .map(|key| key.split(',').map(Into::into).collect())
+                        .unwrap_or_default(),
+                    columns,
+                },
+            };
+
+            result = Some(Dataset { metadata, rows });
+        }
+
+        self.report
+            .log(report::Action::Get, report::Entity::Dataset, id, "");
+
+        tx.commit()?;
+
+        Ok(result)
+    }
+
+    fn add(&mut self, resource: &Dataset) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        let checksum = resource.checksum().to_string();
+        let id = resource.id();
+
+        if let Some(record) = DatasetRecord::select(&tx, &id)? {
+            if record.checksum != checksum {
+                DatasetRecord::delete(&tx, &id)?;
+                create(&tx, resource)?;
+            }
+        } else {
+            create(&tx, resource)?;
+        }
+
+        Cache::insert_trailmark(&tx, &checksum, "dataset", &self.timestamp)?;
+
+        self.report
+            .log(report::Action::Add, report::Entity::Dataset, &id, "");
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn drop(&mut self, id: &str) -> Result<Option<Dataset>> {
+        let resource = self.get(id)?;
+        let tx = self.conn.transaction()?;
+
+        if resource.is_some() {
+            DatasetRecord::delete(&tx, id)?;
+        }
+
+        self.report
+            .log(report::Action::Prune, report::Entity::Dataset, id, "");
+
+        tx.commit()?;
+
+        Ok(resource)
+    }
+}
+
+/// Helper to perform a strict create. Will fail if the dataset exists.
+fn create(tx: &Transaction, resource: &Dataset) -> Result<()> {
+    let id = resource.id();
+    let metadata = &resource.metadata;
+    let primary_key = &metadata.table_schema.primary_key;
+
+    DatasetRecord::insert(
+        tx,
+        &DatasetRecord {
+            id: id.clone(),
+            checksum: resource.checksum().to_string(),
+            name: metadata.name.clone(),
+            description: metadata.description.clone(),
+            maintainer: metadata.maintainer.clone(),
+            url: metadata.url.clone(),
+            primary_key: if primary_key.is_empty() {
+                None
+            } else {
+                Some(primary_key.join(","))
+            },
+        },
+    )?;
+
+    for (ordinal, column) in metadata.table_schema.columns.iter().enumerate() {
+        DatasetColumnRecord::insert(
+            tx,
+            &DatasetColumnRecord {
+                dataset_id: id.clone(),
+                ordinal: ordinal as u32,
+                name: column.name.clone(),
+                title: column.titles.clone(),
+                description: column.description.clone(),
+                datatype: column.datatype.to_string(),
+                required: column.required,
+            },
+        )?;
+    }
+
+    for (ordinal, row) in resource.rows.iter().enumerate() {
+        DatasetRowRecord::insert(
+            tx,
+            &DatasetRowRecord {
+                dataset_id: id.clone(),
+                ordinal: ordinal as u32,
+                cells: serde_json::to_string(row)?,
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const METADATA: &str = r#"{
+      "@context": "http://www.w3.org/ns/csvw",
+      "url": "sdo_membership.csv",
+      "schema:name": "Standards Development Organisations membership",
+      "tableSchema": {
+        "primaryKey": "organisation_url",
+        "columns": [
+          {"titles": "organisation_url", "name": "organisation_url", "datatype": "schema:URL", "required": true},
+          {"titles": "organisation_name", "name": "organisation_name", "datatype": "string", "required": true},
+          {"titles": "cost", "name": "cost", "datatype": "number"},
+          {"titles": "renewal_date", "name": "renewal_date", "datatype": "date"}
+        ]
+      }
+    }"#;
+
+    #[test]
+    fn read_valid_table() -> Result<()> {
+        let metadata: Metadata = parse_json(METADATA)?;
+        let dataset = Dataset::new(
+            metadata,
+            "organisation_url,organisation_name,cost,renewal_date\nhttps://www.w3.org/,W3C,7800,2021-09-30\nhttps://www.bsigroup.com/,BSi,,\n",
+        )?;
+
+        assert_eq!(dataset.id(), "sdo-membership");
+        assert_eq!(dataset.rows.len(), 2);
+        assert_eq!(
+            dataset.rows[1],
+            vec!["https://www.bsigroup.com/", "BSi", "", ""]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn report_every_violation() -> Result<()> {
+        let metadata: Metadata = parse_json(METADATA)?;
+        let error = Dataset::new(
+            metadata,
+            "organisation_url,organisation_name,cost,renewal_date\nhttps://www.w3.org/,,lots,2021-09-31\nhttps://www.w3.org/,W3C,,soon\n",
+        )
+        .expect_err("an invalid table")
+        .downcast::<DatasetError>()?;
+
+        assert_eq!(
+            error.to_string(),
+            "The table does not conform to its schema:\n\
+             line 2: missing value for the required column `organisation_name`\n\
+             line 2: `lots` in column `cost` is not a valid number\n\
+             line 3: `soon` in column `renewal_date` is not a valid date\n\
+             line 3: duplicate primary key `https://www.w3.org/`"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn reject_mismatched_header() -> Result<()> {
+        let metadata: Metadata = parse_json(METADATA)?;
+        let error = Dataset::new(metadata, "organisation_url,name,cost,renewal_date\n")
+            .expect_err("a mismatched header")
+            .downcast::<DatasetError>()?;
+
+        assert!(matches!(error, DatasetError::Header { .. }));
+
+        Ok(())
+    }
+
+    #[test]
+    fn add_get_drop() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+        let metadata: Metadata = parse_json(METADATA)?;
+        let dataset = Dataset::new(
+            metadata,
+            "organisation_url,organisation_name,cost,renewal_date\nhttps://www.w3.org/,W3C,7800,2021-09-30\n",
+        )?;
+
+        cache.add(&dataset)?;
+        cache.add(&dataset)?;
+
+        let cached: Dataset = cache.get("sdo-membership")?.expect("dataset to exist");
+
+        assert_eq!(cached, dataset);
+        assert_eq!(cached.checksum(), dataset.checksum());
+
+        let _: Option<Dataset> = cache.drop("sdo-membership")?;
+        let void: Option<Dataset> = cache.get("sdo-membership")?;
+
+        assert!(void.is_none());
+
+        Ok(())
+    }
+}
@@ -2,11 +2,12 @@ use anyhow;
 use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
+use thiserror::Error;
 
 use super::Date;
 use crate::checksum::{Digest, Hasher, Tag};
 
-#[derive(Debug, Clone, Deserialize, Serialize)]
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
 #[serde(rename_all = "kebab-case")]
 pub enum EndorsementStatus {
     Identified,
@@ -17,6 +18,26 @@ pub enum EndorsementStatus {
     Superseded,
 }
 
+impl EndorsementStatus {
+    /// Whether the endorsement state machine allows moving from this status to the given one.
+    ///
+    /// See `corpus/assets/endorsement_state_machine.mmd`.
+    pub fn can_transition_to(&self, next: &EndorsementStatus) -> bool {
+        use EndorsementStatus::*;
+
+        matches!(
+            (self, next),
+            (Identified, Proposed)
+                | (Proposed, Endorsed)
+                | (Proposed, Disavowed)
+                | (Endorsed, Endorsed)
+                | (Endorsed, Retired)
+                | (Endorsed, Superseded)
+                | (Disavowed, Proposed)
+        )
+    }
+}
+
 impl fmt::Display for EndorsementStatus {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         use EndorsementStatus::*;
@@ -61,7 +82,7 @@ pub struct EndorsementState {
     pub status: EndorsementStatus,
     pub start_date: Date,
     pub review_date: Date,
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
     pub end_date: Option<Date>,
 }
 
@@ -74,3 +95,172 @@ impl Digest for EndorsementState {
         self.end_date.digest(hasher);
     }
 }
+
+/// A step in the endorsement history of a standard.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EndorsementTransition {
+    /// The status the standard moved to.
+    pub status: EndorsementStatus,
+    /// The date the standard moved to the status.
+    pub date: Date,
+}
+
+impl Digest for EndorsementTransition {
+    fn digest(&self, hasher: &mut Hasher) {
+        hasher.update(&Tag::Dict.to_bytes());
+        self.status.digest(hasher);
+        self.date.digest(hasher);
+    }
+}
+
+#[derive(Debug, Clone, Error)]
+pub enum EndorsementError {
+    #[error("the endorsement history must start as `identified` but starts as `{0}`")]
+    InvalidStart(EndorsementStatus),
+    #[error(
+        "the endorsement history moves from `{from}` to `{to}` which is not an allowed transition"
+    )]
+    InvalidTransition {
+        from: EndorsementStatus,
+        to: EndorsementStatus,
+    },
+    #[error("the endorsement history moves to `{status}` on {date} which is before the previous transition")]
+    OutOfOrder {
+        status: EndorsementStatus,
+        date: Date,
+    },
+    #[error("the endorsement history ends as `{history}` but the endorsement state is `{state}`")]
+    Mismatch {
+        history: EndorsementStatus,
+        state: EndorsementStatus,
+    },
+}
+
+/// Checks the given history follows the endorsement state machine and ends with the given state.
+///
+/// An empty history is always valid.
+pub fn validate_history(
+    history: &[EndorsementTransition],
+    state: &EndorsementState,
+) -> Result<(), EndorsementError> {
+    let (first, last) = match (history.first(), history.last()) {
+        (Some(first), Some(last)) => (first, last),
+        _ => return Ok(()),
+    };
+
+    if first.status != EndorsementStatus::Identified {
+        return Err(EndorsementError::InvalidStart(first.status.clone()));
+    }
+
+    for pair in history.windows(2) {
+        let (previous, next) = (&pair[0], &pair[1]);
+
+        if !previous.status.can_transition_to(&next.status) {
+            return Err(EndorsementError::InvalidTransition {
+                from: previous.status.clone(),
+                to: next.status.clone(),
+            });
+        }
+
+        if next.date < previous.date {
+            return Err(EndorsementError::OutOfOrder {
+                status: next.status.clone(),
+                date: next.date.clone(),
+            });
+        }
+    }
+
+    if last.status != state.status {
+        return Err(EndorsementError::Mismatch {
+            history: last.status.clone(),
+            state: state.status.clone(),
+        });
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use EndorsementStatus::*;
+
+    fn transition(status: EndorsementStatus, date: &str) -> EndorsementTransition {
+        EndorsementTransition {
+            status,
+            date: date.into(),
+        }
+    }
+
+    fn state(status: EndorsementStatus) -> EndorsementState {
+        EndorsementState {
+            status,
+            start_date: "2021-06-01".into(),
+            review_date: "2022-06-01".into(),
+            end_date: None,
+        }
+    }
+
+    #[test]
+    fn valid_history() {
+        let history = vec![
+            transition(Identified, "2021-01-01"),
+            transition(Proposed, "2021-02-01"),
+            transition(Disavowed, "2021-03-01"),
+            transition(Proposed, "2021-04-01"),
+            transition(Endorsed, "2021-06-01"),
+        ];
+
+        assert!(validate_history(&history, &state(Endorsed)).is_ok());
+    }
+
+    #[test]
+    fn invalid_transition() {
+        let history = vec![
+            transition(Identified, "2021-01-01"),
+            transition(Endorsed, "2021-06-01"),
+        ];
+        let actual = validate_history(&history, &state(Endorsed));
+
+        assert!(matches!(
+            actual,
+            Err(EndorsementError::InvalidTransition {
+                from: Identified,
+                to: Endorsed
+            })
+        ));
+    }
+
+    #[test]
+    fn invalid_start() {
+        let history = vec![transition(Proposed, "2021-01-01")];
+        let actual = validate_history(&history, &state(Proposed));
+
+        assert!(matches!(
+            actual,
+            Err(EndorsementError::InvalidStart(Proposed))
+        ));
+    }
+
+    #[test]
+    fn out_of_order() {
+        let history = vec![
+            transition(Identified, "2021-02-01"),
+            transition(Proposed, "2021-01-01"),
+        ];
+        let actual = validate_history(&history, &state(Proposed));
+
+        assert!(matches!(actual, Err(EndorsementError::OutOfOrder { .. })));
+    }
+
+    #[test]
+    fn mismatched_state() {
+        let history = vec![
+            transition(Identified, "2021-01-01"),
+            transition(Proposed, "2021-02-01"),
+        ];
+        let actual = validate_history(&history, &state(Endorsed));
+
+        assert!(matches!(actual, Err(EndorsementError::Mismatch { .. })));
+    }
+}
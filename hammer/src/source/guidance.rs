@@ -3,9 +3,13 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-use super::{split_content, Date, OrganisationId, StandardId, Url};
-use crate::cache::{Cache, GuidanceRecord, GuidanceStandardRecord, Transaction};
-use crate::checksum::{Checksum, Digest, Hasher};
+use super::{parse_frontmatter, Date, OrganisationId, StandardId, Url};
+use crate::cache::{
+    Cache, GuidanceEditorialItemRecord, GuidanceEditorialRecord, GuidanceRecord,
+    GuidanceStandardRecord, SearchRecord, Transaction,
+};
+use crate::checksum::{Checksum, Digest, Hasher, Tag};
+use crate::markdown;
 use crate::report;
 use crate::resource::Resource;
 use crate::Status;
@@ -48,8 +52,7 @@ impl FromStr for Guidance {
     type Err = anyhow::Error;
 
     fn from_str(blob: &str) -> Result<Self, Self::Err> {
-        let (frontmatter, content) = split_content(blob)?;
-        let metadata = serde_yaml::from_str(frontmatter)?;
+        let (metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
 
         Ok(Self {
             metadata,
@@ -70,12 +73,13 @@ impl Resource<Guidance> for Cache {
                 .iter()
                 .map(|record| record.standard_id.clone())
                 .collect::<Vec<_>>();
-            let related = if related_list.len() == 0 {
+            let related = if related_list.is_empty() {
                 None
             } else {
                 Some(related_list)
             };
 
+            let editorial = select_editorial(&tx, id)?;
             let metadata = Metadata {
                 id: cached.id,
                 description: cached.description,
@@ -86,6 +90,15 @@ impl Resource<Guidance> for Cache {
                 publication_date: cached.publication_date,
                 standards: related,
                 canonical_url: cached.canonical_url,
+                authors: editorial.authors,
+                reviewers: editorial.reviewers,
+                purpose: editorial.purpose,
+                target: editorial.target,
+                user_needs: editorial.user_needs,
+                route: editorial.route,
+                out_of_scope: editorial.out_of_scope,
+                problem_statements: editorial.problem_statements,
+                tags: editorial.tags,
             };
 
             let guidance = Guidance {
@@ -96,8 +109,7 @@ impl Resource<Guidance> for Cache {
             result = Some(guidance);
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Guidance, id, "");
 
         tx.commit()?;
@@ -111,17 +123,17 @@ impl Resource<Guidance> for Cache {
 
         if let Some(cached) = GuidanceRecord::select(&tx, item.id())? {
             if cached.checksum != checksum {
-                GuidanceRecord::delete(&tx, &item.id())?;
-                create(&tx, &item)?;
+                GuidanceRecord::delete(&tx, item.id())?;
+                create(&tx, item)?;
             }
         } else {
-            create(&tx, &item)?;
+            create(&tx, item)?;
         }
 
+        SearchRecord::insert(&tx, &item.into())?;
         Cache::insert_trailmark(&tx, &checksum, "guidance", &self.timestamp)?;
 
-        &self
-            .report
+        self.report
             .log(report::Action::Add, report::Entity::Standard, item.id(), "");
 
         tx.commit()?;
@@ -130,15 +142,15 @@ impl Resource<Guidance> for Cache {
     }
 
     fn drop(&mut self, id: &str) -> Result<Option<Guidance>> {
-        let item = self.get(&id)?;
+        let item = self.get(id)?;
         let tx = self.conn.transaction()?;
 
         if item.is_some() {
             GuidanceRecord::delete(&tx, id)?;
+            SearchRecord::delete(&tx, "guidance", id)?;
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Prune, report::Entity::Guidance, id, "");
 
         tx.commit()?;
@@ -148,12 +160,12 @@ impl Resource<Guidance> for Cache {
 }
 
 fn create(tx: &Transaction, item: &Guidance) -> Result<()> {
-    GuidanceRecord::insert(&tx, &item.into())?;
+    GuidanceRecord::insert(tx, &item.into())?;
 
     if let Some(list) = &item.metadata.standards {
         for standard_id in list {
             GuidanceStandardRecord::insert(
-                &tx,
+                tx,
                 &GuidanceStandardRecord {
                     guidance_id: item.id().clone(),
                     standard_id: standard_id.clone(),
@@ -162,6 +174,82 @@ fn create(tx: &Transaction, item: &Guidance) -> Result<()> {
         }
     }
 
+    insert_editorial(tx, item.id(), &item.metadata.editorial())?;
+
+    Ok(())
+}
+
+/// Reads the editorial fields of the given guidance from the cache.
+pub(crate) fn select_editorial(tx: &Transaction, guidance_id: &str) -> Result<Editorial> {
+    let mut editorial = Editorial::default();
+
+    if let Some(record) = GuidanceEditorialRecord::select(tx, guidance_id)? {
+        editorial.purpose = record.purpose;
+        editorial.target = record.target;
+        editorial.user_needs = record.user_needs;
+        editorial.route = record.route;
+        editorial.out_of_scope = record.out_of_scope;
+    }
+
+    for record in GuidanceEditorialItemRecord::select(tx, guidance_id)? {
+        let list = match record.field.as_str() {
+            "author" => &mut editorial.authors,
+            "reviewer" => &mut editorial.reviewers,
+            "problem_statement" => &mut editorial.problem_statements,
+            "tag" => &mut editorial.tags,
+            _ => continue,
+        };
+
+        list.push(record.value);
+    }
+
+    Ok(editorial)
+}
+
+fn insert_editorial(tx: &Transaction, guidance_id: &str, editorial: &Editorial) -> Result<()> {
+    let record = GuidanceEditorialRecord {
+        guidance_id: guidance_id.to_string(),
+        purpose: editorial.purpose.clone(),
+        target: editorial.target.clone(),
+        user_needs: editorial.user_needs.clone(),
+        route: editorial.route.clone(),
+        out_of_scope: editorial.out_of_scope.clone(),
+    };
+    let has_values = [
+        &record.purpose,
+        &record.target,
+        &record.user_needs,
+        &record.route,
+        &record.out_of_scope,
+    ]
+    .iter()
+    .any(|value| value.is_some());
+
+    if has_values {
+        GuidanceEditorialRecord::insert(tx, &record)?;
+    }
+
+    let lists = [
+        ("author", &editorial.authors),
+        ("reviewer", &editorial.reviewers),
+        ("problem_statement", &editorial.problem_statements),
+        ("tag", &editorial.tags),
+    ];
+
+    for (field, list) in lists {
+        for (ordinal, value) in list.iter().enumerate() {
+            GuidanceEditorialItemRecord::insert(
+                tx,
+                &GuidanceEditorialItemRecord {
+                    guidance_id: guidance_id.to_string(),
+                    field: field.to_string(),
+                    ordinal: ordinal as u32,
+                    value: value.clone(),
+                },
+            )?;
+        }
+    }
+
     Ok(())
 }
 
@@ -182,22 +270,69 @@ impl From<&Guidance> for GuidanceRecord {
     }
 }
 
+impl From<&Guidance> for SearchRecord {
+    fn from(guidance: &Guidance) -> Self {
+        SearchRecord {
+            resource_type: "guidance".into(),
+            resource_id: guidance.id().clone(),
+            title: markdown::take_title(&guidance.content)
+                .unwrap_or_else(|_| guidance.id().clone()),
+            content: guidance.content.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Metadata {
     #[serde(rename = "identifier")]
-    id: GuidanceId,
+    pub id: GuidanceId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub maintainer: OrganisationId,
+    pub status: Status,
+    pub creation_date: Date,
+    pub update_date: Date,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication_date: Option<Date>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub standards: Option<Vec<StandardId>>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<Url>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    description: Option<String>,
-    maintainer: OrganisationId,
-    status: Status,
-    creation_date: Date,
-    update_date: Date,
+    pub target: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    publication_date: Option<Date>,
+    pub user_needs: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    standards: Option<Vec<StandardId>>,
+    pub route: Option<String>,
     #[serde(default, skip_serializing_if = "Option::is_none")]
-    canonical_url: Option<Url>,
+    pub out_of_scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problem_statements: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Metadata {
+    /// The editorial notes of the guidance, which are not published but must survive a round trip.
+    pub fn editorial(&self) -> Editorial {
+        Editorial {
+            authors: self.authors.clone(),
+            reviewers: self.reviewers.clone(),
+            purpose: self.purpose.clone(),
+            target: self.target.clone(),
+            user_needs: self.user_needs.clone(),
+            route: self.route.clone(),
+            out_of_scope: self.out_of_scope.clone(),
+            problem_statements: self.problem_statements.clone(),
+            tags: self.tags.clone(),
+        }
+    }
 }
 
 impl Digest for Metadata {
@@ -211,6 +346,51 @@ impl Digest for Metadata {
         self.publication_date.digest(hasher);
         self.standards.digest(hasher);
         self.canonical_url.digest(hasher);
+        self.editorial().digest(hasher);
+    }
+}
+
+/// The editorial fields of a guidance, kept together in the Zola page.
+#[derive(Debug, Clone, Default, PartialEq, Deserialize, Serialize)]
+pub struct Editorial {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub authors: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub reviewers: Vec<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub purpose: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub target: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub user_needs: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub route: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub out_of_scope: Option<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub problem_statements: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tags: Vec<String>,
+}
+
+impl Editorial {
+    pub fn is_empty(&self) -> bool {
+        self == &Editorial::default()
+    }
+}
+
+impl Digest for Editorial {
+    fn digest(&self, hasher: &mut Hasher) {
+        hasher.update(&Tag::Dict.to_bytes());
+        self.authors.digest(hasher);
+        self.reviewers.digest(hasher);
+        self.purpose.digest(hasher);
+        self.target.digest(hasher);
+        self.user_needs.digest(hasher);
+        self.route.digest(hasher);
+        self.out_of_scope.digest(hasher);
+        self.problem_statements.digest(hasher);
+        self.tags.digest(hasher);
     }
 }
 
@@ -220,7 +400,7 @@ mod tests {
     use anyhow::Result;
     use std::str::FromStr;
 
-    static GUIDANCE: &'static str = r#"---
+    static GUIDANCE: &str = r#"---
 type: guidance
 identifier: when-to-use-a-graphql-api
 maintainer: data-standards-authority
@@ -253,12 +433,12 @@ standards:
         let mut cache = Cache::connect(":memory:")?;
 
         cache.add(&original)?;
-        let cached: Guidance = cache.get(&original.id())?.expect("guidance doesn't exist");
+        let cached: Guidance = cache.get(original.id())?.expect("guidance doesn't exist");
 
         assert_eq!(&original.checksum(), &cached.checksum());
 
-        let _: Option<Guidance> = cache.drop(&original.id())?;
-        let void: Option<Guidance> = cache.get(&original.id())?;
+        let _: Option<Guidance> = cache.drop(original.id())?;
+        let void: Option<Guidance> = cache.get(original.id())?;
 
         assert!(void.is_none());
 
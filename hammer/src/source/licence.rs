@@ -3,7 +3,7 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-use super::LicenceId;
+use super::{parse_json, LicenceId};
 use crate::cache::{Cache, LicenceRecord};
 use crate::checksum::{Checksum, Digest, Hasher};
 use crate::report;
@@ -50,7 +50,7 @@ impl FromStr for Licence {
     type Err = anyhow::Error;
 
     fn from_str(blob: &str) -> Result<Self, Self::Err> {
-        let licence = serde_json::from_str(blob)?;
+        let licence = parse_json(blob)?;
 
         Ok(licence)
     }
@@ -70,8 +70,7 @@ impl Resource<Licence> for Cache {
             });
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Licence, licence_id, "");
 
         tx.commit()?;
@@ -94,7 +93,7 @@ impl Resource<Licence> for Cache {
 
         Cache::insert_trailmark(&tx, &checksum, "licence", &self.timestamp)?;
 
-        &self.report.log(
+        self.report.log(
             report::Action::Add,
             report::Entity::Licence,
             &licence.id,
@@ -107,14 +106,14 @@ impl Resource<Licence> for Cache {
     }
 
     fn drop(&mut self, licence_id: &str) -> Result<Option<Licence>> {
-        let licence = self.get(&licence_id)?;
+        let licence = self.get(licence_id)?;
         let tx = self.conn.transaction()?;
 
         if licence.is_some() {
             LicenceRecord::delete(&tx, licence_id)?;
         }
 
-        &self.report.log(
+        self.report.log(
             report::Action::Prune,
             report::Entity::Licence,
             licence_id,
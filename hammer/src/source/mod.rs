@@ -1,41 +1,52 @@
 //! This module deals with data shaped as source, a mix of Markdown, Toml, CSV and YAML.
 //!
-//! Source Markdown files are prepended with a YAML frontmatter.
-use anyhow::Result;
+//! Source Markdown files are prepended with a YAML frontmatter, or a TOML one as Zola pages are. Any image they link to is read as an [`Asset`].
+use anyhow::{anyhow, Result};
 use lazy_static::lazy_static;
 use log::{info, warn};
 use regex::Regex;
-use std::fs::File;
-use std::io::prelude::*;
-use std::io::BufReader;
+use serde::de::DeserializeOwned;
+use std::fs;
 use std::path::Path;
 use std::str::FromStr;
+use thiserror::Error;
 use walkdir::{DirEntry, WalkDir};
 
+pub mod asset;
+pub mod dataset;
 pub mod endorsement;
 pub mod guidance;
 pub mod licence;
 pub mod organisation;
+pub mod process;
 pub mod section;
 pub mod standard;
+pub mod story;
+pub mod strict;
 pub mod theme;
 pub mod topic;
 
+pub use asset::Asset;
+pub use dataset::Dataset;
 pub use guidance::Guidance;
 pub use licence::Licence;
 pub use organisation::Organisation;
+pub use process::Process;
 pub use section::Section;
 pub use standard::Standard;
+pub use story::{CaseStudy, UseCase};
+pub use strict::UnknownFields;
 pub use theme::Theme;
 pub use topic::Topic;
 
 use crate::cache::Cache;
+use crate::diagnostic::{Diagnostic, Diagnostics, LocatedError, Location};
 use crate::resource::{Resource, ResourceType};
 
 // TODO: Consider promoting to Chrono
 pub type Date = String;
 
-// pub type SubjectId = String;
+pub type SubjectId = String;
 pub type StandardId = String;
 pub type LicenceId = String;
 pub type OrganisationId = String;
@@ -43,107 +54,477 @@ pub type TopicId = String;
 pub type ThemeId = String;
 pub type Url = String;
 
-fn split_content(blob: &str) -> Result<(&str, &str)> {
+#[derive(Debug, Clone, Error)]
+pub enum SourceError {
+    #[error("Could not find a frontmatter delimited by `---` (YAML) or `+++` (TOML) in the given source text.")]
+    MissingFrontmatter,
+    #[error(
+        "The frontmatter has no `type` property. Expected one of {}.",
+        MARKDOWN_TYPES
+    )]
+    MissingType,
+}
+
+/// The resource types a Markdown source can declare, for error messages.
+const MARKDOWN_TYPES: &str =
+    "case-study, guidance, process, section, standard, theme, topic or use-case";
+
+/// The language of a frontmatter, given by its delimiters.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// Delimited by `---`.
+    Yaml,
+    /// Delimited by `+++`, as Zola pages are.
+    Toml,
+}
+
+fn split_content(blob: &str) -> Result<(Format, &str, &str)> {
     lazy_static! {
-        static ref FRONTMATTER_RE: Regex =
+        static ref YAML_RE: Regex =
             Regex::new(r"^\s*---(\r?\n(?s).*?(?-s))---\r?\n?((?s).*(?-s))$").unwrap();
+        static ref TOML_RE: Regex =
+            Regex::new(r"^\s*\+\+\+(\r?\n(?s).*?(?-s))\+\+\+\r?\n?((?s).*(?-s))$").unwrap();
     }
 
-    let groups = FRONTMATTER_RE
-        .captures(blob)
-        .expect("frontmatter split failure");
+    let (format, groups) = match (YAML_RE.captures(blob), TOML_RE.captures(blob)) {
+        (Some(groups), _) => (Format::Yaml, groups),
+        (None, Some(groups)) => (Format::Toml, groups),
+        (None, None) => return Err(SourceError::MissingFrontmatter.into()),
+    };
     let frontmatter = groups.get(1).expect("group frontmatter missing").as_str();
     let content = groups.get(2).expect("group content missing").as_str();
 
-    Ok((frontmatter, content))
+    Ok((format, frontmatter, content))
+}
+
+/// Splits the given source text and deserialises its frontmatter, either YAML or TOML.
+///
+/// Errors are located within the full source text, accounting for the lines before the frontmatter.
+pub(crate) fn parse_frontmatter<T: DeserializeOwned>(blob: &str) -> Result<(T, &str)> {
+    let (format, frontmatter, content) = split_content(blob)?;
+    let offset = frontmatter.as_ptr() as usize - blob.as_ptr() as usize;
+    let line_offset = blob[..offset].matches('\n').count();
+    let metadata = match format {
+        Format::Yaml => serde_yaml::from_str(frontmatter)
+            .map_err(|err| locate_yaml_error(blob, line_offset, &err))?,
+        Format::Toml => parse_toml(frontmatter)
+            .map_err(|err| locate_toml_error(blob, line_offset, &err))?
+            .try_into()?,
+    };
+
+    Ok((metadata, content))
+}
+
+/// Parses a TOML frontmatter.
+///
+/// TOML has dates of its own whereas the model keeps them as the strings YAML reads them as.
+fn parse_toml(frontmatter: &str) -> Result<toml::Value, toml::de::Error> {
+    fn stringify_dates(value: toml::Value) -> toml::Value {
+        match value {
+            toml::Value::Datetime(date) => toml::Value::String(date.to_string()),
+            toml::Value::Array(list) => {
+                toml::Value::Array(list.into_iter().map(stringify_dates).collect())
+            }
+            toml::Value::Table(table) => toml::Value::Table(
+                table
+                    .into_iter()
+                    .map(|(key, value)| (key, stringify_dates(value)))
+                    .collect(),
+            ),
+            value => value,
+        }
+    }
+
+    toml::from_str(frontmatter).map(stringify_dates)
+}
+
+fn locate_toml_error(blob: &str, line_offset: usize, err: &toml::de::Error) -> anyhow::Error {
+    lazy_static! {
+        static ref MESSAGE_RE: Regex = Regex::new(r"^(.*?)(?: at line \d+ column \d+)?$").unwrap();
+    }
+
+    let (line, column) = match err.line_col() {
+        Some(position) => position,
+        None => return anyhow!(err.to_string()),
+    };
+    let text = err.to_string();
+    let message = MESSAGE_RE
+        .captures(&text)
+        .and_then(|groups| groups.get(1))
+        .map_or("", |m| m.as_str());
+
+    // TOML positions are 0-based and the frontmatter starts right after the `+++` delimiter.
+    LocatedError {
+        message: message.to_string(),
+        location: Location::new(blob, line + line_offset + 1, column + 1, None),
+    }
+    .into()
+}
+
+fn locate_yaml_error(blob: &str, line_offset: usize, err: &serde_yaml::Error) -> anyhow::Error {
+    lazy_static! {
+        static ref MESSAGE_RE: Regex =
+            Regex::new(r"^(?:([\w\.\[\]\?-]+): )?(.*?)(?: at line \d+ column \d+)?$").unwrap();
+        static ref MISSING_RE: Regex = Regex::new(r"^missing field `(.+)`$").unwrap();
+    }
+
+    let location = match err.location() {
+        Some(location) => location,
+        None => return anyhow!(err.to_string()),
+    };
+    let text = err.to_string();
+    let groups = MESSAGE_RE.captures(&text).expect("message to match");
+    let parent = groups.get(1).map(|m| m.as_str());
+    let message = groups.get(2).map_or("", |m| m.as_str());
+    // A missing field is reported against its parent so the key is the parent plus the field.
+    let key = match (parent, MISSING_RE.captures(message)) {
+        (Some(parent), Some(missing)) => Some(format!("{}.{}", parent, &missing[1])),
+        (None, Some(missing)) => Some(missing[1].to_string()),
+        (parent, None) => parent.map(Into::into),
+    };
+
+    LocatedError {
+        message: message.to_string(),
+        location: Location::new(blob, location.line() + line_offset, location.column(), key),
+    }
+    .into()
+}
+
+/// Detects the resource type declared by the `type` property in the frontmatter of the given source text.
+///
+/// The property can be anywhere in the frontmatter and neither its name nor its value are case sensitive. A missing
+/// or unknown type is an error.
+pub fn resource_type(blob: &str) -> Result<ResourceType> {
+    lazy_static! {
+        static ref TYPE_RE: Regex = Regex::new(r"(?im)^type\s*[:=]").unwrap();
+    }
+
+    let (frontmatter, _): (serde_yaml::Value, &str) = parse_frontmatter(blob)?;
+    let declared = frontmatter
+        .as_mapping()
+        .into_iter()
+        .flat_map(|mapping| mapping.iter())
+        .find(|(key, _)| {
+            key.as_str()
+                .is_some_and(|key| key.eq_ignore_ascii_case("type"))
+        })
+        .map(|(_, value)| value.as_str().unwrap_or_default())
+        .ok_or(SourceError::MissingType)?;
+
+    ResourceType::from_declaration(declared).ok_or_else(|| {
+        let (_, text, _) = split_content(blob).expect("frontmatter to be already split");
+        let offset = text.as_ptr() as usize - blob.as_ptr() as usize;
+        let line = TYPE_RE
+            .find(text)
+            .map_or(1, |m| blob[..offset + m.start()].matches('\n').count() + 1);
+
+        LocatedError {
+            message: format!(
+                "unknown type `{}`, expected one of {}",
+                declared, MARKDOWN_TYPES
+            ),
+            location: Location::new(blob, line, 1, Some("type".into())),
+        }
+        .into()
+    })
+}
+
+/// Deserialises the given JSON source text locating any error within it.
+fn parse_json<T: DeserializeOwned>(blob: &str) -> Result<T> {
+    lazy_static! {
+        static ref MESSAGE_RE: Regex = Regex::new(r"^(.*?)(?: at line \d+ column \d+)?$").unwrap();
+    }
+
+    serde_json::from_str(blob).map_err(|err| {
+        let text = err.to_string();
+        let message = MESSAGE_RE
+            .captures(&text)
+            .and_then(|groups| groups.get(1))
+            .map_or("", |m| m.as_str());
+
+        LocatedError {
+            message: message.to_string(),
+            location: Location::new(blob, err.line(), err.column(), None),
+        }
+        .into()
+    })
 }
 
 /// Helper function for the CLI to read from the given path and cache the content.
-pub fn read(source_dir: &Path, cache: &mut Cache) -> Result<()> {
+///
+/// Files that fail to be processed don't stop the reading. Instead, their errors are collected and returned as
+/// diagnostics so the caller can decide what to do with them. Properties that are not part of the model are
+/// reported according to the given `unknown_fields` mode.
+pub fn read(
+    source_dir: &Path,
+    cache: &mut Cache,
+    unknown_fields: UnknownFields,
+) -> Result<Diagnostics> {
     let walker = WalkDir::new(source_dir).into_iter();
+    let mut diagnostics = Diagnostics::new();
 
     for result in walker.filter_entry(|e| !is_hidden(e)) {
         let entry = result?;
 
-        if let Some(ext) = entry.path().extension() {
-            if ext == "md" {
-                process_markdown_source(cache, &entry.path())?;
-            } else if ext == "json" {
-                process_json_source(cache, &entry.path())?;
-            } else {
-                warn!("unprocessed {}", &entry.path().display());
-            }
+        diagnostics.extend(read_file(source_dir, entry.path(), cache, unknown_fields));
+    }
+
+    Ok(diagnostics)
+}
+
+/// Reads a single source file within `source_dir` into the cache, returning the problems found.
+fn read_file(
+    source_dir: &Path,
+    path: &Path,
+    cache: &mut Cache,
+    unknown_fields: UnknownFields,
+) -> Diagnostics {
+    let mut diagnostics = Diagnostics::new();
+
+    if let Some(ext) = path.extension() {
+        let outcome = if ext == "md" || ext == "json" {
+            process_text_source(cache, source_dir, path, unknown_fields, &mut diagnostics)
+        } else if asset::is_asset_path(path) {
+            process_asset_source(cache, path)
+        } else if dataset::metadata_path(path).is_some() {
+            // Read along with its metadata file.
+            Ok(())
+        } else {
+            warn!("unprocessed {}", &path.display());
+            Ok(())
+        };
+
+        if let Err(err) = outcome {
+            warn!("failed {}", &path.display());
+            diagnostics.push_error(path, &err);
         }
     }
 
-    Ok(())
+    diagnostics
 }
 
-fn process_markdown_source(cache: &mut Cache, entry: &Path) -> Result<()> {
-    let path = entry.display().to_string();
-    let mut file = File::open(&entry)?;
-    let mut contents = String::new();
-    file.read_to_string(&mut contents)?;
-    let resource_type = ResourceType::from_hint(&contents);
+/// Re-reads a source file within `source_dir` that changed since it was last read.
+///
+/// Resources the file no longer declares are dropped from the cache. A CSV table described by a metadata file
+/// refreshes the dataset declared by the metadata file.
+pub fn refresh(
+    source_dir: &Path,
+    path: &Path,
+    cache: &mut Cache,
+    unknown_fields: UnknownFields,
+) -> Result<Diagnostics> {
+    if let Some(metadata_path) = dataset::metadata_path(path) {
+        return refresh(source_dir, &metadata_path, cache, unknown_fields);
+    }
+
+    let previous = cache.take_origins(&path.display().to_string())?;
+    let diagnostics = read_file(source_dir, path, cache, unknown_fields);
+    let current = cache.origins(&path.display().to_string())?;
+
+    for origin in previous {
+        let is_current = current.iter().any(|record| {
+            record.resource_type == origin.resource_type && record.resource_id == origin.resource_id
+        });
+
+        if !is_current {
+            drop_resource(cache, &origin.resource_type, &origin.resource_id)?;
+        }
+    }
+
+    Ok(diagnostics)
+}
 
+/// Drops every resource read from a source file that no longer exists.
+///
+/// A missing CSV table still described by a metadata file refreshes the dataset so the missing table is reported.
+pub fn forget(source_dir: &Path, path: &Path, cache: &mut Cache) -> Result<()> {
+    if let Some(metadata_path) = dataset::metadata_path(path) {
+        let diagnostics = refresh(source_dir, &metadata_path, cache, UnknownFields::Ignore)?;
+
+        if !diagnostics.is_empty() {
+            warn!("{}", diagnostics);
+        }
+
+        return Ok(());
+    }
+
+    for origin in cache.take_origins(&path.display().to_string())? {
+        drop_resource(cache, &origin.resource_type, &origin.resource_id)?;
+    }
+
+    Ok(())
+}
+
+fn drop_resource(cache: &mut Cache, resource_type: &str, id: &str) -> Result<()> {
     match resource_type {
-        ResourceType::Unknown => {
-            warn!("unknown type {}", &path);
+        "asset" => Resource::<Asset>::drop(cache, id).map(|_| ()),
+        "case-study" => Resource::<CaseStudy>::drop(cache, id).map(|_| ()),
+        "dataset" => Resource::<Dataset>::drop(cache, id).map(|_| ()),
+        "guidance" => Resource::<Guidance>::drop(cache, id).map(|_| ()),
+        "licence" => Resource::<Licence>::drop(cache, id).map(|_| ()),
+        "organisation" => Resource::<Organisation>::drop(cache, id).map(|_| ()),
+        "process" => Resource::<Process>::drop(cache, id).map(|_| ()),
+        "section" => Resource::<Section>::drop(cache, id).map(|_| ()),
+        "standard" => Resource::<Standard>::drop(cache, id).map(|_| ()),
+        "theme" => Resource::<Theme>::drop(cache, id).map(|_| ()),
+        "topic" => Resource::<Topic>::drop(cache, id).map(|_| ()),
+        "use-case" => Resource::<UseCase>::drop(cache, id).map(|_| ()),
+        _ => Err(anyhow!("unknown resource type `{}`", resource_type)),
+    }
+}
+
+/// Reads a Markdown or JSON source file, checking its properties against the model before caching it.
+///
+/// Unknown properties are reported according to the given `unknown_fields` mode. When denied, the source is left out
+/// of the cache.
+fn process_text_source(
+    cache: &mut Cache,
+    source_dir: &Path,
+    entry: &Path,
+    unknown_fields: UnknownFields,
+    diagnostics: &mut Diagnostics,
+) -> Result<()> {
+    let blob = fs::read_to_string(entry)?;
+
+    if unknown_fields != UnknownFields::Ignore {
+        // A source that fails to deserialize is reported, with its location, when processed below.
+        let fields = strict::unknown_fields(entry, &blob).unwrap_or_default();
+        let denied = unknown_fields == UnknownFields::Deny && !fields.is_empty();
+
+        for field in fields {
+            let message = format!("unknown field `{}`", field);
+
+            diagnostics.push(match unknown_fields {
+                UnknownFields::Deny => Diagnostic::new(entry, &message),
+                _ => Diagnostic::warning(entry, &message),
+            });
         }
-        ref typ => {
-            info!("{} type {}", typ, &path);
+
+        if denied {
+            return Ok(());
         }
     }
 
+    if entry.extension().is_some_and(|ext| ext == "md") {
+        process_markdown_source(cache, source_dir, entry, &blob, diagnostics)
+    } else {
+        process_json_source(cache, entry, &blob)
+    }
+}
+
+fn process_markdown_source(
+    cache: &mut Cache,
+    source_dir: &Path,
+    entry: &Path,
+    blob: &str,
+    diagnostics: &mut Diagnostics,
+) -> Result<()> {
+    let path = entry.display().to_string();
+    let (contents, diagrams) = asset::render_diagrams(blob)?;
+    let base_dir = entry.parent().unwrap_or_else(|| Path::new(""));
+    let (contents, unresolved) = asset::rewrite_links(&contents, base_dir, source_dir);
+
+    for message in unresolved {
+        diagnostics.push(Diagnostic::warning(entry, &message));
+    }
+    let resource_type = resource_type(&contents)?;
+
+    info!("{} type {}", resource_type, &path);
+
     match resource_type {
+        ResourceType::CaseStudy => {
+            let resource = CaseStudy::from_str(&contents)?;
+            cache.add(&resource)?;
+            cache.insert_origin("case-study", resource.id(), &path)?;
+        }
         ResourceType::Guidance => {
             let resource = Guidance::from_str(&contents)?;
-            cache.add((&resource).into())?;
+            cache.add(&resource)?;
+            cache.insert_origin("guidance", resource.id(), &path)?;
+        }
+        ResourceType::Process => {
+            let resource = Process::from_source(entry, &contents)?;
+            cache.add(&resource)?;
+            cache.insert_origin("process", resource.id(), &path)?;
         }
         ResourceType::Section => {
             let resource = Section::from_str(&contents)?;
-            cache.add((&resource).into())?;
+            cache.add(&resource)?;
+            cache.insert_origin("section", resource.id(), &path)?;
         }
         ResourceType::Standard => {
             let resource = Standard::from_str(&contents)?;
-            cache.add((&resource).into())?;
+            cache.add(&resource)?;
+            cache.insert_origin("standard", resource.id(), &path)?;
         }
         ResourceType::Theme => {
             let resource = Theme::from_str(&contents)?;
-            cache.add((&resource).into())?;
+            cache.add(&resource)?;
+            cache.insert_origin("theme", resource.id(), &path)?;
         }
         ResourceType::Topic => {
             let resource = Topic::from_str(&contents)?;
-            cache.add((&resource).into())?;
+            cache.add(&resource)?;
+            cache.insert_origin("topic", resource.id(), &path)?;
+        }
+        ResourceType::UseCase => {
+            let resource = UseCase::from_str(&contents)?;
+            cache.add(&resource)?;
+            cache.insert_origin("use-case", resource.id(), &path)?;
         }
         _ => (),
     }
+
+    for diagram in diagrams {
+        cache.add(&diagram)?;
+        cache.insert_origin("asset", diagram.id(), &path)?;
+    }
+
+    Ok(())
+}
+
+fn process_asset_source(cache: &mut Cache, entry: &Path) -> Result<()> {
+    let path = entry.display().to_string();
+    let resource = Asset::from_path(entry)?;
+
+    cache.add(&resource)?;
+    cache.insert_origin("asset", resource.id(), &path)?;
+    info!("asset {}", &path);
+
     Ok(())
 }
 
-fn process_json_source(cache: &mut Cache, entry: &Path) -> Result<()> {
+fn process_json_source(cache: &mut Cache, entry: &Path, blob: &str) -> Result<()> {
     let path = &entry.display().to_string();
+
+    if dataset::is_metadata_path(entry) {
+        let resource = Dataset::from_source(entry, blob)?;
+        cache.add(&resource)?;
+        cache.insert_origin("dataset", &resource.id(), path)?;
+        info!("dataset {}", &path);
+
+        return Ok(());
+    }
+
     let file_stem = entry.file_stem().map(|s| s.to_string_lossy().into_owned());
     if let Some(stem) = file_stem.as_ref() {
         match stem.as_str() {
             "licences" => {
-                let file = File::open(path)?;
-                let reader = BufReader::new(file);
-                let resources: Vec<Licence> = serde_json::from_reader(reader)?;
+                let resources: Vec<Licence> = parse_json(blob)?;
 
                 for resource in &resources {
                     cache.add(resource)?;
+                    cache.insert_origin("licence", resource.id(), path)?;
                 }
 
                 info!("licence set {}", &path);
             }
             "organisations" => {
-                let file = File::open(path)?;
-                let reader = BufReader::new(file);
-                let resources: Vec<Organisation> = serde_json::from_reader(reader)?;
+                let resources: Vec<Organisation> = parse_json(blob)?;
 
                 for resource in &resources {
                     cache.add(resource)?;
+                    cache.insert_origin("organisation", resource.id(), path)?;
                 }
 
                 info!("organisation set {}", &path);
@@ -164,3 +545,246 @@ fn is_hidden(entry: &DirEntry) -> bool {
         .map(|s| s.starts_with("."))
         .unwrap_or(false)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use temp_testdir::TempDir;
+
+    #[test]
+    fn split_without_frontmatter() {
+        let actual = split_content("# Vapour\n\nNo frontmatter here.");
+
+        assert!(actual.is_err(), "error when no frontmatter found");
+    }
+
+    #[test]
+    fn toml_frontmatter() -> Result<()> {
+        let yaml = "---\ntype: standard\nidentifier: vapour\nname: Vapour\ntopic: exchange\nspecification: https://spec.vapour.org/\nmaintainer: data-standards-authority\nendorsement_state:\n  status: endorsed\n  start_date: 2021-06-01\n  review_date: 2021-12-01\n---\n# Vapour";
+        let toml = "+++\r\ntype = \"standard\"\r\nidentifier = \"vapour\"\r\nname = \"Vapour\"\r\ntopic = \"exchange\"\r\nspecification = \"https://spec.vapour.org/\"\r\nmaintainer = \"data-standards-authority\"\r\n\r\n[endorsement_state]\r\nstatus = \"endorsed\"\r\nstart_date = 2021-06-01\r\nreview_date = \"2021-12-01\"\r\n+++\r\n# Vapour";
+
+        assert!(matches!(resource_type(toml)?, ResourceType::Standard));
+        assert_eq!(
+            Standard::from_str(toml)?.checksum(),
+            Standard::from_str(yaml.replace('\n', "\r\n").as_str())?.checksum()
+        );
+
+        let error = located(Standard::from_str(
+            "\n+++\ntype = \"standard\"\nidentifier = vapour\n+++\n# Vapour",
+        ));
+
+        assert_eq!(error.location.line, 4);
+        assert_eq!(error.location.snippet, "identifier = vapour");
+
+        Ok(())
+    }
+
+    #[test]
+    fn detect_resource_type() -> Result<()> {
+        let crlf = "---\r\ntype: standard\r\nidentifier: vapour\r\n---\r\n# Vapour";
+        let commented = "---\n# A topic.\nidentifier: exchange\nType: Topic\n---\n# Exchange";
+
+        assert!(matches!(resource_type(crlf)?, ResourceType::Standard));
+        assert!(matches!(resource_type(commented)?, ResourceType::Topic));
+        assert!(matches!(
+            resource_type("---\ntype: Process\n---\n# Endorsement")?,
+            ResourceType::Process
+        ));
+
+        let missing = resource_type("---\nidentifier: vapour\n---\n# Vapour").unwrap_err();
+
+        assert!(matches!(
+            missing.downcast_ref::<SourceError>(),
+            Some(SourceError::MissingType)
+        ));
+
+        let unknown = resource_type("---\nidentifier: vapour\ntype: dataset\n---\n")
+            .unwrap_err()
+            .downcast::<LocatedError>()
+            .expect("a located error");
+
+        assert!(unknown.message.starts_with("unknown type `dataset`"));
+        assert_eq!(unknown.location.line, 3);
+        assert_eq!(unknown.location.snippet, "type: dataset");
+
+        Ok(())
+    }
+
+    fn located(result: Result<Standard>) -> LocatedError {
+        result
+            .expect_err("an error")
+            .downcast::<LocatedError>()
+            .expect("a located error")
+    }
+
+    #[test]
+    fn locate_invalid_variant() {
+        let raw = "\n---\ntype: standard\nidentifier: vapour\nname: Vapour\ntopic: exchange\nspecification: https://spec.vapour.org/\nmaintainer: data-standards-authority\nendorsement_state:\n    status: endorsd\n    start_date: 2021-06-01\n    review_date: 2021-06-01\n---\n# Vapour";
+        let error = located(Standard::from_str(raw));
+
+        assert!(error.message.starts_with("unknown variant `endorsd`"));
+        assert_eq!(error.location.line, 10);
+        assert_eq!(error.location.column, 13);
+        assert_eq!(
+            error.location.key.as_deref(),
+            Some("endorsement_state.status")
+        );
+        assert_eq!(error.location.snippet, "    status: endorsd");
+    }
+
+    #[test]
+    fn locate_missing_field() {
+        let raw = "---\ntype: standard\nidentifier: vapour\n---\n# Vapour";
+        let error = located(Standard::from_str(raw));
+
+        assert_eq!(error.message, "missing field `name`");
+        assert_eq!(error.location.line, 2);
+        assert_eq!(error.location.key.as_deref(), Some("name"));
+    }
+
+    #[test]
+    fn read_collects_every_failure() -> Result<()> {
+        let temp = TempDir::default();
+        let dir = temp.as_ref();
+        fs::write(
+            dir.join("exchange.md"),
+            "---\ntype: topic\nidentifier: exchange\nname: Exchange\ntheme: other\nordinal: 1\n---\n",
+        )?;
+        fs::write(
+            dir.join("vapour.md"),
+            "---\ntype: standard\nidentifier: vapour\n---\n",
+        )?;
+        fs::write(
+            dir.join("steam.md"),
+            "---\ntype: topic\nidentifier: steam\n",
+        )?;
+        fs::write(dir.join("licences.json"), "[{\"id\": \"ogl\"}]")?;
+
+        let mut cache = Cache::connect(":memory:")?;
+        let diagnostics = read(dir, &mut cache, UnknownFields::Ignore)?;
+        let paths: Vec<_> = diagnostics
+            .by_path()
+            .keys()
+            .map(|p| p.to_path_buf())
+            .collect();
+
+        assert_eq!(diagnostics.len(), 3);
+        assert_eq!(
+            paths,
+            vec![
+                dir.join("licences.json"),
+                dir.join("steam.md"),
+                dir.join("vapour.md")
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_reports_unknown_fields() -> Result<()> {
+        let temp = TempDir::default();
+        let dir = temp.as_ref();
+        fs::write(
+            dir.join("exchange.md"),
+            "---\ntype: topic\nidentifier: exchange\nname: Exchange\ntheme: other\nordinal: 1\ndescriptoin: Moving data\n---\n",
+        )?;
+
+        let mut cache = Cache::connect(":memory:")?;
+        let ignored = read(dir, &mut cache, UnknownFields::Ignore)?;
+        let warned = read(dir, &mut cache, UnknownFields::Warn)?;
+        let denied = read(dir, &mut cache, UnknownFields::Deny)?;
+
+        assert!(ignored.is_empty());
+        assert_eq!(warned.len(), 1);
+        assert!(!warned.has_errors());
+        assert!(denied.has_errors());
+        assert_eq!(
+            denied.iter().next().map(|d| d.message.as_str()),
+            Some("unknown field `descriptoin`")
+        );
+
+        let mut cache = Cache::connect(":memory:")?;
+        read(dir, &mut cache, UnknownFields::Deny)?;
+        let denied: Option<Topic> = cache.get("exchange")?;
+
+        assert!(denied.is_none(), "denied source left out of the cache");
+
+        Ok(())
+    }
+
+    #[test]
+    fn refresh_and_forget() -> Result<()> {
+        let temp = TempDir::default();
+        let dir = temp.as_ref();
+        let path = dir.join("exchange.md");
+        fs::write(
+            &path,
+            "---\ntype: topic\nidentifier: exchange\nname: Exchange\ntheme: other\nordinal: 1\n---\n",
+        )?;
+
+        let mut cache = Cache::connect(":memory:")?;
+        read(dir, &mut cache, UnknownFields::Ignore)?;
+
+        fs::write(
+            &path,
+            "---\ntype: topic\nidentifier: interchange\nname: Interchange\ntheme: other\nordinal: 1\n---\n",
+        )?;
+        refresh(dir, &path, &mut cache, UnknownFields::Ignore)?;
+
+        let old: Option<Topic> = cache.get("exchange")?;
+        let new: Option<Topic> = cache.get("interchange")?;
+
+        assert!(old.is_none(), "the renamed topic is dropped");
+        assert!(new.is_some());
+
+        fs::remove_file(&path)?;
+        forget(dir, &path, &mut cache)?;
+
+        let new: Option<Topic> = cache.get("interchange")?;
+
+        assert!(new.is_none(), "the deleted topic is dropped");
+
+        Ok(())
+    }
+
+    #[test]
+    fn read_and_refresh_dataset() -> Result<()> {
+        let temp = TempDir::default();
+        let dir = temp.as_ref();
+        let table = dir.join("members.csv");
+        fs::write(
+            dir.join("members.csv-metadata.json"),
+            r#"{
+              "@context": "http://www.w3.org/ns/csvw",
+              "url": "members.csv",
+              "schema:name": "Members",
+              "tableSchema": {
+                "primaryKey": "url",
+                "columns": [
+                  {"titles": "url", "name": "url", "datatype": "schema:URL", "required": true},
+                  {"titles": "cost", "name": "cost", "datatype": "number"}
+                ]
+              }
+            }"#,
+        )?;
+        fs::write(&table, "url,cost\nhttps://www.w3.org/,7800\n")?;
+
+        let mut cache = Cache::connect(":memory:")?;
+        let diagnostics = read(dir, &mut cache, UnknownFields::Deny)?;
+        let dataset: Option<Dataset> = cache.get("members")?;
+
+        assert!(diagnostics.is_empty());
+        assert_eq!(dataset.map(|dataset| dataset.rows.len()), Some(1));
+
+        fs::write(&table, "url,cost\nhttps://www.w3.org/,lots\n")?;
+        let diagnostics = refresh(dir, &table, &mut cache, UnknownFields::Ignore)?;
+        let dataset: Option<Dataset> = cache.get("members")?;
+
+        assert!(diagnostics.has_errors());
+        assert!(dataset.is_none(), "the invalid dataset is dropped");
+
+        Ok(())
+    }
+}
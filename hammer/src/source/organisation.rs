@@ -3,6 +3,7 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
+use super::parse_json;
 use crate::cache::{Cache, OrganisationRecord};
 use crate::checksum::{Checksum, Digest, Hasher};
 use crate::report;
@@ -48,7 +49,7 @@ impl FromStr for Organisation {
     type Err = anyhow::Error;
 
     fn from_str(blob: &str) -> Result<Self, Self::Err> {
-        let organisation = serde_json::from_str(blob)?;
+        let organisation = parse_json(blob)?;
 
         Ok(organisation)
     }
@@ -67,8 +68,7 @@ impl Resource<Organisation> for Cache {
             });
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Organisation, id, "");
 
         tx.commit()?;
@@ -91,7 +91,7 @@ impl Resource<Organisation> for Cache {
 
         Cache::insert_trailmark(&tx, &checksum, "organisation", &self.timestamp)?;
 
-        &self.report.log(
+        self.report.log(
             report::Action::Add,
             report::Entity::Organisation,
             &item.id,
@@ -104,15 +104,14 @@ impl Resource<Organisation> for Cache {
     }
 
     fn drop(&mut self, id: &str) -> Result<Option<Organisation>> {
-        let item = self.get(&id)?;
+        let item = self.get(id)?;
         let tx = self.conn.transaction()?;
 
         if item.is_some() {
             OrganisationRecord::delete(&tx, id)?;
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Prune, report::Entity::Organisation, id, "");
 
         tx.commit()?;
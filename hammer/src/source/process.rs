@@ -0,0 +1,242 @@
+//! This module covers the reference documents, such as processes, from an input point of view.
+//!
+//! Reference documents live under the `reference/` directory of the corpus. They have no `identifier`, instead they
+//! are identified by their path within `reference/` so nested directories map to nested sections.
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::path::{Component, Path};
+
+use super::parse_frontmatter;
+use crate::cache::{Cache, ProcessRecord, SearchRecord};
+use crate::checksum::{Checksum, Digest, Hasher};
+use crate::markdown;
+use crate::report;
+use crate::resource::Resource;
+
+/// The directory of the corpus holding the reference documents.
+pub const DIRECTORY: &str = "reference";
+
+pub type ProcessId = String;
+
+#[derive(Debug, Clone)]
+pub struct Process {
+    pub metadata: Metadata,
+    pub content: String,
+}
+
+impl Process {
+    /// Reads a process from the given source text, identified by the path of its source file.
+    pub fn from_source(path: &Path, blob: &str) -> Result<Process> {
+        let (mut metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
+
+        metadata.id = path_id(path);
+
+        Ok(Process {
+            metadata,
+            content: content.into(),
+        })
+    }
+
+    pub fn id(&self) -> &ProcessId {
+        &self.metadata.id
+    }
+
+    pub fn checksum(&self) -> Checksum {
+        self.into()
+    }
+}
+
+impl Digest for Process {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.metadata.digest(hasher);
+        self.content.digest(hasher);
+    }
+}
+
+impl From<&Process> for Checksum {
+    fn from(resource: &Process) -> Checksum {
+        let mut hasher = Hasher::new();
+        resource.digest(&mut hasher);
+
+        hasher.finalize()
+    }
+}
+
+/// The identifier for the source file at the given path, e.g. `processes/endorsement-process` for
+/// `corpus/reference/processes/endorsement_process.md`.
+///
+/// Underscores become dashes as they do for any other published path.
+pub fn path_id(path: &Path) -> ProcessId {
+    let stem = path.with_extension("");
+    let components: Vec<String> = stem
+        .components()
+        .filter_map(|component| match component {
+            Component::Normal(name) => Some(name.to_string_lossy().replace('_', "-")),
+            _ => None,
+        })
+        .collect();
+    let start = components
+        .iter()
+        .rposition(|name| name == DIRECTORY)
+        .map_or(components.len().saturating_sub(1), |position| position + 1);
+
+    components[start..].join("/")
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Metadata {
+    /// The path of the process within `reference/`.
+    #[serde(skip)]
+    pub id: ProcessId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+impl Digest for Metadata {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.id.digest(hasher);
+        self.description.digest(hasher);
+    }
+}
+
+impl Resource<Process> for Cache {
+    fn get(&mut self, id: &str) -> Result<Option<Process>> {
+        let tx = self.conn.transaction()?;
+        let mut result = None;
+
+        if let Some(record) = ProcessRecord::select(&tx, id)? {
+            let metadata = Metadata {
+                id: record.id,
+                description: record.description,
+            };
+
+            result = Some(Process {
+                metadata,
+                content: record.content,
+            });
+        }
+
+        self.report
+            .log(report::Action::Get, report::Entity::Process, id, "");
+
+        tx.commit()?;
+
+        Ok(result)
+    }
+
+    fn add(&mut self, resource: &Process) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        let checksum = resource.checksum().to_string();
+
+        if let Some(record) = ProcessRecord::select(&tx, resource.id())? {
+            if record.checksum != checksum {
+                ProcessRecord::delete(&tx, resource.id())?;
+                ProcessRecord::insert(&tx, &resource.into())?;
+            }
+        } else {
+            ProcessRecord::insert(&tx, &resource.into())?;
+        }
+
+        SearchRecord::insert(&tx, &resource.into())?;
+        Cache::insert_trailmark(&tx, &checksum, "process", &self.timestamp)?;
+
+        self.report.log(
+            report::Action::Add,
+            report::Entity::Process,
+            resource.id(),
+            "",
+        );
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn drop(&mut self, id: &str) -> Result<Option<Process>> {
+        let resource = self.get(id)?;
+        let tx = self.conn.transaction()?;
+
+        if resource.is_some() {
+            ProcessRecord::delete(&tx, id)?;
+            SearchRecord::delete(&tx, "process", id)?;
+        }
+
+        self.report
+            .log(report::Action::Prune, report::Entity::Process, id, "");
+
+        tx.commit()?;
+
+        Ok(resource)
+    }
+}
+
+impl From<&Process> for ProcessRecord {
+    fn from(resource: &Process) -> Self {
+        ProcessRecord {
+            id: resource.id().clone(),
+            checksum: resource.checksum().to_string(),
+            description: resource.metadata.description.clone(),
+            content: resource.content.clone(),
+        }
+    }
+}
+
+impl From<&Process> for SearchRecord {
+    fn from(resource: &Process) -> Self {
+        SearchRecord {
+            resource_type: "process".into(),
+            resource_id: resource.id().clone(),
+            title: markdown::take_title(&resource.content)
+                .unwrap_or_else(|_| resource.id().clone()),
+            content: resource.content.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+
+    static PROCESS: &str = r#"---
+type: process
+description: The stages a standard goes through.
+---
+# Endorsement process
+
+The **endorsement process** represents the stages a standard needs to go through.
+"#;
+
+    #[test]
+    fn identify_by_path() {
+        assert_eq!(
+            path_id(Path::new(
+                "corpus/reference/processes/endorsement_process.md"
+            )),
+            "processes/endorsement-process"
+        );
+        assert_eq!(path_id(Path::new("reference/glossary.md")), "glossary");
+        assert_eq!(path_id(Path::new("elsewhere/glossary.md")), "glossary");
+    }
+
+    #[test]
+    fn gad() -> Result<()> {
+        let original = Process::from_source(
+            Path::new("corpus/reference/processes/endorsement_process.md"),
+            PROCESS,
+        )?;
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&original)?;
+        let cached: Process = cache.get(original.id())?.expect("process doesn't exist");
+
+        assert_eq!(&original.checksum(), &cached.checksum());
+
+        let _: Option<Process> = cache.drop(original.id())?;
+        let void: Option<Process> = cache.get(original.id())?;
+
+        assert!(void.is_none());
+
+        Ok(())
+    }
+}
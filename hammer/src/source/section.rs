@@ -3,7 +3,7 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-use super::split_content;
+use super::parse_frontmatter;
 use crate::cache::records::*;
 use crate::cache::Cache;
 use crate::checksum::{Checksum, Digest, Hasher};
@@ -46,8 +46,7 @@ impl FromStr for Section {
     type Err = anyhow::Error;
 
     fn from_str(blob: &str) -> Result<Self, Self::Err> {
-        let (frontmatter, content) = split_content(blob)?;
-        let metadata = serde_yaml::from_str(frontmatter)?;
+        let (metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
 
         Ok(Self {
             metadata,
@@ -89,8 +88,7 @@ impl Resource<Section> for Cache {
             result = Some(resource);
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Section, id, "");
 
         tx.commit()?;
@@ -113,7 +111,7 @@ impl Resource<Section> for Cache {
 
         Cache::insert_trailmark(&tx, &checksum, "section", &self.timestamp)?;
 
-        &self.report.log(
+        self.report.log(
             report::Action::Add,
             report::Entity::Section,
             resource.id(),
@@ -126,15 +124,14 @@ impl Resource<Section> for Cache {
     }
 
     fn drop(&mut self, id: &str) -> Result<Option<Section>> {
-        let resource = self.get(&id)?;
+        let resource = self.get(id)?;
         let tx = self.conn.transaction()?;
 
         if resource.is_some() {
             SectionRecord::delete(&tx, id)?;
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Prune, report::Entity::Section, id, "");
 
         tx.commit()?;
@@ -176,7 +173,7 @@ Lorem ipsum"#;
 
         cache.add(&original)?;
 
-        let cached: Section = cache.get(&original.id())?.expect("section doesn't exist");
+        let cached: Section = cache.get(original.id())?.expect("section doesn't exist");
 
         assert_eq!(&original.checksum(), &cached.checksum());
 
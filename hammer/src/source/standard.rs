@@ -3,11 +3,13 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-use super::endorsement::EndorsementState;
-use super::{split_content, LicenceId, OrganisationId, StandardId, TopicId, Url};
+use super::endorsement::{validate_history, EndorsementState, EndorsementTransition};
+use super::{
+    parse_frontmatter, Date, LicenceId, OrganisationId, StandardId, SubjectId, TopicId, Url,
+};
 use crate::cache::records::*;
 use crate::cache::{Cache, Transaction};
-use crate::checksum::{Checksum, Digest, Hasher};
+use crate::checksum::{Checksum, Digest, Hasher, Tag};
 use crate::report;
 use crate::resource::Resource;
 
@@ -47,8 +49,9 @@ impl FromStr for Standard {
     type Err = anyhow::Error;
 
     fn from_str(blob: &str) -> Result<Self, Self::Err> {
-        let (frontmatter, content) = split_content(blob)?;
-        let metadata = serde_yaml::from_str(frontmatter)?;
+        let (metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
+
+        validate_history(&metadata.endorsement_history, &metadata.endorsement_state)?;
 
         Ok(Self {
             metadata,
@@ -69,8 +72,12 @@ pub struct Metadata {
     pub acronym: Option<String>,
     /// The topic used to classify the standard.
     pub topic: TopicId,
-    // /// The list of subjects that refine the topic classification.
-    // subjects: Vec<SubjectId>,
+    /// The list of subjects that refine the topic classification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subjects: Vec<SubjectId>,
+    /// The identifier of the external standard this standard conforms to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conforms_to: Option<String>,
     /// The URL to the technical specification for the standard.
     pub specification: Url,
     /// The licence the standard (or specification) is licensed under.
@@ -79,9 +86,27 @@ pub struct Metadata {
     /// The organisation maintaining the specification.
     pub maintainer: OrganisationId,
     /// The list of related standards.
-    #[serde(default)]
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub related: Vec<StandardId>,
     pub endorsement_state: EndorsementState,
+    /// The ordered list of endorsement statuses the standard went through.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endorsement_history: Vec<EndorsementTransition>,
+    /// The stage of the standard in the Data Standards Authority process.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dsa_process: Option<DsaProcess>,
+    /// The public consultation held for the standard.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consultation: Option<Consultation>,
+    /// The list of external guidance about the standard.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub guidance: Vec<ExternalResource>,
+    /// The list of external use cases for the standard.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub use_cases: Vec<ExternalResource>,
+    /// The list of external case studies using the standard.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub case_studies: Vec<ExternalResource>,
 }
 
 impl Digest for Metadata {
@@ -90,12 +115,74 @@ impl Digest for Metadata {
         self.name.digest(hasher);
         self.acronym.digest(hasher);
         self.topic.digest(hasher);
-        // self.subjects.digest(hasher);
+        self.subjects.digest(hasher);
+        self.conforms_to.digest(hasher);
         self.specification.digest(hasher);
         self.licence.digest(hasher);
         self.maintainer.digest(hasher);
         self.related.digest(hasher);
         self.endorsement_state.digest(hasher);
+        self.endorsement_history.digest(hasher);
+        self.dsa_process.digest(hasher);
+        self.consultation.digest(hasher);
+        self.guidance.digest(hasher);
+        self.use_cases.digest(hasher);
+        self.case_studies.digest(hasher);
+    }
+}
+
+/// The progress of a standard through the Data Standards Authority process.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DsaProcess {
+    /// The current stage, e.g. `monitor`.
+    pub stage: String,
+    pub start_date: Date,
+    pub update_date: Date,
+}
+
+impl Digest for DsaProcess {
+    fn digest(&self, hasher: &mut Hasher) {
+        hasher.update(&Tag::Dict.to_bytes());
+        self.stage.digest(hasher);
+        self.start_date.digest(hasher);
+        self.update_date.digest(hasher);
+    }
+}
+
+/// A public consultation about a standard.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Consultation {
+    pub title: String,
+    pub url: Url,
+    pub start_date: Date,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<Date>,
+}
+
+impl Digest for Consultation {
+    fn digest(&self, hasher: &mut Hasher) {
+        hasher.update(&Tag::Dict.to_bytes());
+        self.title.digest(hasher);
+        self.url.digest(hasher);
+        self.start_date.digest(hasher);
+        self.end_date.digest(hasher);
+    }
+}
+
+/// A resource about a standard published outside of this site.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExternalResource {
+    pub title: String,
+    pub publisher: OrganisationId,
+    pub url: Url,
+}
+
+impl Digest for ExternalResource {
+    fn digest(&self, hasher: &mut Hasher) {
+        hasher.update(&Tag::Dict.to_bytes());
+        self.title.digest(hasher);
+        self.publisher.digest(hasher);
+        self.url.digest(hasher);
     }
 }
 
@@ -108,6 +195,10 @@ impl Resource<Standard> for Cache {
             let related_records = RelatedStandardRecord::select(&tx, standard_id)?;
             let endorsement_record = EndorsementStateRecord::select(&tx, standard_id)?
                 .expect("missing endorsement state. the cache is corrupted.");
+            let transition_records = EndorsementTransitionRecord::select(&tx, standard_id)?;
+            let subject_records = StandardSubjectRecord::select(&tx, standard_id)?;
+            let dsa_process_record = DsaProcessRecord::select(&tx, standard_id)?;
+            let consultation_record = ConsultationRecord::select(&tx, standard_id)?;
 
             let related = related_records
                 .iter()
@@ -119,16 +210,49 @@ impl Resource<Standard> for Cache {
                 review_date: endorsement_record.review_date,
                 end_date: endorsement_record.end_date,
             };
+            let mut endorsement_history = Vec::new();
+
+            for record in transition_records {
+                endorsement_history.push(EndorsementTransition {
+                    status: record.status.parse()?,
+                    date: record.date,
+                });
+            }
+
+            let subjects = subject_records
+                .into_iter()
+                .map(|record| record.subject_id)
+                .collect();
+            let dsa_process = dsa_process_record.map(|record| DsaProcess {
+                stage: record.stage,
+                start_date: record.start_date,
+                update_date: record.update_date,
+            });
+            let consultation = consultation_record.map(|record| Consultation {
+                title: record.title,
+                url: record.url,
+                start_date: record.start_date,
+                end_date: record.end_date,
+            });
+
             let metadata = Metadata {
                 id: standard_record.id,
                 name: standard_record.name,
                 acronym: standard_record.acronym,
                 topic: standard_record.topic_id,
+                subjects,
+                conforms_to: standard_record.conforms_to,
                 specification: standard_record.specification,
                 licence: standard_record.licence_id,
                 maintainer: standard_record.maintainer_id,
                 related,
                 endorsement_state,
+                endorsement_history,
+                dsa_process,
+                consultation,
+                guidance: select_external_resources(&tx, standard_id, "guidance")?,
+                use_cases: select_external_resources(&tx, standard_id, "use-case")?,
+                case_studies: select_external_resources(&tx, standard_id, "case-study")?,
             };
             let standard = Standard {
                 metadata,
@@ -138,7 +262,7 @@ impl Resource<Standard> for Cache {
             result = Some(standard);
         }
 
-        &self.report.log(
+        self.report.log(
             report::Action::Get,
             report::Entity::Standard,
             standard_id,
@@ -162,9 +286,10 @@ impl Resource<Standard> for Cache {
             create_standard(&tx, standard)?;
         }
 
+        SearchRecord::insert(&tx, &standard.into())?;
         Cache::insert_trailmark(&tx, &checksum, "standard", &self.timestamp)?;
 
-        &self.report.log(
+        self.report.log(
             report::Action::Add,
             report::Entity::Standard,
             standard.id(),
@@ -177,14 +302,15 @@ impl Resource<Standard> for Cache {
     }
 
     fn drop(&mut self, standard_id: &str) -> Result<Option<Standard>> {
-        let standard = self.get(&standard_id)?;
+        let standard = self.get(standard_id)?;
         let tx = self.conn.transaction()?;
 
         if standard.is_some() {
             StandardRecord::delete(&tx, standard_id)?;
+            SearchRecord::delete(&tx, "standard", standard_id)?;
         }
 
-        &self.report.log(
+        self.report.log(
             report::Action::Prune,
             report::Entity::Standard,
             standard_id,
@@ -208,6 +334,18 @@ impl From<&Standard> for StandardRecord {
             specification: standard.metadata.specification.clone(),
             licence_id: standard.metadata.licence.clone(),
             maintainer_id: standard.metadata.maintainer.clone(),
+            conforms_to: standard.metadata.conforms_to.clone(),
+            content: standard.content.clone(),
+        }
+    }
+}
+
+impl From<&Standard> for SearchRecord {
+    fn from(standard: &Standard) -> Self {
+        SearchRecord {
+            resource_type: "standard".into(),
+            resource_id: standard.id().clone(),
+            title: standard.metadata.name.clone(),
             content: standard.content.clone(),
         }
     }
@@ -227,11 +365,11 @@ impl From<&Standard> for EndorsementStateRecord {
 
 /// Helper to perform a strict create. Will fail if the standard exists.
 fn create_standard(tx: &Transaction, standard: &Standard) -> Result<()> {
-    StandardRecord::insert(&tx, &standard.into())?;
+    StandardRecord::insert(tx, &standard.into())?;
 
     for related in &standard.metadata.related {
         RelatedStandardRecord::insert(
-            &tx,
+            tx,
             &RelatedStandardRecord {
                 standard_id: standard.id().clone(),
                 related_standard_id: related.clone(),
@@ -239,15 +377,112 @@ fn create_standard(tx: &Transaction, standard: &Standard) -> Result<()> {
         )?;
     }
 
-    EndorsementStateRecord::insert(&tx, &standard.into())?;
+    EndorsementStateRecord::insert(tx, &standard.into())?;
+
+    for (ordinal, transition) in standard.metadata.endorsement_history.iter().enumerate() {
+        EndorsementTransitionRecord::insert(
+            tx,
+            &EndorsementTransitionRecord {
+                standard_id: standard.id().clone(),
+                ordinal: ordinal as u32,
+                status: transition.status.to_string(),
+                date: transition.date.clone(),
+            },
+        )?;
+    }
+
+    for (ordinal, subject) in standard.metadata.subjects.iter().enumerate() {
+        StandardSubjectRecord::insert(
+            tx,
+            &StandardSubjectRecord {
+                standard_id: standard.id().clone(),
+                ordinal: ordinal as u32,
+                subject_id: subject.clone(),
+            },
+        )?;
+    }
+
+    if let Some(dsa_process) = &standard.metadata.dsa_process {
+        DsaProcessRecord::insert(
+            tx,
+            &DsaProcessRecord {
+                standard_id: standard.id().clone(),
+                stage: dsa_process.stage.clone(),
+                start_date: dsa_process.start_date.clone(),
+                update_date: dsa_process.update_date.clone(),
+            },
+        )?;
+    }
+
+    if let Some(consultation) = &standard.metadata.consultation {
+        ConsultationRecord::insert(
+            tx,
+            &ConsultationRecord {
+                standard_id: standard.id().clone(),
+                title: consultation.title.clone(),
+                url: consultation.url.clone(),
+                start_date: consultation.start_date.clone(),
+                end_date: consultation.end_date.clone(),
+            },
+        )?;
+    }
+
+    insert_external_resources(tx, standard.id(), "guidance", &standard.metadata.guidance)?;
+    insert_external_resources(tx, standard.id(), "use-case", &standard.metadata.use_cases)?;
+    insert_external_resources(
+        tx,
+        standard.id(),
+        "case-study",
+        &standard.metadata.case_studies,
+    )?;
 
     Ok(())
 }
 
+fn insert_external_resources(
+    tx: &Transaction,
+    standard_id: &str,
+    resource_type: &str,
+    resources: &[ExternalResource],
+) -> Result<()> {
+    for (ordinal, resource) in resources.iter().enumerate() {
+        ExternalResourceRecord::insert(
+            tx,
+            &ExternalResourceRecord {
+                standard_id: standard_id.to_string(),
+                resource_type: resource_type.to_string(),
+                ordinal: ordinal as u32,
+                title: resource.title.clone(),
+                publisher_id: resource.publisher.clone(),
+                url: resource.url.clone(),
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+fn select_external_resources(
+    tx: &Transaction,
+    standard_id: &str,
+    resource_type: &str,
+) -> Result<Vec<ExternalResource>> {
+    let records = ExternalResourceRecord::select(tx, standard_id, resource_type)?;
+
+    Ok(records
+        .into_iter()
+        .map(|record| ExternalResource {
+            title: record.title,
+            publisher: record.publisher_id,
+            url: record.url,
+        })
+        .collect())
+}
+
 /// Helper to perform replace an existing standard. This relies on the `ON DELETE CASCADE`.
 fn update_standard(tx: &Transaction, standard: &Standard) -> Result<()> {
-    StandardRecord::delete(&tx, &standard.id())?;
-    create_standard(&tx, standard)?;
+    StandardRecord::delete(tx, standard.id())?;
+    create_standard(tx, standard)?;
 
     Ok(())
 }
@@ -258,7 +493,7 @@ mod tests {
     use anyhow::Result;
     use std::str::FromStr;
 
-    static VAPOUR_STANDARD: &'static str = r#"---
+    static VAPOUR_STANDARD: &str = r#"---
 type: standard
 identifier: vapour
 name: Vapour
@@ -278,7 +513,7 @@ related:
 # Vapour
 
 This standard will give you no overhead."#;
-    static STEAM_STANDARD: &'static str = r#"---
+    static STEAM_STANDARD: &str = r#"---
 type: standard
 identifier: steam
 name: Steam
@@ -306,7 +541,7 @@ This standard will give you warmth."#;
         assert_eq!(standard.id(), "vapour");
         assert_eq!(
             &standard.checksum().to_string(),
-            "feb2a425f367add826789547e59390d05a9c8aade19a3d619760d57294629faf"
+            "4dbae5ae509635be4e4fe15c4d61a9159fc0bdd1c3e1159dc8ae923c86af7c90"
         );
         assert_eq!(
             &standard.content,
@@ -325,7 +560,7 @@ This standard will give you warmth."#;
 
         assert_eq!(
             &standard.checksum().to_string(),
-            "feb2a425f367add826789547e59390d05a9c8aade19a3d619760d57294629faf"
+            "4dbae5ae509635be4e4fe15c4d61a9159fc0bdd1c3e1159dc8ae923c86af7c90"
         );
 
         Ok(())
@@ -342,7 +577,7 @@ This standard will give you warmth."#;
 
         assert_eq!(
             &vapour.checksum().to_string(),
-            "feb2a425f367add826789547e59390d05a9c8aade19a3d619760d57294629faf"
+            "4dbae5ae509635be4e4fe15c4d61a9159fc0bdd1c3e1159dc8ae923c86af7c90"
         );
 
         Ok(())
@@ -358,7 +593,7 @@ This standard will give you warmth."#;
 
         assert_eq!(
             &vapour.checksum().to_string(),
-            "feb2a425f367add826789547e59390d05a9c8aade19a3d619760d57294629faf"
+            "4dbae5ae509635be4e4fe15c4d61a9159fc0bdd1c3e1159dc8ae923c86af7c90"
         );
 
         Ok(())
@@ -400,18 +635,146 @@ This standard will give you no overhead."#;
         Ok(())
     }
 
+    #[test]
+    fn endorsement_history() -> Result<()> {
+        let raw = r#"---
+type: standard
+identifier: vapour
+name: Vapour
+topic: exchange
+specification: https://spec.vapour.org/
+maintainer: data-standards-authority
+endorsement_state:
+    status: endorsed
+    start_date: 2021-06-01
+    review_date: 2022-06-01
+endorsement_history:
+    - status: identified
+      date: 2021-01-01
+    - status: proposed
+      date: 2021-03-01
+    - status: endorsed
+      date: 2021-06-01
+---
+# Vapour"#;
+        let original = Standard::from_str(raw)?;
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&original)?;
+        let cached: Standard = cache.get(original.id())?.expect("standard doesn't exist");
+
+        assert_eq!(cached.metadata.endorsement_history.len(), 3);
+        assert_eq!(&original.checksum(), &cached.checksum());
+
+        Ok(())
+    }
+
+    #[test]
+    fn rich_frontmatter() -> Result<()> {
+        let raw = r#"---
+type: standard
+identifier: uprn
+name: Unique Property Reference Number
+topic: geospatial
+subjects:
+  - identification
+  - geospatial
+conforms_to: bs7666-2
+specification: https://www.geoplace.co.uk/addresses/uprn
+maintainer: ordnance-survey
+endorsement_state:
+  status: endorsed
+  start_date: 2020-12-16
+  review_date: 2020-12-16
+dsa_process:
+  stage: monitor
+  start_date: 2020-12-16
+  update_date: 2020-12-16
+consultation:
+  title: Identifying property and street information
+  url: https://github.com/alphagov/open-standards/issues/68
+  start_date: 2019-01-24
+  end_date: 2020-07-14
+guidance:
+  - title: Open Identifiers Policy
+    publisher: ordnance-survey
+    url: https://www.ordnancesurvey.co.uk/open-id-policy
+use_cases:
+  - title: The power of the UPRN
+    publisher: geoplace
+    url: https://www.geoplace.co.uk/the-uprn
+case_studies:
+  - title: What can food hygiene tell us about fire safety?
+    publisher: geoplace
+    url: https://www.geoplace.co.uk/food-hygiene
+  - title: Connecting fire and flood risk with place through UPRNs
+    publisher: ukauthority
+    url: https://www.ukauthority.com/uprns
+---
+# UPRN"#;
+        let original = Standard::from_str(raw)?;
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&original)?;
+        let cached: Standard = cache.get(original.id())?.expect("standard doesn't exist");
+
+        assert_eq!(
+            cached.metadata.subjects,
+            vec!["identification", "geospatial"]
+        );
+        assert_eq!(cached.metadata.conforms_to.as_deref(), Some("bs7666-2"));
+        assert_eq!(
+            cached
+                .metadata
+                .dsa_process
+                .as_ref()
+                .map(|x| x.stage.as_str()),
+            Some("monitor")
+        );
+        assert_eq!(cached.metadata.case_studies.len(), 2);
+        assert_eq!(cached.metadata.case_studies[1].publisher, "ukauthority");
+        assert_eq!(&original.checksum(), &cached.checksum());
+
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_endorsement_history() {
+        let raw = r#"---
+type: standard
+identifier: vapour
+name: Vapour
+topic: exchange
+specification: https://spec.vapour.org/
+maintainer: data-standards-authority
+endorsement_state:
+    status: endorsed
+    start_date: 2021-06-01
+    review_date: 2022-06-01
+endorsement_history:
+    - status: identified
+      date: 2021-01-01
+    - status: endorsed
+      date: 2021-06-01
+---
+# Vapour"#;
+        let actual = Standard::from_str(raw);
+
+        assert!(actual.is_err(), "error when skipping the proposed status");
+    }
+
     #[test]
     fn gad_standard() -> Result<()> {
         let original = Standard::from_str(VAPOUR_STANDARD)?;
         let mut cache = Cache::connect(":memory:")?;
 
         cache.add(&original)?;
-        let cached: Standard = cache.get(&original.id())?.expect("standard doesn't exist");
+        let cached: Standard = cache.get(original.id())?.expect("standard doesn't exist");
 
         assert_eq!(&original.checksum(), &cached.checksum());
 
-        let _: Option<Standard> = cache.drop(&original.id())?;
-        let void: Option<Standard> = cache.get(&original.id())?;
+        let _: Option<Standard> = cache.drop(original.id())?;
+        let void: Option<Standard> = cache.get(original.id())?;
 
         assert!(void.is_none());
 
@@ -0,0 +1,285 @@
+//! This module covers stories, i.e. case studies and use cases, from an input point of view.
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use super::{parse_frontmatter, Date, OrganisationId, StandardId, Url};
+use crate::cache::{Cache, StoryRecord, StoryStandardRecord, Transaction};
+use crate::checksum::{Checksum, Digest, Hasher};
+use crate::report;
+use crate::resource::{CaseStudyKind, Resource, StoryKind, UseCaseKind};
+
+pub type StoryId = String;
+
+pub type CaseStudy = Story<CaseStudyKind>;
+pub type UseCase = Story<UseCaseKind>;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(bound = "")]
+pub struct Story<K: StoryKind> {
+    pub metadata: Metadata,
+    pub content: String,
+    #[serde(skip)]
+    kind: PhantomData<K>,
+}
+
+impl<K: StoryKind> Story<K> {
+    pub fn new(metadata: Metadata, content: String) -> Self {
+        Self {
+            metadata,
+            content,
+            kind: PhantomData,
+        }
+    }
+
+    pub fn id(&self) -> &StoryId {
+        &self.metadata.id
+    }
+
+    pub fn checksum(&self) -> Checksum {
+        self.into()
+    }
+}
+
+impl<K: StoryKind> Digest for Story<K> {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.metadata.digest(hasher);
+        self.content.digest(hasher);
+    }
+}
+
+impl<K: StoryKind> From<&Story<K>> for Checksum {
+    fn from(resource: &Story<K>) -> Checksum {
+        let mut hasher = Hasher::new();
+        resource.digest(&mut hasher);
+
+        hasher.finalize()
+    }
+}
+
+impl<K: StoryKind> FromStr for Story<K> {
+    type Err = anyhow::Error;
+
+    fn from_str(blob: &str) -> Result<Self, Self::Err> {
+        let (metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
+
+        Ok(Self::new(metadata, content.into()))
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Metadata {
+    /// A local unique identifier for the story.
+    #[serde(rename = "identifier")]
+    pub id: StoryId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// The organisation publishing the story.
+    pub publisher: OrganisationId,
+    pub creation_date: Date,
+    pub update_date: Date,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication_date: Option<Date>,
+    /// The list of standards the story refers to.
+    #[serde(default)]
+    pub standards: Vec<StandardId>,
+    /// The URL to the original publication, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<Url>,
+}
+
+impl Digest for Metadata {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.id.digest(hasher);
+        self.description.digest(hasher);
+        self.publisher.digest(hasher);
+        self.creation_date.digest(hasher);
+        self.update_date.digest(hasher);
+        self.publication_date.digest(hasher);
+        self.standards.digest(hasher);
+        self.canonical_url.digest(hasher);
+    }
+}
+
+impl<K: StoryKind> Resource<Story<K>> for Cache {
+    fn get(&mut self, id: &str) -> Result<Option<Story<K>>> {
+        let tx = self.conn.transaction()?;
+        let mut result = None;
+
+        if let Some(record) = StoryRecord::select(&tx, K::TABLE, id)? {
+            let standards = StoryStandardRecord::select(&tx, K::TABLE, id)?
+                .into_iter()
+                .map(|record| record.standard_id)
+                .collect();
+            let metadata = Metadata {
+                id: record.id,
+                description: record.description,
+                publisher: record.publisher_id,
+                creation_date: record.creation_date,
+                update_date: record.update_date,
+                publication_date: record.publication_date,
+                standards,
+                canonical_url: record.canonical_url,
+            };
+
+            result = Some(Story::new(metadata, record.content));
+        }
+
+        self.report.log(report::Action::Get, K::ENTITY, id, "");
+
+        tx.commit()?;
+
+        Ok(result)
+    }
+
+    fn add(&mut self, resource: &Story<K>) -> Result<()> {
+        let tx = self.conn.transaction()?;
+        let checksum = resource.checksum().to_string();
+
+        if let Some(record) = StoryRecord::select(&tx, K::TABLE, resource.id())? {
+            if record.checksum != checksum {
+                StoryRecord::delete(&tx, K::TABLE, resource.id())?;
+                create(&tx, resource)?;
+            }
+        } else {
+            create(&tx, resource)?;
+        }
+
+        Cache::insert_trailmark(
+            &tx,
+            &checksum,
+            &K::RESOURCE_TYPE.to_string(),
+            &self.timestamp,
+        )?;
+
+        self.report
+            .log(report::Action::Add, K::ENTITY, resource.id(), "");
+
+        tx.commit()?;
+
+        Ok(())
+    }
+
+    fn drop(&mut self, id: &str) -> Result<Option<Story<K>>> {
+        let resource = self.get(id)?;
+        let tx = self.conn.transaction()?;
+
+        if resource.is_some() {
+            StoryRecord::delete(&tx, K::TABLE, id)?;
+        }
+
+        self.report.log(report::Action::Prune, K::ENTITY, id, "");
+
+        tx.commit()?;
+
+        Ok(resource)
+    }
+}
+
+/// Helper to perform a strict create. Will fail if the story exists.
+fn create<K: StoryKind>(tx: &Transaction, resource: &Story<K>) -> Result<()> {
+    StoryRecord::insert(tx, K::TABLE, &resource.into())?;
+
+    for standard_id in &resource.metadata.standards {
+        StoryStandardRecord::insert(
+            tx,
+            K::TABLE,
+            &StoryStandardRecord {
+                story_id: resource.id().clone(),
+                standard_id: standard_id.clone(),
+            },
+        )?;
+    }
+
+    Ok(())
+}
+
+impl<K: StoryKind> From<&Story<K>> for StoryRecord {
+    fn from(resource: &Story<K>) -> Self {
+        StoryRecord {
+            id: resource.id().clone(),
+            checksum: resource.checksum().to_string(),
+            description: resource.metadata.description.clone(),
+            publisher_id: resource.metadata.publisher.clone(),
+            creation_date: resource.metadata.creation_date.clone(),
+            update_date: resource.metadata.update_date.clone(),
+            publication_date: resource.metadata.publication_date.clone(),
+            canonical_url: resource.metadata.canonical_url.clone(),
+            content: resource.content.clone(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anyhow::Result;
+    use std::str::FromStr;
+
+    static CASE_STUDY: &str = r#"---
+type: case-study
+identifier: fire-safety-and-food-hygiene
+publisher: geoplace
+creation_date: 2021-04-01
+update_date: 2021-05-14
+standards:
+  - uprn
+  - usrn
+---
+# What can food hygiene tell us about fire safety?
+
+Linking food hygiene inspections to fire safety records through the UPRN.
+"#;
+
+    static USE_CASE: &str = r#"---
+type: use-case
+identifier: property-matching
+publisher: geoplace
+creation_date: 2021-04-01
+update_date: 2021-05-14
+standards:
+  - uprn
+---
+# Matching property records across services
+
+Services need to agree on which property a record refers to.
+"#;
+
+    #[test]
+    fn gad() -> Result<()> {
+        let original = CaseStudy::from_str(CASE_STUDY)?;
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&original)?;
+        let cached: CaseStudy = cache.get(original.id())?.expect("case study doesn't exist");
+
+        assert_eq!(&original.checksum(), &cached.checksum());
+        assert_eq!(cached.metadata.standards, vec!["uprn", "usrn"]);
+
+        let _: Option<CaseStudy> = cache.drop(original.id())?;
+        let void: Option<CaseStudy> = cache.get(original.id())?;
+
+        assert!(void.is_none());
+
+        Ok(())
+    }
+
+    #[test]
+    fn kinds_apart() -> Result<()> {
+        let case_study = CaseStudy::from_str(CASE_STUDY)?;
+        let use_case = UseCase::from_str(USE_CASE)?;
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&case_study)?;
+        cache.add(&use_case)?;
+
+        let cached: Option<UseCase> = cache.get(case_study.id())?;
+        assert!(cached.is_none());
+
+        let cached: UseCase = cache.get(use_case.id())?.expect("use case doesn't exist");
+        assert_eq!(&use_case.checksum(), &cached.checksum());
+
+        Ok(())
+    }
+}
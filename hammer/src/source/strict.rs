@@ -0,0 +1,196 @@
+//! This module finds the source properties that are not part of the model.
+//!
+//! Serde ignores unknown keys so a misspelt property such as `reveiw_date` would otherwise vanish without notice.
+use anyhow::{bail, Result};
+use serde::de::{Deserialize, Deserializer};
+use serde_ignored::Path as FieldPath;
+use std::error::Error;
+use std::path::Path;
+use std::str::FromStr;
+
+use super::{
+    dataset, guidance, parse_toml, process, resource_type, section, split_content, standard, story,
+    theme, topic, Format, Licence, Organisation,
+};
+use crate::resource::ResourceType;
+
+/// What to do when a source has properties that are not part of the model.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum UnknownFields {
+    #[default]
+    Ignore,
+    Warn,
+    Deny,
+}
+
+impl FromStr for UnknownFields {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "ignore" => Ok(UnknownFields::Ignore),
+            "warn" => Ok(UnknownFields::Warn),
+            "deny" => Ok(UnknownFields::Deny),
+            _ => bail!(
+                "'{}' is not a valid unknown fields mode. Expected ignore, warn or deny",
+                s
+            ),
+        }
+    }
+}
+
+/// Lists the properties of the given source blob that don't map to a field of its resource type.
+pub fn unknown_fields(path: &Path, blob: &str) -> Result<Vec<String>> {
+    // Other files, such as assets, might not even be text.
+    match path.extension() {
+        Some(ext) if ext == "md" => markdown_unknown_fields(blob),
+        Some(ext) if ext == "json" => {
+            let stem = path.file_stem().unwrap_or_default().to_string_lossy();
+
+            json_unknown_fields(&stem, blob)
+        }
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn markdown_unknown_fields(blob: &str) -> Result<Vec<String>> {
+    let (format, frontmatter, _) = split_content(blob)?;
+    let resource_type = resource_type(blob)?;
+    let fields = match format {
+        Format::Yaml => metadata_unknown_fields(
+            &resource_type,
+            serde_yaml::from_str::<serde_yaml::Value>(frontmatter)?,
+        )?,
+        Format::Toml => metadata_unknown_fields(&resource_type, parse_toml(frontmatter)?)?,
+    };
+
+    // The `type` property drives the dispatch so it is never part of the model.
+    Ok(fields
+        .into_iter()
+        .filter(|field| !field.eq_ignore_ascii_case("type"))
+        .collect())
+}
+
+fn metadata_unknown_fields<'de, D>(
+    resource_type: &ResourceType,
+    frontmatter: D,
+) -> Result<Vec<String>>
+where
+    D: Deserializer<'de>,
+    D::Error: Error + Send + Sync + 'static,
+{
+    match resource_type {
+        ResourceType::CaseStudy => ignored::<story::Metadata, _>(frontmatter),
+        ResourceType::Guidance => ignored::<guidance::Metadata, _>(frontmatter),
+        ResourceType::Process => ignored::<process::Metadata, _>(frontmatter),
+        ResourceType::Section => ignored::<section::Metadata, _>(frontmatter),
+        ResourceType::Standard => ignored::<standard::Metadata, _>(frontmatter),
+        ResourceType::Theme => ignored::<theme::Metadata, _>(frontmatter),
+        ResourceType::Topic => ignored::<topic::Metadata, _>(frontmatter),
+        ResourceType::UseCase => ignored::<story::Metadata, _>(frontmatter),
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn json_unknown_fields(stem: &str, blob: &str) -> Result<Vec<String>> {
+    let value: serde_json::Value = serde_json::from_str(blob)?;
+
+    match stem {
+        _ if stem.ends_with(".csv-metadata") => ignored::<dataset::Metadata, _>(value),
+        "licences" => ignored::<Vec<Licence>, _>(value),
+        "organisations" => ignored::<Vec<Organisation>, _>(value),
+        _ => Ok(Vec::new()),
+    }
+}
+
+fn ignored<'de, T, D>(deserializer: D) -> Result<Vec<String>>
+where
+    T: Deserialize<'de>,
+    D: Deserializer<'de>,
+    D::Error: Error + Send + Sync + 'static,
+{
+    let mut fields = Vec::new();
+    let _: T = serde_ignored::deserialize(deserializer, |path| fields.push(render(&path)))?;
+
+    Ok(fields)
+}
+
+/// Renders the path to a property as `parent.child` or `list[0].child`.
+fn render(path: &FieldPath) -> String {
+    match path {
+        FieldPath::Root => String::new(),
+        FieldPath::Seq { parent, index } => format!("{}[{}]", render(parent), index),
+        FieldPath::Map { parent, key } => match render(parent) {
+            parent if parent.is_empty() => key.clone(),
+            parent => format!("{}.{}", parent, key),
+        },
+        FieldPath::Some { parent }
+        | FieldPath::NewtypeStruct { parent }
+        | FieldPath::NewtypeVariant { parent } => render(parent),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn misspelt_standard_fields() -> Result<()> {
+        let raw = r#"---
+type: standard
+identifier: vapour
+name: Vapour
+topic: exchange
+specification: https://spec.vapour.org/
+maintainer: data-standards-authority
+endorsement_state:
+    status: identified
+    start_date: 2021-06-01
+    review_date: 2021-06-01
+    reveiw_date: 2021-06-01
+consultation:
+    title: Vapour
+    url: https://example.org/vapour
+    start_date: 2021-01-01
+guidance:
+    - title: Vapour in practice
+      publisher: data-standards-authority
+      url: https://example.org/vapour/practice
+      summary: How to
+relatd:
+    - steam
+---
+# Vapour"#;
+        let actual = markdown_unknown_fields(raw)?;
+
+        assert_eq!(
+            actual,
+            vec![
+                "endorsement_state.reveiw_date",
+                "guidance[0].summary",
+                "relatd"
+            ]
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn misspelt_toml_fields() -> Result<()> {
+        let raw = "+++\nType = \"Topic\"\nidentifier = \"exchange\"\nname = \"Exchange\"\ntheme = \"other\"\nordinal = 1\ndescriptoin = \"Moving data\"\n+++\n# Exchange";
+
+        assert_eq!(markdown_unknown_fields(raw)?, vec!["descriptoin"]);
+
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_licence_fields() -> Result<()> {
+        let raw = r#"[{"id": "ogl", "name": "OGL", "url": "https://ogl.gov.uk", "version": 3}]"#;
+        let actual = json_unknown_fields("licences", raw)?;
+
+        assert_eq!(actual, vec!["[0].version"]);
+
+        Ok(())
+    }
+}
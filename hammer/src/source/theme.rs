@@ -3,8 +3,8 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-use super::{split_content, ThemeId};
-use crate::cache::{Cache, ThemeRecord};
+use super::{parse_frontmatter, ThemeId};
+use crate::cache::{Cache, SearchRecord, ThemeRecord};
 use crate::checksum::{Checksum, Digest, Hasher};
 use crate::report;
 use crate::resource::Resource;
@@ -45,8 +45,7 @@ impl FromStr for Theme {
     type Err = anyhow::Error;
 
     fn from_str(blob: &str) -> Result<Self, Self::Err> {
-        let (frontmatter, content) = split_content(blob)?;
-        let metadata = serde_yaml::from_str(frontmatter)?;
+        let (metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
 
         Ok(Self {
             metadata,
@@ -88,8 +87,7 @@ impl Resource<Theme> for Cache {
             result = Some(resource)
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Theme, id, "");
 
         tx.commit()?;
@@ -110,9 +108,10 @@ impl Resource<Theme> for Cache {
             ThemeRecord::insert(&tx, &resource.into())?;
         }
 
+        SearchRecord::insert(&tx, &resource.into())?;
         Cache::insert_trailmark(&tx, &checksum, "theme", &self.timestamp)?;
 
-        &self.report.log(
+        self.report.log(
             report::Action::Add,
             report::Entity::Theme,
             resource.id(),
@@ -130,10 +129,10 @@ impl Resource<Theme> for Cache {
 
         if resource.is_some() {
             ThemeRecord::delete(&tx, id)?;
+            SearchRecord::delete(&tx, "theme", id)?;
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Prune, report::Entity::Theme, id, "");
 
         tx.commit()?;
@@ -153,3 +152,14 @@ impl From<&Theme> for ThemeRecord {
         }
     }
 }
+
+impl From<&Theme> for SearchRecord {
+    fn from(resource: &Theme) -> Self {
+        SearchRecord {
+            resource_type: "theme".into(),
+            resource_id: resource.id().clone(),
+            title: resource.metadata.name.clone(),
+            content: resource.content.clone(),
+        }
+    }
+}
@@ -3,8 +3,8 @@ use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::str::FromStr;
 
-use super::{split_content, TopicId};
-use crate::cache::{Cache, TopicRecord};
+use super::{parse_frontmatter, TopicId};
+use crate::cache::{Cache, SearchRecord, TopicRecord};
 use crate::checksum::{Checksum, Digest, Hasher};
 use crate::report;
 use crate::resource::Resource;
@@ -45,8 +45,7 @@ impl FromStr for Topic {
     type Err = anyhow::Error;
 
     fn from_str(blob: &str) -> Result<Self, Self::Err> {
-        let (frontmatter, content) = split_content(blob)?;
-        let metadata = serde_yaml::from_str(frontmatter)?;
+        let (metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
 
         Ok(Self {
             metadata,
@@ -91,8 +90,7 @@ impl Resource<Topic> for Cache {
             result = Some(topic)
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Topic, id, "");
 
         tx.commit()?;
@@ -113,9 +111,10 @@ impl Resource<Topic> for Cache {
             TopicRecord::insert(&tx, &resource.into())?;
         }
 
+        SearchRecord::insert(&tx, &resource.into())?;
         Cache::insert_trailmark(&tx, &checksum, "topic", &self.timestamp)?;
 
-        &self.report.log(
+        self.report.log(
             report::Action::Add,
             report::Entity::Topic,
             resource.id(),
@@ -133,10 +132,10 @@ impl Resource<Topic> for Cache {
 
         if resource.is_some() {
             TopicRecord::delete(&tx, id)?;
+            SearchRecord::delete(&tx, "topic", id)?;
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Prune, report::Entity::Topic, id, "");
 
         tx.commit()?;
@@ -158,6 +157,17 @@ impl From<&Topic> for TopicRecord {
     }
 }
 
+impl From<&Topic> for SearchRecord {
+    fn from(resource: &Topic) -> Self {
+        SearchRecord {
+            resource_type: "topic".into(),
+            resource_id: resource.id().clone(),
+            title: resource.metadata.name.clone(),
+            content: resource.content.clone(),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -179,12 +189,12 @@ Standards that focus on geospatial information. For example, Local Authority bou
 
         cache.add(&resource)?;
 
-        let cached: Topic = cache.get(&resource.id())?.expect("topic doesn't exist");
+        let cached: Topic = cache.get(resource.id())?.expect("topic doesn't exist");
 
         assert_eq!(&resource.checksum(), &cached.checksum());
 
-        let _: Option<Topic> = cache.drop(&resource.id())?;
-        let void: Option<Topic> = cache.get(&resource.id())?;
+        let _: Option<Topic> = cache.drop(resource.id())?;
+        let void: Option<Topic> = cache.get(resource.id())?;
 
         assert!(void.is_none());
 
@@ -0,0 +1,8 @@
+-- The source file each resource was read from.
+CREATE TABLE IF NOT EXISTS origin (
+  resource_type text NOT NULL,
+  resource_id   text NOT NULL,
+  path          text NOT NULL,
+
+  PRIMARY KEY (resource_type, resource_id)
+);
@@ -0,0 +1,10 @@
+-- The ordered list of endorsement statuses a standard went through.
+CREATE TABLE IF NOT EXISTS endorsement_transition (
+  standard_id text    NOT NULL,
+  ordinal     integer NOT NULL,
+  status      text    NOT NULL,
+  date        date    NOT NULL,
+
+  PRIMARY KEY (standard_id, ordinal),
+  FOREIGN KEY (standard_id) REFERENCES standard (id) ON DELETE CASCADE
+);
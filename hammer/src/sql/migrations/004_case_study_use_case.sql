@@ -0,0 +1,39 @@
+CREATE TABLE IF NOT EXISTS case_study_standard (
+  case_study_id text NOT NULL,
+  standard_id   text NOT NULL,
+
+  UNIQUE (case_study_id, standard_id),
+  FOREIGN KEY (case_study_id) REFERENCES case_study (id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS case_study (
+  id               text NOT NULL PRIMARY KEY,
+  checksum         text NOT NULL,
+  description      text,
+  publisher_id     text NOT NULL,
+  creation_date    date NOT NULL,
+  update_date      date NOT NULL,
+  publication_date date,
+  canonical_url    text,
+  content          text NOT NULL
+);
+
+CREATE TABLE IF NOT EXISTS use_case_standard (
+  use_case_id text NOT NULL,
+  standard_id text NOT NULL,
+
+  UNIQUE (use_case_id, standard_id),
+  FOREIGN KEY (use_case_id) REFERENCES use_case (id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS use_case (
+  id               text NOT NULL PRIMARY KEY,
+  checksum         text NOT NULL,
+  description      text,
+  publisher_id     text NOT NULL,
+  creation_date    date NOT NULL,
+  update_date      date NOT NULL,
+  publication_date date,
+  canonical_url    text,
+  content          text NOT NULL
+);
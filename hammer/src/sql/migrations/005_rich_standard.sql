@@ -0,0 +1,44 @@
+-- Appended last so `SELECT *` sees the same column order in fresh and migrated caches.
+ALTER TABLE standard ADD COLUMN conforms_to text;
+
+CREATE TABLE IF NOT EXISTS standard_subject (
+  standard_id text    NOT NULL,
+  ordinal     integer NOT NULL,
+  subject_id  text    NOT NULL,
+
+  PRIMARY KEY (standard_id, ordinal),
+  UNIQUE (standard_id, subject_id),
+  FOREIGN KEY (standard_id) REFERENCES standard (id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS dsa_process (
+  standard_id text NOT NULL PRIMARY KEY,
+  stage       text NOT NULL,
+  start_date  date NOT NULL,
+  update_date date NOT NULL,
+
+  FOREIGN KEY (standard_id) REFERENCES standard (id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS consultation (
+  standard_id text NOT NULL PRIMARY KEY,
+  title       text NOT NULL,
+  url         text NOT NULL,
+  start_date  date NOT NULL,
+  end_date    date,
+
+  FOREIGN KEY (standard_id) REFERENCES standard (id) ON DELETE CASCADE
+);
+
+-- Guidance, use cases and case studies about a standard published elsewhere.
+CREATE TABLE IF NOT EXISTS external_resource (
+  standard_id   text    NOT NULL,
+  resource_type text    NOT NULL,
+  ordinal       integer NOT NULL,
+  title         text    NOT NULL,
+  publisher_id  text    NOT NULL,
+  url           text    NOT NULL,
+
+  PRIMARY KEY (standard_id, resource_type, ordinal),
+  FOREIGN KEY (standard_id) REFERENCES standard (id) ON DELETE CASCADE
+);
@@ -0,0 +1,11 @@
+-- Full-text index over the body of standards, guidance, topics and themes.
+--
+-- Kept in sync by the resource implementations so an existing cache is indexed on its next build.
+CREATE VIRTUAL TABLE IF NOT EXISTS search_index USING fts5 (
+  resource_type UNINDEXED,
+  resource_id   UNINDEXED,
+  title,
+  content,
+
+  tokenize = 'porter unicode61'
+);
@@ -0,0 +1,33 @@
+CREATE TABLE IF NOT EXISTS dataset (
+  id          text NOT NULL PRIMARY KEY,
+  checksum    text NOT NULL,
+  name        text NOT NULL,
+  description text,
+  maintainer  text,
+  url         text NOT NULL,
+  -- Comma separated list of column names.
+  primary_key text
+);
+
+CREATE TABLE IF NOT EXISTS dataset_column (
+  dataset_id  text    NOT NULL,
+  ordinal     integer NOT NULL,
+  name        text    NOT NULL,
+  title       text    NOT NULL,
+  description text,
+  datatype    text    NOT NULL,
+  required    boolean NOT NULL,
+
+  UNIQUE (dataset_id, name),
+  FOREIGN KEY (dataset_id) REFERENCES dataset (id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS dataset_row (
+  dataset_id text    NOT NULL,
+  ordinal    integer NOT NULL,
+  -- JSON array with the cells of the row in column order.
+  cells      text    NOT NULL,
+
+  UNIQUE (dataset_id, ordinal),
+  FOREIGN KEY (dataset_id) REFERENCES dataset (id) ON DELETE CASCADE
+);
@@ -0,0 +1,8 @@
+-- Reference documents such as processes. The identifier is the path under `reference/`, e.g.
+-- `processes/endorsement-process`.
+CREATE TABLE IF NOT EXISTS process (
+  id          text NOT NULL PRIMARY KEY,
+  checksum    text NOT NULL,
+  description text,
+  content     text NOT NULL
+);
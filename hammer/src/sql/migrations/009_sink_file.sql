@@ -0,0 +1,7 @@
+-- The files written into each sink directory so a later run only removes files the workbench wrote.
+CREATE TABLE IF NOT EXISTS sink_file (
+  sink_dir text NOT NULL,
+  path     text NOT NULL,
+
+  PRIMARY KEY (sink_dir, path)
+);
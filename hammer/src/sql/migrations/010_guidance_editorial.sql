@@ -0,0 +1,3 @@
+-- The editorial fields of a guidance as a JSON object. Appended last so `SELECT *` sees the same column order in
+-- fresh and migrated caches.
+ALTER TABLE guidance ADD COLUMN editorial text NOT NULL DEFAULT '{}';
@@ -0,0 +1,2 @@
+-- The files written into each sink directory are now listed in a manifest within the sink itself.
+DROP TABLE IF EXISTS sink_file;
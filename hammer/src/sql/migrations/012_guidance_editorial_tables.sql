@@ -0,0 +1,53 @@
+-- The editorial fields of a guidance move from a JSON column into their own tables.
+CREATE TABLE IF NOT EXISTS guidance_editorial (
+  guidance_id  text NOT NULL PRIMARY KEY,
+  purpose      text,
+  target       text,
+  user_needs   text,
+  route        text,
+  out_of_scope text,
+
+  FOREIGN KEY (guidance_id) REFERENCES guidance (id) ON DELETE CASCADE
+);
+
+-- The list fields of a guidance: `author`, `reviewer`, `problem_statement` and `tag`.
+CREATE TABLE IF NOT EXISTS guidance_editorial_item (
+  guidance_id text    NOT NULL,
+  field       text    NOT NULL,
+  ordinal     integer NOT NULL,
+  value       text    NOT NULL,
+
+  PRIMARY KEY (guidance_id, field, ordinal),
+  FOREIGN KEY (guidance_id) REFERENCES guidance (id) ON DELETE CASCADE
+);
+
+INSERT INTO guidance_editorial
+SELECT
+  id,
+  json_extract(editorial, '$.purpose'),
+  json_extract(editorial, '$.target'),
+  json_extract(editorial, '$.user_needs'),
+  json_extract(editorial, '$.route'),
+  json_extract(editorial, '$.out_of_scope')
+FROM
+  guidance
+WHERE
+  json_extract(editorial, '$.purpose') IS NOT NULL
+  OR json_extract(editorial, '$.target') IS NOT NULL
+  OR json_extract(editorial, '$.user_needs') IS NOT NULL
+  OR json_extract(editorial, '$.route') IS NOT NULL
+  OR json_extract(editorial, '$.out_of_scope') IS NOT NULL;
+
+INSERT INTO guidance_editorial_item
+SELECT guidance.id, list.field, item.key, item.value
+FROM
+  guidance,
+  (
+    SELECT 'author' AS field, '$.authors' AS path
+    UNION ALL SELECT 'reviewer', '$.reviewers'
+    UNION ALL SELECT 'problem_statement', '$.problem_statements'
+    UNION ALL SELECT 'tag', '$.tags'
+  ) AS list,
+  json_each(guidance.editorial, list.path) AS item;
+
+ALTER TABLE guidance DROP COLUMN editorial;
@@ -0,0 +1,362 @@
+//! This module exports the standards and guidance as CSV tables for spreadsheet users.
+//!
+//! Each table comes with a CSVW descriptor, `{table}.csv-metadata.json`, in the same shape as the ones in
+//! `corpus/datasets` so the columns are documented and typed wherever the tables end up.
+use anyhow::Result;
+use serde::Serialize;
+use std::path::{Path, PathBuf};
+
+use crate::api::{self, Guidance, Standard};
+use crate::cache::Cache;
+use crate::sink::{self, Page, Summary};
+
+const MAINTAINER: &str =
+    "Data Standards Authority <data-standards-authority@digital.cabinet-office.gov.uk>";
+
+/// A row of `standards.csv`.
+#[derive(Debug, Clone, Serialize)]
+struct StandardRow {
+    id: String,
+    name: String,
+    acronym: Option<String>,
+    topic: Option<String>,
+    theme: Option<String>,
+    maintainer: String,
+    licence: Option<String>,
+    specification: String,
+    status: String,
+    start_date: String,
+    review_date: String,
+    end_date: Option<String>,
+}
+
+impl From<&Standard> for StandardRow {
+    fn from(standard: &Standard) -> Self {
+        let state = &standard.endorsement_state;
+
+        StandardRow {
+            id: standard.id.clone(),
+            name: standard.name.clone(),
+            acronym: standard.acronym.clone(),
+            topic: standard.topic.as_ref().map(|topic| topic.name.clone()),
+            theme: standard
+                .topic
+                .as_ref()
+                .map(|topic| topic.theme.name.clone()),
+            maintainer: standard.maintainer.name.clone(),
+            licence: standard
+                .licence
+                .as_ref()
+                .map(|licence| licence.name.clone()),
+            specification: standard.specification.clone(),
+            status: state.status.clone(),
+            start_date: state.start_date.clone(),
+            review_date: state.review_date.clone(),
+            end_date: state.end_date.clone(),
+        }
+    }
+}
+
+const STANDARD_COLUMNS: [Column; 12] = [
+    Column::required("id", "string", "The identifier of the standard"),
+    Column::required("name", "string", "The name of the standard"),
+    Column::optional("acronym", "string", "The acronym of the standard"),
+    Column::optional(
+        "topic",
+        "string",
+        "The name of the topic the standard is classified under",
+    ),
+    Column::optional("theme", "string", "The name of the theme of the topic"),
+    Column::required(
+        "maintainer",
+        "string",
+        "The name of the organisation that maintains the standard",
+    ),
+    Column::optional(
+        "licence",
+        "string",
+        "The name of the licence the standard is released under",
+    ),
+    Column::required(
+        "specification",
+        "schema:URL",
+        "The URL to the technical specification",
+    ),
+    Column::required(
+        "status",
+        "string",
+        "The endorsement status: identified, proposed, endorsed, retired, disavowed or superseded",
+    ),
+    Column::required(
+        "start_date",
+        "date",
+        "The date the endorsement status started",
+    ),
+    Column::required(
+        "review_date",
+        "date",
+        "The date the endorsement status is due to be reviewed",
+    ),
+    Column::optional("end_date", "date", "The date the endorsement status ended"),
+];
+
+/// A row of `guidance.csv`.
+#[derive(Debug, Clone, Serialize)]
+struct GuidanceRow {
+    id: String,
+    title: String,
+    status: String,
+    maintainer: String,
+    creation_date: String,
+    update_date: String,
+    publication_date: Option<String>,
+    canonical_url: Option<String>,
+}
+
+impl From<&Guidance> for GuidanceRow {
+    fn from(guidance: &Guidance) -> Self {
+        GuidanceRow {
+            id: guidance.id.clone(),
+            title: guidance.title.clone(),
+            status: guidance.status.clone(),
+            maintainer: guidance.maintainer.name.clone(),
+            creation_date: guidance.creation_date.clone(),
+            update_date: guidance.update_date.clone(),
+            publication_date: guidance.publication_date.clone(),
+            canonical_url: guidance.canonical_url.clone(),
+        }
+    }
+}
+
+const GUIDANCE_COLUMNS: [Column; 8] = [
+    Column::required("id", "string", "The identifier of the guidance"),
+    Column::required("title", "string", "The title of the guidance"),
+    Column::required(
+        "status",
+        "string",
+        "The publication status: draft, published or obsolete",
+    ),
+    Column::required(
+        "maintainer",
+        "string",
+        "The name of the organisation that maintains the guidance",
+    ),
+    Column::required("creation_date", "date", "The date the guidance was created"),
+    Column::required(
+        "update_date",
+        "date",
+        "The date the guidance was last updated",
+    ),
+    Column::optional(
+        "publication_date",
+        "date",
+        "The date the guidance was published",
+    ),
+    Column::optional(
+        "canonical_url",
+        "schema:URL",
+        "The URL to the publication in GOV.UK",
+    ),
+];
+
+/// A CSVW table descriptor.
+#[derive(Debug, Clone, Serialize)]
+struct Metadata {
+    #[serde(rename = "@context")]
+    context: &'static str,
+    url: String,
+    #[serde(rename = "schema:name")]
+    name: &'static str,
+    #[serde(rename = "schema:maintainer")]
+    maintainer: &'static str,
+    #[serde(rename = "schema:description")]
+    description: &'static str,
+    #[serde(rename = "tableSchema")]
+    table_schema: TableSchema,
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct TableSchema {
+    #[serde(rename = "primaryKey")]
+    primary_key: &'static str,
+    columns: &'static [Column],
+}
+
+#[derive(Debug, Clone, Serialize)]
+struct Column {
+    titles: &'static str,
+    name: &'static str,
+    #[serde(rename = "schema:description")]
+    description: &'static str,
+    datatype: &'static str,
+    #[serde(skip_serializing_if = "std::ops::Not::not")]
+    required: bool,
+}
+
+impl Column {
+    const fn required(
+        name: &'static str,
+        datatype: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Column {
+            titles: name,
+            name,
+            description,
+            datatype,
+            required: true,
+        }
+    }
+
+    const fn optional(
+        name: &'static str,
+        datatype: &'static str,
+        description: &'static str,
+    ) -> Self {
+        Column {
+            titles: name,
+            name,
+            description,
+            datatype,
+            required: false,
+        }
+    }
+}
+
+/// Writes the tables that differ from the ones in the sink and removes any file no longer rendered.
+pub fn write(sink_dir: &Path, cache: &mut Cache) -> Result<Summary> {
+    let pages = render(cache)?;
+
+    sink::write(sink_dir, &pages)
+}
+
+/// Renders `standards.csv` and `guidance.csv` with their CSVW descriptors.
+pub fn render(cache: &mut Cache) -> Result<Vec<Page>> {
+    let standards: Vec<StandardRow> = api::standards(cache)?.iter().map(Into::into).collect();
+    let guidance: Vec<GuidanceRow> = api::guidance(cache)?.iter().map(Into::into).collect();
+    let mut pages = table(
+        "standards",
+        "Data standards",
+        "List of data standards with their current endorsement state",
+        &STANDARD_COLUMNS,
+        &standards,
+    )?;
+
+    pages.extend(table(
+        "guidance",
+        "Data standards guidance",
+        "List of guidance about data standards",
+        &GUIDANCE_COLUMNS,
+        &guidance,
+    )?);
+
+    Ok(pages)
+}
+
+fn table<T: Serialize>(
+    table: &str,
+    name: &'static str,
+    description: &'static str,
+    columns: &'static [Column],
+    rows: &[T],
+) -> Result<Vec<Page>> {
+    let url = format!("{}.csv", table);
+    let mut writer = csv::Writer::from_writer(Vec::new());
+
+    // Headers come from the first row so an empty table needs them explicitly.
+    if rows.is_empty() {
+        writer.write_record(columns.iter().map(|column| column.name))?;
+    }
+
+    for row in rows {
+        writer.serialize(row)?;
+    }
+
+    let metadata = Metadata {
+        context: "http://www.w3.org/ns/csvw",
+        url: url.clone(),
+        name,
+        maintainer: MAINTAINER,
+        description,
+        table_schema: TableSchema {
+            primary_key: "id",
+            columns,
+        },
+    };
+    let mut metadata_content = serde_json::to_string_pretty(&metadata)?;
+    metadata_content.push('\n');
+
+    Ok(vec![
+        Page::new(
+            PathBuf::from(&url),
+            String::from_utf8(writer.into_inner()?)?,
+        ),
+        Page::new(
+            PathBuf::from(format!("{}-metadata.json", url)),
+            metadata_content,
+        ),
+    ])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source;
+    use std::str::FromStr;
+
+    #[test]
+    fn standards_table() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&source::Organisation::from_str(
+            r#"{"id": "geoplace", "name": "GeoPlace", "url": "https://www.geoplace.co.uk/"}"#,
+        )?)?;
+        cache.add(&source::Theme::from_str(
+            "---\ntype: theme\nidentifier: reference-data\nname: Reference Data\nordinal: 1\n---\nShared codes.",
+        )?)?;
+        cache.add(&source::Topic::from_str(
+            "---\ntype: topic\nidentifier: geospatial\nname: Geospatial\ntheme: reference-data\nordinal: 1\n---\nPlaces.",
+        )?)?;
+        cache.add(&source::Standard::from_str(
+            r#"---
+type: standard
+identifier: uprn
+name: Unique Property Reference Number
+acronym: UPRN
+topic: geospatial
+specification: https://www.geoplace.co.uk/addresses/uprn
+maintainer: geoplace
+endorsement_state:
+    status: endorsed
+    start_date: 2020-12-16
+    review_date: 2021-12-16
+---
+The UPRN."#,
+        )?)?;
+
+        let pages = render(&mut cache)?;
+
+        assert_eq!(
+            pages[0].content,
+            "id,name,acronym,topic,theme,maintainer,licence,specification,status,start_date,review_date,end_date\n\
+             uprn,Unique Property Reference Number,UPRN,Geospatial,Reference Data,GeoPlace,,https://www.geoplace.co.uk/addresses/uprn,endorsed,2020-12-16,2021-12-16,\n"
+        );
+        assert_eq!(
+            pages[2].content,
+            "id,title,status,maintainer,creation_date,update_date,publication_date,canonical_url\n"
+        );
+
+        let metadata: serde_json::Value = serde_json::from_str(&pages[1].content)?;
+
+        assert_eq!(metadata["url"], "standards.csv");
+        assert_eq!(metadata["tableSchema"]["primaryKey"], "id");
+        assert_eq!(metadata["tableSchema"]["columns"][11]["name"], "end_date");
+        assert_eq!(
+            metadata["tableSchema"]["columns"][11]["required"],
+            serde_json::Value::Null
+        );
+
+        Ok(())
+    }
+}
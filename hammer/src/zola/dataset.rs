@@ -0,0 +1,267 @@
+//! This module covers the dataset page from a Zola point of view.
+//!
+//! Datasets are rendered as [page bundles] so the CSV table is published next to the page for download.
+//!
+//! [page bundles]: https://www.getzola.org/documentation/content/overview/#asset-colocation
+use anyhow::Result;
+use rusqlite::Transaction;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+
+use crate::cache::records::*;
+use crate::cache::Cache;
+use crate::checksum::{Checksum, Digest, Hasher};
+
+type DatasetId = String;
+
+#[derive(Debug, Clone)]
+pub struct Dataset {
+    pub metadata: Metadata,
+    pub content: String,
+}
+
+impl Dataset {
+    pub fn id(&self) -> &DatasetId {
+        &self.metadata.extra.id
+    }
+
+    pub fn checksum(&self) -> Checksum {
+        self.into()
+    }
+
+    pub fn path(&self) -> String {
+        format!("{}/index.md", self.id())
+    }
+
+    /// The path to the CSV table, colocated with the page.
+    pub fn table_path(&self) -> String {
+        format!("{}/{}", self.id(), &self.metadata.extra.download)
+    }
+
+    /// Renders the CSV table with the column titles as header.
+    pub fn table(&self) -> Result<String> {
+        let mut writer = csv::Writer::from_writer(Vec::new());
+
+        writer.write_record(
+            self.metadata
+                .extra
+                .columns
+                .iter()
+                .map(|column| &column.title),
+        )?;
+
+        for row in &self.metadata.extra.rows {
+            writer.write_record(row)?;
+        }
+
+        Ok(String::from_utf8(writer.into_inner()?)?)
+    }
+}
+
+impl fmt::Display for Dataset {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let metadata = toml::to_string(&self.metadata).expect("metadata to serialize as TOML");
+
+        writeln!(f, "+++")?;
+        write!(f, "{}", &metadata)?;
+        writeln!(f, "+++")?;
+        write!(f, "{}", &self.content)
+    }
+}
+
+impl Digest for Dataset {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.metadata.digest(hasher);
+        self.content.digest(hasher);
+    }
+}
+
+impl From<&Dataset> for Checksum {
+    fn from(resource: &Dataset) -> Checksum {
+        let mut hasher = Hasher::new();
+        resource.digest(&mut hasher);
+
+        hasher.finalize()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Metadata {
+    title: String,
+    slug: String,
+    template: String,
+    extra: MetadataExtra,
+}
+
+impl Digest for Metadata {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.extra.id.digest(hasher);
+        self.title.digest(hasher);
+        self.extra.description.digest(hasher);
+        self.extra.maintainer.digest(hasher);
+        self.extra.download.digest(hasher);
+        self.extra.primary_key.digest(hasher);
+        self.extra.rows.digest(hasher);
+
+        for column in &self.extra.columns {
+            column.name.digest(hasher);
+            column.title.digest(hasher);
+            column.description.digest(hasher);
+            column.datatype.digest(hasher);
+        }
+    }
+}
+
+// Plain values must come before tables for the metadata to serialize as TOML.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MetadataExtra {
+    /// A local unique identifier for the dataset.
+    #[serde(rename = "identifier")]
+    pub id: DatasetId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub maintainer: Option<String>,
+    /// The file name of the CSV table, relative to the page.
+    pub download: String,
+    pub primary_key: Vec<String>,
+    /// The cells of every row in column order. Missing values are empty strings.
+    pub rows: Vec<Vec<String>>,
+    pub columns: Vec<Column>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Column {
+    pub name: String,
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub datatype: String,
+    pub required: bool,
+}
+
+pub fn get_all(cache: &mut Cache) -> Result<Vec<Dataset>> {
+    let tx = cache.transaction()?;
+    let records = DatasetRecord::select_all(&tx)?;
+    let mut result = Vec::new();
+
+    for record in records {
+        let resource = into_resource(&tx, record)?;
+
+        result.push(resource);
+    }
+
+    tx.commit()?;
+
+    Ok(result)
+}
+
+fn into_resource(tx: &Transaction, record: DatasetRecord) -> Result<Dataset> {
+    let columns = DatasetColumnRecord::select(tx, &record.id)?
+        .into_iter()
+        .map(|column| Column {
+            name: column.name,
+            title: column.title,
+            description: column.description,
+            datatype: column.datatype,
+            required: column.required,
+        })
+        .collect();
+    let mut rows = Vec::new();
+
+    for row in DatasetRowRecord::select(tx, &record.id)? {
+        rows.push(serde_json::from_str(&row.cells)?);
+    }
+
+    let extra = MetadataExtra {
+        id: record.id.clone(),
+        description: record.description,
+        maintainer: record.maintainer,
+        download: record.url,
+        primary_key: record
+            .primary_key
+            .map(|key| key.split(',').map(Into::into).collect())
+            .unwrap_or_default(),
+        rows,
+        columns,
+    };
+    let metadata = Metadata {
+        title: record.name,
+        slug: record.id,
+        template: "dataset.html".to_string(),
+        extra,
+    };
+
+    Ok(Dataset {
+        metadata,
+        content: String::new(),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source;
+
+    #[test]
+    fn from_source() -> Result<()> {
+        let metadata = r#"{
+          "@context": "http://www.w3.org/ns/csvw",
+          "url": "sdo_membership.csv",
+          "schema:name": "Standards Development Organisations membership",
+          "schema:description": "List of Standards Development Organisations",
+          "tableSchema": {
+            "primaryKey": "organisation_url",
+            "columns": [
+              {"titles": "organisation_url", "name": "organisation_url", "datatype": "schema:URL", "required": true},
+              {"titles": "Cost", "name": "cost", "schema:description": "The cost", "datatype": "number"}
+            ]
+          }
+        }"#;
+        let zola_page = r#"+++
+title = "Standards Development Organisations membership"
+slug = "sdo-membership"
+template = "dataset.html"
+
+[extra]
+identifier = "sdo-membership"
+description = "List of Standards Development Organisations"
+download = "sdo_membership.csv"
+primary_key = ["organisation_url"]
+rows = [["https://www.w3.org/", "7800"], ["https://www.bsigroup.com/", ""]]
+
+[[extra.columns]]
+name = "organisation_url"
+title = "organisation_url"
+datatype = "schema:URL"
+required = true
+
+[[extra.columns]]
+name = "cost"
+title = "Cost"
+description = "The cost"
+datatype = "number"
+required = false
++++
+"#;
+        let mut cache = Cache::connect(":memory:")?;
+        let resource = source::Dataset::new(
+            serde_json::from_str(metadata)?,
+            "organisation_url,Cost\nhttps://www.w3.org/,7800\nhttps://www.bsigroup.com/,\n",
+        )?;
+
+        cache.add(&resource)?;
+
+        let actual: Dataset = get_all(&mut cache)?.remove(0);
+
+        assert_eq!(&actual.to_string(), zola_page);
+        assert_eq!(actual.table_path(), "sdo-membership/sdo_membership.csv");
+        assert_eq!(
+            actual.table()?,
+            "organisation_url,Cost\nhttps://www.w3.org/,7800\nhttps://www.bsigroup.com/,\n"
+        );
+
+        Ok(())
+    }
+}
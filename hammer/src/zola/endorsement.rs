@@ -74,3 +74,18 @@ impl Digest for EndorsementState {
         self.end_date.digest(hasher);
     }
 }
+
+/// A step in the endorsement history of a standard.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct EndorsementTransition {
+    pub status: EndorsementStatus,
+    pub date: Date,
+}
+
+impl Digest for EndorsementTransition {
+    fn digest(&self, hasher: &mut Hasher) {
+        hasher.update(&Tag::Dict.to_bytes());
+        self.status.digest(hasher);
+        self.date.digest(hasher);
+    }
+}
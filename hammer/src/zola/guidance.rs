@@ -6,13 +6,15 @@ use serde::{Deserialize, Serialize};
 use std::fmt;
 use std::str::FromStr;
 
-use super::{GuidanceId, Organisation, Url};
+use super::{Document, GuidanceId, Organisation, StandardId, Url};
 use crate::cache::records::*;
 use crate::cache::Cache;
 use crate::checksum::{Checksum, Digest, Hasher};
+use crate::integrity;
 use crate::markdown;
 use crate::report;
 use crate::resource::Resource;
+use crate::source::{self, parse_frontmatter};
 use crate::Status;
 
 #[derive(Debug, Clone)]
@@ -33,6 +35,24 @@ impl Guidance {
     pub fn path(&self) -> String {
         format!("{}.md", self.id())
     }
+
+    /// The search index entry for the guidance, published under the given section path.
+    pub fn document(&self, section_path: &str) -> Document {
+        let extra = &self.metadata.extra;
+        let path = format!("{}{}/", section_path, &self.metadata.slug);
+
+        Document {
+            status: Some(extra.status.to_string()),
+            organisation: Some(extra.maintainer.name.clone()),
+            ..Document::new(
+                "guidance",
+                self.id(),
+                path,
+                &self.metadata.title,
+                markdown::plain_text(&self.content),
+            )
+        }
+    }
 }
 
 impl fmt::Display for Guidance {
@@ -46,6 +66,19 @@ impl fmt::Display for Guidance {
     }
 }
 
+impl FromStr for Guidance {
+    type Err = anyhow::Error;
+
+    fn from_str(blob: &str) -> Result<Self, Self::Err> {
+        let (metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
+
+        Ok(Self {
+            metadata,
+            content: content.into(),
+        })
+    }
+}
+
 impl Digest for Guidance {
     fn digest(&self, hasher: &mut Hasher) {
         self.metadata.digest(hasher);
@@ -62,6 +95,39 @@ impl From<&Guidance> for Checksum {
     }
 }
 
+/// Recovers the source guidance from its page, putting the title back as the heading of the content.
+impl From<&Guidance> for source::Guidance {
+    fn from(resource: &Guidance) -> Self {
+        let extra = &resource.metadata.extra;
+        let editorial = extra.editorial.clone();
+        let metadata = source::guidance::Metadata {
+            id: extra.id.clone(),
+            description: extra.description.clone(),
+            maintainer: extra.maintainer.id().clone(),
+            status: extra.status.clone(),
+            creation_date: extra.creation_date.clone(),
+            update_date: extra.update_date.clone(),
+            publication_date: extra.publication_date.clone(),
+            standards: Some(extra.standards.clone()).filter(|list| !list.is_empty()),
+            canonical_url: extra.canonical_url.clone(),
+            authors: editorial.authors,
+            reviewers: editorial.reviewers,
+            purpose: editorial.purpose,
+            target: editorial.target,
+            user_needs: editorial.user_needs,
+            route: editorial.route,
+            out_of_scope: editorial.out_of_scope,
+            problem_statements: editorial.problem_statements,
+            tags: editorial.tags,
+        };
+
+        source::Guidance {
+            metadata,
+            content: format!("# {}{}", &resource.metadata.title, &resource.content),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Metadata {
     title: String,
@@ -81,6 +147,9 @@ impl Digest for Metadata {
         self.extra.publication_date.digest(hasher);
         self.extra.maintainer.id().digest(hasher);
         self.extra.canonical_url.digest(hasher);
+        self.extra.description.digest(hasher);
+        self.extra.standards.digest(hasher);
+        self.extra.editorial.digest(hasher);
     }
 }
 
@@ -89,6 +158,8 @@ pub struct MetadataExtra {
     /// A local unique identifier for the standard.
     #[serde(rename = "identifier")]
     pub id: GuidanceId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
     pub status: Status,
     pub creation_date: String,
     pub update_date: String,
@@ -96,8 +167,14 @@ pub struct MetadataExtra {
     pub publication_date: Option<String>,
     /// The URL to the publication in GOV.UK.
     pub canonical_url: Option<Url>,
+    /// The identifiers of the standards the guidance is about.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub standards: Vec<StandardId>,
     /// The organisation maintaining the specification.
     pub maintainer: Organisation,
+    /// The editorial fields from the source, not shown on the site.
+    #[serde(default, skip_serializing_if = "source::guidance::Editorial::is_empty")]
+    pub editorial: source::guidance::Editorial,
 }
 
 impl Resource<Guidance> for Cache {
@@ -106,11 +183,10 @@ impl Resource<Guidance> for Cache {
         let mut result = None;
 
         if let Some(record) = GuidanceRecord::select(&tx, id)? {
-            result = Some(into_resource(&tx, record)?);
+            result = into_resource(&tx, record)?;
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Guidance, id, "");
 
         tx.commit()?;
@@ -133,9 +209,9 @@ pub fn get_all(cache: &mut Cache) -> Result<Vec<Guidance>> {
     let mut result = Vec::new();
 
     for record in records {
-        let resource = into_resource(&tx, record)?;
-
-        result.push(resource);
+        if let Some(resource) = into_resource(&tx, record)? {
+            result.push(resource);
+        }
     }
 
     tx.commit()?;
@@ -143,21 +219,42 @@ pub fn get_all(cache: &mut Cache) -> Result<Vec<Guidance>> {
     Ok(result)
 }
 
-fn into_resource(tx: &Transaction, record: GuidanceRecord) -> Result<Guidance> {
-    let id = &record.id;
-    let maintainer =
-        OrganisationRecord::select(&tx, &record.maintainer_id)?.expect("maintainer to exist");
+fn into_resource(tx: &Transaction, record: GuidanceRecord) -> Result<Option<Guidance>> {
+    let _id = &record.id;
+    let maintainer = match OrganisationRecord::select(tx, &record.maintainer_id)? {
+        Some(maintainer) => maintainer,
+        None => {
+            integrity::skip_unknown_organisation(
+                tx,
+                "guidance",
+                &record.id,
+                "maintainer",
+                &record.maintainer_id,
+            )?;
+
+            return Ok(None);
+        }
+    };
+    let standards = GuidanceStandardRecord::select(tx, &record.id)?
+        .into_iter()
+        .map(|record| record.standard_id)
+        .collect();
+    let editorial = source::guidance::select_editorial(tx, &record.id)?;
     let extra = MetadataExtra {
         id: record.id.clone(),
+        description: record.description,
         status: record.status,
         creation_date: record.creation_date.clone(),
         update_date: record.update_date,
         publication_date: record.publication_date,
         canonical_url: record.canonical_url,
+        standards,
         maintainer: maintainer.into(),
+        editorial,
     };
     let date = FromStr::from_str(&format!("{}T00:00:00Z", &record.creation_date))?;
-    let (title, content) = markdown::split_title(&record.content)?;
+    // Kept verbatim so the source can be recovered from the page.
+    let (title, content) = markdown::split_title_verbatim(&record.content)?;
     let metadata = Metadata {
         title,
         date,
@@ -167,5 +264,5 @@ fn into_resource(tx: &Transaction, record: GuidanceRecord) -> Result<Guidance> {
     };
     let resource = Guidance { metadata, content };
 
-    Ok(resource)
+    Ok(Some(resource))
 }
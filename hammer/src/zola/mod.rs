@@ -2,88 +2,163 @@
 //!
 //! [Zola]: https://www.getzola.org/
 
+mod dataset;
 mod endorsement;
 mod guidance;
 mod licence;
 mod organisation;
+mod process;
+pub mod search_index;
 pub mod section;
 mod standard;
+mod story;
 mod taxonomy;
 mod theme;
 mod topic;
 
-pub use endorsement::EndorsementState;
+pub use dataset::Dataset;
+pub use endorsement::{EndorsementState, EndorsementTransition};
 pub use guidance::Guidance;
 pub use licence::Licence;
 pub use organisation::Organisation;
+pub use process::Process;
+pub use search_index::{Document, SearchIndex};
 pub use section::Section;
-pub use standard::Standard;
+pub use standard::{Standard, StandardReference};
+pub use story::{CaseStudy, UseCase};
 pub use taxonomy::TopicReference;
 pub use theme::Theme;
 pub use topic::Topic;
 
 use anyhow::Result;
 use log::{info, warn};
-use std::fs;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
-use crate::cache::Cache;
-use crate::resource::ResourceType;
+use crate::cache::{AssetRecord, Cache};
+use crate::resource::{CaseStudyKind, ResourceType, UseCaseKind};
+use crate::sink;
+pub use crate::sink::{Blob, Page, Summary};
+use crate::source::asset;
 
 type StandardId = String;
 type LicenceId = String;
+type OrganisationId = String;
 type GuidanceId = String;
 type TopicId = String;
 type ThemeId = String;
 type Url = String;
 type Date = String;
 
-pub fn write(sink_dir: &Path, cache: &mut Cache) -> Result<()> {
-    let sections = section::get_all(cache)?;
+/// Writes the pages that differ from the ones in the sink and removes any file no longer rendered.
+pub fn write(sink_dir: &Path, cache: &mut Cache) -> Result<Summary> {
+    let pages = render(cache)?;
 
-    // Agressively clean previous build.
-    if sink_dir.exists() {
-        fs::remove_dir_all(sink_dir)?;
-    }
-    fs::create_dir(sink_dir)?;
+    sink::write(sink_dir, &pages)
+}
+
+/// Copies the assets into the given Zola static directory, under the path they are linked from.
+pub fn write_assets(static_dir: &Path, cache: &mut Cache) -> Result<Summary> {
+    let tx = cache.transaction()?;
+    let blobs: Vec<Blob> = AssetRecord::select_all(&tx)?
+        .into_iter()
+        .map(|record| Blob::new(PathBuf::from(record.id), record.content))
+        .collect();
+
+    tx.commit()?;
+
+    sink::write_blobs(&static_dir.join(asset::DIRECTORY), &blobs)
+}
+
+/// Renders every page from the cache.
+pub fn render(cache: &mut Cache) -> Result<Vec<Page>> {
+    let sections = section::get_all(cache)?;
+    let mut pages = Vec::new();
 
     for section in sections {
-        let section_path = sink_dir.join(&section.path());
+        let section_path = PathBuf::from(section.path());
         let resource_type = section.resource_type()?;
 
-        fs::create_dir(&section_path)?;
-        fs::write(&section_path.join("_index.md"), &section.to_string())?;
+        pages.push(Page::new(
+            section_path.join("_index.md"),
+            section.to_string(),
+        ));
 
         match resource_type {
             ResourceType::Standard => {
-                info!("Write standard set");
+                info!("Render standard set");
                 let resources = standard::get_all(cache)?;
                 for resource in resources {
-                    let resource_path = section_path.join(&resource.path());
-                    fs::write(&resource_path, &resource.to_string())?;
+                    let resource_path = section_path.join(resource.path());
+                    pages.push(Page::new(resource_path, resource.to_string()));
                 }
             }
             ResourceType::Guidance => {
-                info!("Write guidance set");
+                info!("Render guidance set");
                 let resources = guidance::get_all(cache)?;
                 for resource in resources {
-                    let resource_path = section_path.join(&resource.path());
-                    fs::write(&resource_path, &resource.to_string())?;
+                    let resource_path = section_path.join(resource.path());
+                    pages.push(Page::new(resource_path, resource.to_string()));
+                }
+            }
+            ResourceType::CaseStudy => {
+                info!("Render case study set");
+                let resources = story::get_all::<CaseStudyKind>(cache)?;
+                for resource in resources {
+                    let resource_path = section_path.join(resource.path());
+                    pages.push(Page::new(resource_path, resource.to_string()));
+                }
+            }
+            ResourceType::UseCase => {
+                info!("Render use case set");
+                let resources = story::get_all::<UseCaseKind>(cache)?;
+                for resource in resources {
+                    let resource_path = section_path.join(resource.path());
+                    pages.push(Page::new(resource_path, resource.to_string()));
+                }
+            }
+            ResourceType::Process => {
+                info!("Render process set");
+                let resources = process::get_all(cache)?;
+                for directory in process::directories(&resources, section.template()) {
+                    pages.push(Page::new(
+                        section_path.join(&directory.path).join("_index.md"),
+                        directory.to_string(),
+                    ));
+                }
+                for resource in resources {
+                    let resource_path = section_path.join(resource.path());
+                    pages.push(Page::new(resource_path, resource.to_string()));
+                }
+            }
+            ResourceType::Dataset => {
+                info!("Render dataset set");
+                let resources = dataset::get_all(cache)?;
+                for resource in resources {
+                    pages.push(Page::new(
+                        section_path.join(resource.path()),
+                        resource.to_string(),
+                    ));
+                    pages.push(Page::new(
+                        section_path.join(resource.table_path()),
+                        resource.table()?,
+                    ));
                 }
             }
             ResourceType::Theme => {
-                info!("Write theme set");
+                info!("Render theme set");
                 let resources = theme::get_all(cache)?;
                 for resource in resources {
-                    let resource_path = section_path.join(&resource.path());
-                    fs::create_dir(&resource_path)?;
-                    fs::write(&resource_path.join("_index.md"), &resource.to_string())?;
+                    let resource_path = section_path.join(resource.path());
+                    pages.push(Page::new(
+                        resource_path.join("_index.md"),
+                        resource.to_string(),
+                    ));
 
-                    info!("Write {} topics set", &resource.id());
-                    let subresources = topic::get_all(cache, &resource.id())?;
+                    info!("Render {} topics set", &resource.id());
+                    let subresources = topic::get_all(cache, resource.id())?;
                     for subresource in subresources {
-                        let subresource_path = resource_path.join(&subresource.path());
-                        fs::write(&subresource_path, &subresource.to_string())?;
+                        let subresource_path = resource_path.join(subresource.path());
+                        pages.push(Page::new(subresource_path, subresource.to_string()));
                     }
                 }
             }
@@ -94,5 +169,201 @@ pub fn write(sink_dir: &Path, cache: &mut Cache) -> Result<()> {
         }
     }
 
-    Ok(())
+    Ok(pages)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source;
+    use std::fs;
+    use std::str::FromStr;
+    use temp_testdir::TempDir;
+
+    #[test]
+    fn incremental_write() -> Result<()> {
+        let temp = TempDir::default();
+        let sink = temp.as_ref().join("content");
+        let mut cache = Cache::connect(":memory:")?;
+        let section = source::Section::from_str(
+            "---\ntype: section\nidentifier: themes\nresource_type: theme\n---\n# Themes",
+        )?;
+        let theme = source::Theme::from_str(
+            "---\ntype: theme\nidentifier: other\nname: Other\nordinal: 1\n---\n",
+        )?;
+
+        cache.add(&section)?;
+        cache.add(&theme)?;
+
+        let first = write(&sink, &mut cache)?;
+        let second = write(&sink, &mut cache)?;
+
+        assert_eq!(first.written.len(), 2);
+        assert_eq!(second.written.len(), 0);
+        assert_eq!(second.unchanged.len(), 2);
+
+        // Files the workbench never wrote, such as hand-written pages, are left alone.
+        fs::write(sink.join("about.md"), "+++\ntitle = \"About\"\n+++\n")?;
+        fs::write(sink.join("themes/notes.md"), "Notes")?;
+
+        let _: Option<source::Theme> = cache.drop("other")?;
+        let third = write(&sink, &mut cache)?;
+
+        assert_eq!(third.to_string(), "0 written, 1 unchanged, 1 removed");
+        assert!(!sink.join("themes/other").exists());
+        assert!(sink.join("about.md").exists());
+        assert!(sink.join("themes/notes.md").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn remove_stale_pages_across_caches() -> Result<()> {
+        let temp = TempDir::default();
+        let sink = temp.as_ref().join("content");
+        let section = source::Section::from_str(
+            "---\ntype: section\nidentifier: themes\nresource_type: theme\n---\n# Themes",
+        )?;
+        let theme = |id: &str| {
+            source::Theme::from_str(&format!(
+                "---\ntype: theme\nidentifier: {}\nname: {}\nordinal: 1\n---\n",
+                id, id
+            ))
+        };
+
+        // Each run of the workbench starts with a brand new in-memory cache.
+        let mut first_cache = Cache::connect(":memory:")?;
+        first_cache.add(&section)?;
+        first_cache.add(&theme("other")?)?;
+        first_cache.add(&theme("retired")?)?;
+
+        let first = write(&sink, &mut first_cache)?;
+
+        fs::write(sink.join("about.md"), "+++\ntitle = \"About\"\n+++\n")?;
+
+        let mut second_cache = Cache::connect(":memory:")?;
+        second_cache.add(&section)?;
+        second_cache.add(&theme("other")?)?;
+
+        let second = write(&sink, &mut second_cache)?;
+
+        assert_eq!(first.written.len(), 3);
+        assert_eq!(second.to_string(), "0 written, 2 unchanged, 1 removed");
+        assert!(!sink.join("themes/retired").exists());
+        assert!(sink.join("themes/other").exists());
+        assert!(sink.join("about.md").exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn copy_assets() -> Result<()> {
+        let temp = TempDir::default();
+        let static_dir = temp.as_ref().join("static");
+        let mut cache = Cache::connect(":memory:")?;
+        let asset = source::Asset::new("svg", b"<svg/>".to_vec())?;
+
+        cache.add(&asset)?;
+
+        let first = write_assets(&static_dir, &mut cache)?;
+        let second = write_assets(&static_dir, &mut cache)?;
+
+        assert_eq!(first.written, vec![PathBuf::from(asset.id())]);
+        assert_eq!(second.unchanged.len(), 1);
+        assert_eq!(
+            fs::read(static_dir.join("assets").join(asset.id()))?,
+            b"<svg/>"
+        );
+
+        Ok(())
+    }
+
+    #[test]
+    fn publish_linked_assets() -> Result<()> {
+        let temp = TempDir::default();
+        let source_dir = temp.as_ref().join("corpus");
+        let svg = b"<svg xmlns=\"http://www.w3.org/2000/svg\"/>";
+
+        fs::create_dir_all(source_dir.join("reference/processes"))?;
+        fs::create_dir_all(source_dir.join("assets"))?;
+        fs::write(
+            source_dir.join("reference.md"),
+            "---\ntype: section\nidentifier: reference\nresource_type: process\n---\n# Reference\n",
+        )?;
+        fs::write(
+            source_dir.join("reference/processes/endorsement_process.md"),
+            "---\ntype: Process\n---\n\n# Endorsement process\n\n## States\n\n![](../assets/endorsement_state_machine.svg)\n",
+        )?;
+        fs::write(
+            source_dir.join("assets/endorsement_state_machine.svg"),
+            &svg[..],
+        )?;
+
+        let mut cache = Cache::connect(":memory:")?;
+        let diagnostics = source::read(&source_dir, &mut cache, source::UnknownFields::Ignore)?;
+
+        assert!(diagnostics.is_empty(), "{}", diagnostics);
+
+        let asset = source::Asset::new("svg", svg.to_vec())?;
+        let pages = render(&mut cache)?;
+        let page = pages
+            .iter()
+            .find(|page| page.path.ends_with("endorsement-process.md"))
+            .expect("process page to be rendered");
+
+        assert!(page.content.contains(&format!("![]({})", asset.url())));
+
+        let static_dir = temp.as_ref().join("static");
+        write_assets(&static_dir, &mut cache)?;
+
+        assert!(static_dir.join("assets").join(asset.id()).exists());
+
+        Ok(())
+    }
+
+    #[test]
+    fn nested_reference_sections() -> Result<()> {
+        let temp = TempDir::default();
+        let source_dir = temp.as_ref().join("corpus");
+        let process = |title: &str| format!("---\ntype: Process\n---\n\n# {}\n", title);
+
+        fs::create_dir_all(source_dir.join("reference/processes/data_sharing"))?;
+        fs::write(
+            source_dir.join("reference.md"),
+            "---\ntype: section\nidentifier: reference\nresource_type: process\n---\n# Reference\n",
+        )?;
+        fs::write(
+            source_dir.join("reference/processes/endorsement_process.md"),
+            process("Endorsement process"),
+        )?;
+        fs::write(
+            source_dir.join("reference/processes/data_sharing/agreement.md"),
+            process("Agreement"),
+        )?;
+
+        let mut cache = Cache::connect(":memory:")?;
+        let diagnostics = source::read(&source_dir, &mut cache, source::UnknownFields::Ignore)?;
+
+        assert!(diagnostics.is_empty(), "{}", diagnostics);
+
+        let mut paths: Vec<_> = render(&mut cache)?
+            .into_iter()
+            .map(|page| page.path.display().to_string())
+            .collect();
+        paths.sort();
+
+        assert_eq!(
+            paths,
+            vec![
+                "reference/_index.md",
+                "reference/processes/_index.md",
+                "reference/processes/data-sharing/_index.md",
+                "reference/processes/data-sharing/agreement.md",
+                "reference/processes/endorsement-process.md",
+            ]
+        );
+
+        Ok(())
+    }
 }
@@ -67,8 +67,7 @@ impl Resource<Organisation> for Cache {
             });
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Organisation, id, "");
 
         tx.commit()?;
@@ -91,7 +90,7 @@ impl Resource<Organisation> for Cache {
 
         Cache::insert_trailmark(&tx, &checksum, "organisation", &self.timestamp)?;
 
-        &self.report.log(
+        self.report.log(
             report::Action::Add,
             report::Entity::Organisation,
             &item.id,
@@ -104,15 +103,14 @@ impl Resource<Organisation> for Cache {
     }
 
     fn drop(&mut self, id: &str) -> Result<Option<Organisation>> {
-        let item = self.get(&id)?;
+        let item = self.get(id)?;
         let tx = self.conn.transaction()?;
 
         if item.is_some() {
             OrganisationRecord::delete(&tx, id)?;
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Prune, report::Entity::Organisation, id, "");
 
         tx.commit()?;
@@ -0,0 +1,244 @@
+//! This module covers the reference documents, such as processes, from a Zola point of view.
+//!
+//! Processes are identified by their path under `reference/` so every directory in between becomes a nested
+//! [Zola section].
+//!
+//! [Zola section]: https://www.getzola.org/documentation/content/section/
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeSet;
+use std::fmt;
+
+use super::Document;
+use crate::cache::records::*;
+use crate::cache::Cache;
+use crate::checksum::{Checksum, Digest, Hasher};
+use crate::markdown;
+
+type ProcessId = String;
+
+#[derive(Debug, Clone)]
+pub struct Process {
+    pub metadata: Metadata,
+    pub content: String,
+}
+
+impl Process {
+    pub fn id(&self) -> &ProcessId {
+        &self.metadata.extra.id
+    }
+
+    pub fn checksum(&self) -> Checksum {
+        self.into()
+    }
+
+    pub fn path(&self) -> String {
+        format!("{}.md", self.id())
+    }
+
+    /// The search index entry for the process, published under the given section path.
+    pub fn document(&self, section_path: &str) -> Document {
+        let path = format!("{}{}/", section_path, self.id());
+
+        Document {
+            description: self.metadata.extra.description.clone(),
+            ..Document::new(
+                "process",
+                self.id(),
+                path,
+                &self.metadata.title,
+                markdown::plain_text(&self.content),
+            )
+        }
+    }
+}
+
+impl fmt::Display for Process {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let metadata = toml::to_string(&self.metadata).expect("metadata to serialize as TOML");
+
+        writeln!(f, "+++")?;
+        write!(f, "{}", &metadata)?;
+        writeln!(f, "+++")?;
+        write!(f, "{}", &self.content)
+    }
+}
+
+impl Digest for Process {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.metadata.digest(hasher);
+        self.content.digest(hasher);
+    }
+}
+
+impl From<&Process> for Checksum {
+    fn from(resource: &Process) -> Checksum {
+        let mut hasher = Hasher::new();
+        resource.digest(&mut hasher);
+
+        hasher.finalize()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Metadata {
+    title: String,
+    slug: String,
+    template: String,
+    extra: MetadataExtra,
+}
+
+impl Digest for Metadata {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.title.digest(hasher);
+        self.slug.digest(hasher);
+        self.template.digest(hasher);
+        self.extra.id.digest(hasher);
+        self.extra.description.digest(hasher);
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MetadataExtra {
+    /// The path of the process within `reference/`.
+    #[serde(rename = "identifier")]
+    pub id: ProcessId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+}
+
+/// A directory in between the reference section and its processes, published as a nested section.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Directory {
+    /// The path of the directory within `reference/`, e.g. `processes`.
+    pub path: String,
+    pub metadata: DirectoryMetadata,
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize, Serialize)]
+pub struct DirectoryMetadata {
+    title: String,
+    template: String,
+}
+
+impl fmt::Display for Directory {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let metadata = toml::to_string(&self.metadata).expect("metadata to serialize as TOML");
+
+        writeln!(f, "+++")?;
+        write!(f, "{}", &metadata)?;
+        writeln!(f, "+++")
+    }
+}
+
+/// Lists every directory the given processes are nested in, parents first. They share the template of the section
+/// they belong to.
+pub fn directories(processes: &[Process], template: &str) -> Vec<Directory> {
+    let mut paths = BTreeSet::new();
+
+    for process in processes {
+        let mut segments: Vec<&str> = process.id().split('/').collect();
+        segments.pop();
+
+        for depth in 1..=segments.len() {
+            paths.insert(segments[..depth].join("/"));
+        }
+    }
+
+    paths
+        .into_iter()
+        .map(|path| {
+            let name = path
+                .rsplit('/')
+                .next()
+                .unwrap_or_default()
+                .replace('-', " ");
+            let mut chars = name.chars();
+            let title = match chars.next() {
+                Some(first) => first.to_uppercase().chain(chars).collect(),
+                None => name.clone(),
+            };
+
+            Directory {
+                path,
+                metadata: DirectoryMetadata {
+                    title,
+                    template: template.to_string(),
+                },
+            }
+        })
+        .collect()
+}
+
+pub fn get_all(cache: &mut Cache) -> Result<Vec<Process>> {
+    let tx = cache.transaction()?;
+    let records = ProcessRecord::select_all(&tx)?;
+    let mut result = Vec::new();
+
+    for record in records {
+        result.push(into_resource(record)?);
+    }
+
+    tx.commit()?;
+
+    Ok(result)
+}
+
+fn into_resource(record: ProcessRecord) -> Result<Process> {
+    let (title, content) = markdown::split_title(&record.content)?;
+    let slug = record.id.rsplit('/').next().unwrap_or_default().to_string();
+    let metadata = Metadata {
+        title,
+        slug,
+        template: "process.html".to_string(),
+        extra: MetadataExtra {
+            id: record.id,
+            description: record.description,
+        },
+    };
+
+    Ok(Process { metadata, content })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source;
+    use std::path::Path;
+
+    #[test]
+    fn nested_directories() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+
+        for path in [
+            "reference/processes/endorsement_process.md",
+            "reference/processes/data_sharing/agreement.md",
+            "reference/glossary.md",
+        ] {
+            let title = Path::new(path).file_stem().unwrap_or_default();
+            let blob = format!("---\ntype: process\n---\n# {}\n", title.to_string_lossy());
+
+            cache.add(&source::Process::from_source(Path::new(path), &blob)?)?;
+        }
+
+        let processes = get_all(&mut cache)?;
+        let directories = directories(&processes, "reference-section.html");
+        let paths: Vec<_> = directories.iter().map(|d| d.path.as_str()).collect();
+        let process = processes
+            .iter()
+            .find(|process| process.id() == "processes/endorsement-process");
+
+        assert_eq!(paths, vec!["processes", "processes/data-sharing"]);
+        assert_eq!(
+            directories[1].to_string(),
+            "+++\ntitle = \"Data sharing\"\ntemplate = \"reference-section.html\"\n+++\n"
+        );
+        assert_eq!(
+            process.map(|process| process.path()),
+            Some("processes/endorsement-process.md".to_string())
+        );
+
+        Ok(())
+    }
+}
@@ -0,0 +1,274 @@
+//! This module builds the search index for the published site.
+//!
+//! Zola's own index only knows about titles and bodies. This one keeps the fields the site search needs to filter by
+//! (e.g. status or topic) and tells how much each field weighs for every resource type.
+use anyhow::Result;
+use log::{info, warn};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+use super::{guidance, process, section, standard, story, theme, topic};
+use crate::cache::Cache;
+use crate::resource::{CaseStudyKind, ResourceType, UseCaseKind};
+
+/// The relative weight of each searchable field per resource type.
+const WEIGHTS: [(&str, &[(&str, u32)]); 7] = [
+    (
+        "case-study",
+        &[
+            ("title", 10),
+            ("description", 5),
+            ("organisation", 2),
+            ("body", 1),
+        ],
+    ),
+    (
+        "guidance",
+        &[
+            ("title", 10),
+            ("description", 5),
+            ("organisation", 2),
+            ("body", 1),
+        ],
+    ),
+    ("process", &[("title", 10), ("description", 5), ("body", 1)]),
+    (
+        "standard",
+        &[
+            ("title", 10),
+            ("acronym", 10),
+            ("subjects", 4),
+            ("topic", 3),
+            ("organisation", 2),
+            ("body", 1),
+        ],
+    ),
+    ("theme", &[("title", 10), ("body", 1)]),
+    ("topic", &[("title", 10), ("theme", 3), ("body", 1)]),
+    (
+        "use-case",
+        &[
+            ("title", 10),
+            ("description", 5),
+            ("organisation", 2),
+            ("body", 1),
+        ],
+    ),
+];
+
+#[derive(Debug, Clone, Serialize)]
+pub struct SearchIndex {
+    pub weights: BTreeMap<&'static str, BTreeMap<&'static str, u32>>,
+    pub documents: Vec<Document>,
+}
+
+/// A searchable resource.
+///
+/// The `topic`, `theme` and `status` fields are identifiers meant for filtering.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Document {
+    #[serde(rename = "type")]
+    pub resource_type: String,
+    pub id: String,
+    /// The path to the page relative to the base URL of the site.
+    pub path: String,
+    pub title: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub acronym: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub status: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub topic: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub theme: Option<String>,
+    /// The name of the maintainer or publisher.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub organisation: Option<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub subjects: Vec<String>,
+    /// The content as plain text.
+    pub body: String,
+}
+
+impl Document {
+    pub fn new(resource_type: &str, id: &str, path: String, title: &str, body: String) -> Self {
+        Document {
+            resource_type: resource_type.into(),
+            id: id.into(),
+            path,
+            title: title.into(),
+            acronym: None,
+            description: None,
+            status: None,
+            topic: None,
+            theme: None,
+            organisation: None,
+            subjects: Vec::new(),
+            body,
+        }
+    }
+}
+
+/// Collects a document for every page published from the cache.
+pub fn index(cache: &mut Cache) -> Result<SearchIndex> {
+    let weights = WEIGHTS
+        .iter()
+        .map(|(resource_type, fields)| (*resource_type, fields.iter().cloned().collect()))
+        .collect();
+    let mut documents = Vec::new();
+
+    for section in section::get_all(cache)? {
+        let section_path = section.path();
+
+        match section.resource_type()? {
+            ResourceType::Standard => {
+                for resource in standard::get_all(cache)? {
+                    documents.push(resource.document(&section_path));
+                }
+            }
+            ResourceType::Guidance => {
+                for resource in guidance::get_all(cache)? {
+                    documents.push(resource.document(&section_path));
+                }
+            }
+            ResourceType::CaseStudy => {
+                for resource in story::get_all::<CaseStudyKind>(cache)? {
+                    documents.push(resource.document(&section_path));
+                }
+            }
+            ResourceType::UseCase => {
+                for resource in story::get_all::<UseCaseKind>(cache)? {
+                    documents.push(resource.document(&section_path));
+                }
+            }
+            ResourceType::Process => {
+                for resource in process::get_all(cache)? {
+                    documents.push(resource.document(&section_path));
+                }
+            }
+            ResourceType::Theme => {
+                for resource in theme::get_all(cache)? {
+                    let document = resource.document(&section_path);
+
+                    for subresource in topic::get_all(cache, resource.id())? {
+                        documents.push(subresource.document(&document.path, resource.id()));
+                    }
+
+                    documents.push(document);
+                }
+            }
+            typ => {
+                warn!("'{}' is an unimplemented zola resource", typ);
+            }
+        }
+    }
+
+    documents.sort_by(|a, b| a.path.cmp(&b.path));
+
+    Ok(SearchIndex { weights, documents })
+}
+
+/// Writes the search index to the given path unless it is already up to date. Returns whether it was written.
+pub fn write(path: &Path, cache: &mut Cache) -> Result<bool> {
+    let text = serde_json::to_string(&index(cache)?)?;
+
+    if fs::read_to_string(path).ok().as_deref() == Some(text.as_str()) {
+        return Ok(false);
+    }
+
+    info!("Writing search index {}", path.display());
+
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, text)?;
+
+    Ok(true)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source;
+    use std::str::FromStr;
+
+    #[test]
+    fn standard_documents() -> Result<()> {
+        let mut cache = Cache::connect(":memory:")?;
+
+        cache.add(&source::Section::from_str(
+            "---\ntype: section\nidentifier: standards\nresource_type: standard\n---\n# Standards",
+        )?)?;
+        cache.add(&source::Section::from_str(
+            "---\ntype: section\nidentifier: themes\nresource_type: theme\n---\n# Themes",
+        )?)?;
+        cache.add(&source::Theme::from_str(
+            "---\ntype: theme\nidentifier: reference-data\nname: Reference Data\nordinal: 1\n---\nShared codes.",
+        )?)?;
+        cache.add(&source::Topic::from_str(
+            "---\ntype: topic\nidentifier: geospatial\nname: Geospatial\ntheme: reference-data\nordinal: 1\n---\nPlaces.",
+        )?)?;
+        cache.add(&source::Organisation::from_str(
+            r#"{"id": "geoplace", "name": "GeoPlace", "url": "https://www.geoplace.co.uk/"}"#,
+        )?)?;
+        cache.add(&source::Standard::from_str(
+            r#"---
+type: standard
+identifier: uprn
+name: Unique Property Reference Number
+acronym: UPRN
+topic: geospatial
+subjects:
+  - identification
+specification: https://www.geoplace.co.uk/addresses/uprn
+maintainer: geoplace
+endorsement_state:
+    status: endorsed
+    start_date: 2020-12-16
+    review_date: 2021-12-16
+---
+The UPRN is a _unique_ identifier."#,
+        )?)?;
+
+        let actual = index(&mut cache)?;
+        let paths: Vec<_> = actual
+            .documents
+            .iter()
+            .map(|doc| doc.path.as_str())
+            .collect();
+
+        assert_eq!(
+            paths,
+            vec![
+                "standards/uprn/",
+                "themes/reference-data/",
+                "themes/reference-data/geospatial/"
+            ]
+        );
+
+        let mut expected = Document::new(
+            "standard",
+            "uprn",
+            "standards/uprn/".into(),
+            "Unique Property Reference Number",
+            "The UPRN is a unique identifier.".into(),
+        );
+        expected.acronym = Some("UPRN".into());
+        expected.status = Some("endorsed".into());
+        expected.topic = Some("geospatial".into());
+        expected.theme = Some("reference-data".into());
+        expected.organisation = Some("GeoPlace".into());
+        expected.subjects = vec!["identification".into()];
+
+        assert_eq!(actual.documents[0], expected);
+        assert_eq!(actual.documents[2].theme, Some("reference-data".into()));
+        assert_eq!(actual.weights["standard"]["acronym"], 10);
+
+        Ok(())
+    }
+}
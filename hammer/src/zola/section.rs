@@ -39,6 +39,10 @@ impl Section {
         format!("{}/", self.id())
     }
 
+    pub fn template(&self) -> &str {
+        &self.metadata.template
+    }
+
     pub fn resource_type(&self) -> Result<ResourceType> {
         ResourceType::from_str(&self.metadata.extra.resource_type)
     }
@@ -115,7 +119,7 @@ pub fn get_all(cache: &mut Cache) -> Result<Vec<Section>> {
 
         let metadata = Metadata {
             title,
-            slug: format!("{}", record.id),
+            slug: record.id.to_string(),
             template: format!("{}-section.html", record.id),
             extra,
         };
@@ -141,7 +145,7 @@ impl Resource<Section> for Cache {
 
             let metadata = Metadata {
                 title,
-                slug: format!("{}", record.id),
+                slug: record.id.to_string(),
                 template: "standard-set.html".to_string(),
                 extra,
             };
@@ -150,8 +154,7 @@ impl Resource<Section> for Cache {
             result = Some(resource);
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Section, id, "");
 
         tx.commit()?;
@@ -3,15 +3,22 @@ use anyhow::Result;
 use chrono::{DateTime, Utc};
 use rusqlite::Transaction;
 use serde::{Deserialize, Serialize};
+use std::convert::TryFrom;
 use std::fmt;
 use std::str::FromStr;
 
-use super::{EndorsementState, Licence, Organisation, StandardId, TopicReference, Url};
+use super::{
+    Date, Document, EndorsementState, EndorsementTransition, Licence, LicenceId, Organisation,
+    OrganisationId, StandardId, TopicId, TopicReference, Url,
+};
 use crate::cache::records::*;
 use crate::cache::Cache;
-use crate::checksum::{Checksum, Digest, Hasher};
+use crate::checksum::{Checksum, Digest, Hasher, Tag};
+use crate::integrity;
+use crate::markdown;
 use crate::report;
 use crate::resource::Resource;
+use crate::source::{self, parse_frontmatter};
 
 #[derive(Debug, Clone)]
 pub struct Standard {
@@ -31,6 +38,28 @@ impl Standard {
     pub fn path(&self) -> String {
         format!("{}.md", self.id())
     }
+
+    /// The search index entry for the standard, published under the given section path.
+    pub fn document(&self, section_path: &str) -> Document {
+        let extra = &self.metadata.extra;
+        let path = format!("{}{}/", section_path, &self.metadata.slug);
+
+        Document {
+            acronym: extra.acronym.clone(),
+            status: Some(extra.endorsement_state.status.to_string()),
+            topic: extra.topic.as_ref().map(|topic| topic.id.clone()),
+            theme: extra.topic.as_ref().map(|topic| topic.theme.clone()),
+            organisation: Some(extra.maintainer.name.clone()),
+            subjects: extra.subjects.clone(),
+            ..Document::new(
+                "standard",
+                self.id(),
+                path,
+                &self.metadata.title,
+                markdown::plain_text(&self.content),
+            )
+        }
+    }
 }
 
 impl fmt::Display for Standard {
@@ -44,6 +73,19 @@ impl fmt::Display for Standard {
     }
 }
 
+impl FromStr for Standard {
+    type Err = anyhow::Error;
+
+    fn from_str(blob: &str) -> Result<Self, Self::Err> {
+        let (metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
+
+        Ok(Self {
+            metadata,
+            content: content.into(),
+        })
+    }
+}
+
 impl Digest for Standard {
     fn digest(&self, hasher: &mut Hasher) {
         self.metadata.digest(hasher);
@@ -60,6 +102,80 @@ impl From<&Standard> for Checksum {
     }
 }
 
+/// Recovers the source standard from its page, keeping only the identifiers of the resources it references.
+impl TryFrom<&Standard> for source::Standard {
+    type Error = anyhow::Error;
+
+    fn try_from(resource: &Standard) -> Result<Self> {
+        let extra = &resource.metadata.extra;
+        let mut endorsement_history = Vec::new();
+
+        for transition in &extra.endorsement_history {
+            endorsement_history.push(source::endorsement::EndorsementTransition {
+                status: transition.status.to_string().parse()?,
+                date: transition.date.clone(),
+            });
+        }
+
+        let metadata = source::standard::Metadata {
+            id: extra.id.clone(),
+            name: extra.name.clone(),
+            acronym: extra.acronym.clone(),
+            topic: extra.topic_id.clone(),
+            subjects: extra.subjects.clone(),
+            conforms_to: extra.conforms_to.clone(),
+            specification: extra.specification.clone(),
+            licence: extra.licence_id.clone(),
+            maintainer: extra.maintainer.id().clone(),
+            related: extra.related_ids.clone(),
+            endorsement_state: source::endorsement::EndorsementState {
+                status: extra.endorsement_state.status.to_string().parse()?,
+                start_date: extra.endorsement_state.start_date.clone(),
+                review_date: extra.endorsement_state.review_date.clone(),
+                end_date: extra.endorsement_state.end_date.clone(),
+            },
+            endorsement_history,
+            dsa_process: extra
+                .dsa_process
+                .as_ref()
+                .map(|process| source::standard::DsaProcess {
+                    stage: process.stage.clone(),
+                    start_date: process.start_date.clone(),
+                    update_date: process.update_date.clone(),
+                }),
+            consultation: extra.consultation.as_ref().map(|consultation| {
+                source::standard::Consultation {
+                    title: consultation.title.clone(),
+                    url: consultation.url.clone(),
+                    start_date: consultation.start_date.clone(),
+                    end_date: consultation.end_date.clone(),
+                }
+            }),
+            guidance: source_external_resources(&extra.guidance),
+            use_cases: source_external_resources(&extra.use_cases),
+            case_studies: source_external_resources(&extra.case_studies),
+        };
+
+        Ok(source::Standard {
+            metadata,
+            content: resource.content.clone(),
+        })
+    }
+}
+
+fn source_external_resources(
+    resources: &[ExternalResource],
+) -> Vec<source::standard::ExternalResource> {
+    resources
+        .iter()
+        .map(|resource| source::standard::ExternalResource {
+            title: resource.title.clone(),
+            publisher: resource.publisher_id.clone(),
+            url: resource.url.clone(),
+        })
+        .collect()
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Metadata {
     title: String,
@@ -74,21 +190,20 @@ impl Digest for Metadata {
         self.extra.id.digest(hasher);
         self.extra.name.digest(hasher);
         self.extra.acronym.digest(hasher);
-        self.extra.topic.digest(hasher);
+        self.extra.topic_id.digest(hasher);
         self.extra.specification.digest(hasher);
-        self.extra
-            .licence
-            .clone()
-            .map(|x| x.id().clone())
-            .digest(hasher);
+        self.extra.licence_id.digest(hasher);
         self.extra.maintainer.id().digest(hasher);
-        self.extra
-            .related
-            .iter()
-            .map(|x| x.id.clone())
-            .collect::<Vec<_>>()
-            .digest(hasher);
+        self.extra.related_ids.digest(hasher);
         self.extra.endorsement_state.digest(hasher);
+        self.extra.endorsement_history.digest(hasher);
+        self.extra.subjects.digest(hasher);
+        self.extra.conforms_to.digest(hasher);
+        self.extra.dsa_process.digest(hasher);
+        self.extra.consultation.digest(hasher);
+        self.extra.guidance.digest(hasher);
+        self.extra.use_cases.digest(hasher);
+        self.extra.case_studies.digest(hasher);
     }
 }
 
@@ -104,18 +219,127 @@ pub struct MetadataExtra {
     pub acronym: Option<String>,
     /// The URL to the technical specification for the standard.
     pub specification: Url,
-    /// The list of related standards.
+    /// The list of subjects that refine the topic classification.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub subjects: Vec<String>,
+    /// The identifier of the external standard this standard conforms to.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub conforms_to: Option<String>,
+    /// The identifier of the topic as given in the source, even if the topic is unknown.
+    pub topic_id: TopicId,
+    /// The identifier of the licence as given in the source, even if the licence is unknown.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub licence_id: Option<LicenceId>,
+    /// The identifiers of the related standards as given in the source, including unknown ones.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub related_ids: Vec<StandardId>,
+    /// The list of known related standards.
     #[serde(default)]
     pub related: Vec<RelatedStandard>,
-    /// The topic used to classify the standard.
+    /// The topic used to classify the standard, when known.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub topic: Option<TopicReference>,
-    /// The licence the standard (or specification) is licensed under.
+    /// The licence the standard (or specification) is licensed under, when known.
     #[serde(default, skip_serializing_if = "Option::is_none")]
     pub licence: Option<Licence>,
     /// The organisation maintaining the specification.
     pub maintainer: Organisation,
     pub endorsement_state: EndorsementState,
+    /// The ordered list of endorsement statuses the standard went through.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub endorsement_history: Vec<EndorsementTransition>,
+    /// The stage of the standard in the Data Standards Authority process.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub dsa_process: Option<DsaProcess>,
+    /// The public consultation held for the standard.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub consultation: Option<Consultation>,
+    /// The list of external guidance about the standard.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub guidance: Vec<ExternalResource>,
+    /// The list of external use cases for the standard.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub use_cases: Vec<ExternalResource>,
+    /// The list of external case studies using the standard.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub case_studies: Vec<ExternalResource>,
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct DsaProcess {
+    pub stage: String,
+    pub start_date: Date,
+    pub update_date: Date,
+}
+
+impl Digest for DsaProcess {
+    fn digest(&self, hasher: &mut Hasher) {
+        hasher.update(&Tag::Dict.to_bytes());
+        self.stage.digest(hasher);
+        self.start_date.digest(hasher);
+        self.update_date.digest(hasher);
+    }
+}
+
+impl From<DsaProcessRecord> for DsaProcess {
+    fn from(record: DsaProcessRecord) -> Self {
+        Self {
+            stage: record.stage,
+            start_date: record.start_date,
+            update_date: record.update_date,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Consultation {
+    pub title: String,
+    pub url: Url,
+    pub start_date: Date,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub end_date: Option<Date>,
+}
+
+impl Digest for Consultation {
+    fn digest(&self, hasher: &mut Hasher) {
+        hasher.update(&Tag::Dict.to_bytes());
+        self.title.digest(hasher);
+        self.url.digest(hasher);
+        self.start_date.digest(hasher);
+        self.end_date.digest(hasher);
+    }
+}
+
+impl From<ConsultationRecord> for Consultation {
+    fn from(record: ConsultationRecord) -> Self {
+        Self {
+            title: record.title,
+            url: record.url,
+            start_date: record.start_date,
+            end_date: record.end_date,
+        }
+    }
+}
+
+/// A resource about the standard published outside of this site.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct ExternalResource {
+    pub title: String,
+    pub url: Url,
+    /// The identifier of the publishing organisation as given in the source.
+    pub publisher_id: OrganisationId,
+    /// The publishing organisation, when known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<Organisation>,
+}
+
+impl Digest for ExternalResource {
+    fn digest(&self, hasher: &mut Hasher) {
+        hasher.update(&Tag::Dict.to_bytes());
+        self.title.digest(hasher);
+        self.url.digest(hasher);
+        self.publisher_id.digest(hasher);
+    }
 }
 
 /// A reference to a related standard.
@@ -131,17 +355,34 @@ impl Digest for RelatedStandard {
     }
 }
 
+/// A reference to a standard from another resource.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StandardReference {
+    pub id: StandardId,
+    pub name: String,
+    pub url: Url,
+}
+
+impl From<StandardRecord> for StandardReference {
+    fn from(record: StandardRecord) -> StandardReference {
+        StandardReference {
+            url: format!("/standards/{}", record.id),
+            id: record.id,
+            name: record.name,
+        }
+    }
+}
+
 impl Resource<Standard> for Cache {
     fn get(&mut self, id: &str) -> Result<Option<Standard>> {
         let tx = self.conn.transaction()?;
         let mut result = None;
 
         if let Some(record) = StandardRecord::select(&tx, id)? {
-            result = Some(into_resource(&tx, record)?);
+            result = into_resource(&tx, record)?;
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Standard, id, "");
 
         tx.commit()?;
@@ -164,9 +405,9 @@ pub fn get_all(cache: &mut Cache) -> Result<Vec<Standard>> {
     let mut result = Vec::new();
 
     for record in records {
-        let resource = into_resource(&tx, record)?;
-
-        result.push(resource);
+        if let Some(resource) = into_resource(&tx, record)? {
+            result.push(resource);
+        }
     }
 
     tx.commit()?;
@@ -174,16 +415,19 @@ pub fn get_all(cache: &mut Cache) -> Result<Vec<Standard>> {
     Ok(result)
 }
 
-fn into_resource(tx: &Transaction, record: StandardRecord) -> Result<Standard> {
+fn into_resource(tx: &Transaction, record: StandardRecord) -> Result<Option<Standard>> {
     let standard_id = &record.id;
-    let related_records = RelatedStandardRecord::select(tx, &standard_id)?;
-    let endorsement_record = EndorsementStateRecord::select(tx, &standard_id)?
+    let related_records = RelatedStandardRecord::select(tx, standard_id)?;
+    let endorsement_record = EndorsementStateRecord::select(tx, standard_id)?
         .expect("missing endorsement state. the cache is corrupted.");
+    let transition_records = EndorsementTransitionRecord::select(tx, standard_id)?;
     let mut related: Vec<RelatedStandard> = Vec::new();
+    let mut related_ids = Vec::new();
 
     for related_record in related_records {
-        if let Some(std_record) = StandardRecord::select(&tx, &related_record.related_standard_id)?
-        {
+        related_ids.push(related_record.related_standard_id.clone());
+
+        if let Some(std_record) = StandardRecord::select(tx, &related_record.related_standard_id)? {
             related.push(RelatedStandard {
                 id: std_record.id,
                 name: std_record.name,
@@ -191,14 +435,26 @@ fn into_resource(tx: &Transaction, record: StandardRecord) -> Result<Standard> {
         }
     }
 
-    let licence = if let Some(licence_id) = record.licence_id {
-        LicenceRecord::select(&tx, &licence_id)?
+    let licence = if let Some(licence_id) = &record.licence_id {
+        LicenceRecord::select(tx, licence_id)?
     } else {
         None
     };
-    let maintainer =
-        OrganisationRecord::select(&tx, &record.maintainer_id)?.expect("maintainer to exist");
-    let topic = TopicRecord::select(&tx, &record.topic_id)?.map(|record| TopicReference {
+    let maintainer = match OrganisationRecord::select(tx, &record.maintainer_id)? {
+        Some(maintainer) => maintainer,
+        None => {
+            integrity::skip_unknown_organisation(
+                tx,
+                "standard",
+                &record.id,
+                "maintainer",
+                &record.maintainer_id,
+            )?;
+
+            return Ok(None);
+        }
+    };
+    let topic = TopicRecord::select(tx, &record.topic_id)?.map(|record| TopicReference {
         id: record.id,
         name: record.name,
         theme: record.theme_id,
@@ -210,16 +466,46 @@ fn into_resource(tx: &Transaction, record: StandardRecord) -> Result<Standard> {
         review_date: endorsement_record.review_date,
         end_date: endorsement_record.end_date,
     };
+    let mut endorsement_history = Vec::new();
+
+    for transition_record in transition_records {
+        endorsement_history.push(EndorsementTransition {
+            status: transition_record.status.parse()?,
+            date: transition_record.date,
+        });
+    }
+
+    let subjects = StandardSubjectRecord::select(tx, standard_id)?
+        .into_iter()
+        .map(|record| record.subject_id)
+        .collect();
+    let dsa_process = DsaProcessRecord::select(tx, standard_id)?.map(Into::into);
+    let consultation = ConsultationRecord::select(tx, standard_id)?.map(Into::into);
+    let guidance = select_external_resources(tx, standard_id, "guidance")?;
+    let use_cases = select_external_resources(tx, standard_id, "use-case")?;
+    let case_studies = select_external_resources(tx, standard_id, "case-study")?;
+
     let extra = MetadataExtra {
         id: record.id.clone(),
         name: record.name.clone(),
         acronym: record.acronym,
         specification: record.specification,
+        topic_id: record.topic_id,
+        licence_id: record.licence_id,
+        related_ids,
         topic,
         licence: licence.map(Into::into),
         maintainer: maintainer.into(),
         related,
         endorsement_state: endorsement_state.clone(),
+        endorsement_history,
+        subjects,
+        conforms_to: record.conforms_to,
+        dsa_process,
+        consultation,
+        guidance,
+        use_cases,
+        case_studies,
     };
     let date = FromStr::from_str(&format!("{}T00:00:00Z", &endorsement_state.start_date))?;
     let metadata = Metadata {
@@ -234,7 +520,28 @@ fn into_resource(tx: &Transaction, record: StandardRecord) -> Result<Standard> {
         content: record.content,
     };
 
-    Ok(standard)
+    Ok(Some(standard))
+}
+
+fn select_external_resources(
+    tx: &Transaction,
+    standard_id: &str,
+    resource_type: &str,
+) -> Result<Vec<ExternalResource>> {
+    let mut result = Vec::new();
+
+    for record in ExternalResourceRecord::select(tx, standard_id, resource_type)? {
+        let publisher = OrganisationRecord::select(tx, &record.publisher_id)?;
+
+        result.push(ExternalResource {
+            title: record.title,
+            url: record.url,
+            publisher_id: record.publisher_id,
+            publisher: publisher.map(Into::into),
+        });
+    }
+
+    Ok(result)
 }
 
 #[cfg(test)]
@@ -301,6 +608,10 @@ template = "standard.html"
 identifier = "vapour"
 name = "Vapour"
 specification = "https://spec.vapour.org/"
+subjects = ["api_access"]
+topic_id = "exchange"
+licence_id = "ogl"
+related_ids = ["steam"]
 
 [[extra.related]]
 id = "steam"
@@ -347,10 +658,42 @@ ordinal: 1
         cache.add(&vapour)?;
         cache.add(&topic)?;
 
-        let actual: Standard = cache.get(&vapour.id())?.unwrap();
+        let actual: Standard = cache.get(vapour.id())?.unwrap();
 
         assert_eq!(&actual.to_string(), zola_page);
 
         Ok(())
     }
+
+    #[test]
+    fn unknown_maintainer() -> Result<()> {
+        let raw = r#"---
+type: standard
+identifier: vapour
+name: Vapour
+topic: exchange
+specification: https://spec.vapour.org/
+maintainer: nobody
+endorsement_state:
+    status: identified
+    start_date: 2021-06-01
+    review_date: 2021-06-01
+---
+This standard will give you no overhead."#;
+        let mut cache = Cache::connect(":memory:")?;
+        let vapour = source::Standard::from_str(raw)?;
+
+        cache.add(&vapour)?;
+        cache.insert_origin("standard", vapour.id(), "standards/vapour.md")?;
+
+        let actual: Option<Standard> = cache.get(vapour.id())?;
+
+        assert!(
+            actual.is_none(),
+            "a standard with an unknown maintainer is skipped"
+        );
+        assert!(get_all(&mut cache)?.is_empty());
+
+        Ok(())
+    }
 }
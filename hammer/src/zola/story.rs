@@ -0,0 +1,349 @@
+//! This module covers story pages, i.e. case studies and use cases, from a Zola point of view.
+use anyhow::Result;
+use chrono::{DateTime, Utc};
+use rusqlite::Transaction;
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::marker::PhantomData;
+use std::str::FromStr;
+
+use super::{Document, Organisation, StandardReference, Url};
+use crate::cache::records::*;
+use crate::cache::Cache;
+use crate::checksum::{Checksum, Digest, Hasher};
+use crate::integrity;
+use crate::markdown;
+use crate::resource::{CaseStudyKind, StoryKind, UseCaseKind};
+
+type StoryId = String;
+
+pub type CaseStudy = Story<CaseStudyKind>;
+pub type UseCase = Story<UseCaseKind>;
+
+#[derive(Debug, Clone)]
+pub struct Story<K: StoryKind> {
+    pub metadata: Metadata,
+    pub content: String,
+    kind: PhantomData<K>,
+}
+
+impl<K: StoryKind> Story<K> {
+    pub fn id(&self) -> &StoryId {
+        &self.metadata.extra.id
+    }
+
+    pub fn checksum(&self) -> Checksum {
+        self.into()
+    }
+
+    pub fn path(&self) -> String {
+        format!("{}.md", self.id())
+    }
+
+    /// The search index entry for the story, published under the given section path.
+    pub fn document(&self, section_path: &str) -> Document {
+        let extra = &self.metadata.extra;
+        let path = format!("{}{}/", section_path, &self.metadata.slug);
+
+        Document {
+            description: extra.description.clone(),
+            organisation: Some(extra.publisher.name.clone()),
+            ..Document::new(
+                &K::RESOURCE_TYPE.to_string(),
+                self.id(),
+                path,
+                &self.metadata.title,
+                markdown::plain_text(&self.content),
+            )
+        }
+    }
+}
+
+impl<K: StoryKind> fmt::Display for Story<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let metadata = toml::to_string(&self.metadata).expect("metadata to serialize as TOML");
+
+        writeln!(f, "+++")?;
+        write!(f, "{}", &metadata)?;
+        writeln!(f, "+++")?;
+        write!(f, "{}", &self.content)
+    }
+}
+
+impl<K: StoryKind> Digest for Story<K> {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.metadata.digest(hasher);
+        self.content.digest(hasher);
+    }
+}
+
+impl<K: StoryKind> From<&Story<K>> for Checksum {
+    fn from(resource: &Story<K>) -> Checksum {
+        let mut hasher = Hasher::new();
+        resource.digest(&mut hasher);
+
+        hasher.finalize()
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Metadata {
+    title: String,
+    date: DateTime<Utc>,
+    slug: String,
+    template: String,
+    extra: MetadataExtra,
+}
+
+impl Digest for Metadata {
+    fn digest(&self, hasher: &mut Hasher) {
+        self.extra.id.digest(hasher);
+        self.title.digest(hasher);
+        self.extra.description.digest(hasher);
+        self.extra.creation_date.digest(hasher);
+        self.extra.update_date.digest(hasher);
+        self.extra.publication_date.digest(hasher);
+        self.extra.publisher.id().digest(hasher);
+        self.extra
+            .standards
+            .iter()
+            .map(|x| x.id.clone())
+            .collect::<Vec<_>>()
+            .digest(hasher);
+        self.extra.canonical_url.digest(hasher);
+    }
+}
+
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct MetadataExtra {
+    /// A local unique identifier for the story.
+    #[serde(rename = "identifier")]
+    pub id: StoryId,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    pub creation_date: String,
+    pub update_date: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub publication_date: Option<String>,
+    /// The URL to the original publication, if any.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub canonical_url: Option<Url>,
+    /// The organisation publishing the story.
+    pub publisher: Organisation,
+    /// The list of standards the story refers to.
+    pub standards: Vec<StandardReference>,
+}
+
+pub fn get_all<K: StoryKind>(cache: &mut Cache) -> Result<Vec<Story<K>>> {
+    let tx = cache.transaction()?;
+    let records = StoryRecord::select_all(&tx, K::TABLE)?;
+    let mut result = Vec::new();
+
+    for record in records {
+        if let Some(resource) = into_resource(&tx, record)? {
+            result.push(resource);
+        }
+    }
+
+    tx.commit()?;
+
+    Ok(result)
+}
+
+fn into_resource<K: StoryKind>(tx: &Transaction, record: StoryRecord) -> Result<Option<Story<K>>> {
+    let publisher = match OrganisationRecord::select(tx, &record.publisher_id)? {
+        Some(publisher) => publisher,
+        None => {
+            integrity::skip_unknown_organisation(
+                tx,
+                &K::RESOURCE_TYPE.to_string(),
+                &record.id,
+                "publisher",
+                &record.publisher_id,
+            )?;
+
+            return Ok(None);
+        }
+    };
+    let mut standards = Vec::new();
+
+    for standard_record in StoryStandardRecord::select(tx, K::TABLE, &record.id)? {
+        if let Some(standard) = StandardRecord::select(tx, &standard_record.standard_id)? {
+            standards.push(standard.into());
+        }
+    }
+
+    let extra = MetadataExtra {
+        id: record.id.clone(),
+        description: record.description,
+        creation_date: record.creation_date.clone(),
+        update_date: record.update_date,
+        publication_date: record.publication_date,
+        canonical_url: record.canonical_url,
+        publisher: publisher.into(),
+        standards,
+    };
+    let date = FromStr::from_str(&format!("{}T00:00:00Z", &record.creation_date))?;
+    let (title, content) = markdown::split_title(&record.content)?;
+    let metadata = Metadata {
+        title,
+        date,
+        slug: record.id,
+        template: format!("{}.html", K::RESOURCE_TYPE),
+        extra,
+    };
+    let resource = Story {
+        metadata,
+        content,
+        kind: PhantomData,
+    };
+
+    Ok(Some(resource))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::resource::Resource;
+    use crate::source;
+
+    #[test]
+    fn case_study_from_source() -> Result<()> {
+        let raw = r#"---
+type: case-study
+identifier: fire-safety-and-food-hygiene
+publisher: geoplace
+creation_date: 2021-04-01
+update_date: 2021-05-14
+standards:
+  - uprn
+---
+# What can food hygiene tell us about fire safety?
+
+Linking food hygiene inspections to fire safety records through the UPRN."#;
+        let uprn_raw = r#"---
+type: standard
+identifier: uprn
+name: Unique Property Reference Number
+topic: geospatial
+specification: https://www.geoplace.co.uk/addresses/uprn
+maintainer: ordnance-survey
+endorsement_state:
+    status: endorsed
+    start_date: 2020-12-16
+    review_date: 2020-12-16
+---
+The Unique Property Reference Number."#;
+        let org_raw = r#"{
+            "id": "geoplace",
+            "name": "Geoplace",
+            "url": "https://www.geoplace.co.uk/"
+        }"#;
+        let zola_page = r#"+++
+title = "What can food hygiene tell us about fire safety?"
+date = "2021-04-01T00:00:00Z"
+slug = "fire-safety-and-food-hygiene"
+template = "case-study.html"
+
+[extra]
+identifier = "fire-safety-and-food-hygiene"
+creation_date = "2021-04-01"
+update_date = "2021-05-14"
+
+[extra.publisher]
+id = "geoplace"
+name = "Geoplace"
+url = "https://www.geoplace.co.uk/"
+
+[[extra.standards]]
+id = "uprn"
+name = "Unique Property Reference Number"
+url = "/standards/uprn"
++++
+Linking food hygiene inspections to fire safety records through the UPRN."#;
+        let mut cache = Cache::connect(":memory:")?;
+        let resource = source::CaseStudy::from_str(raw)?;
+        let uprn = source::Standard::from_str(uprn_raw)?;
+        let org = source::Organisation::from_str(org_raw)?;
+
+        cache.add(&org)?;
+        cache.add(&uprn)?;
+        cache.add(&resource)?;
+
+        let actual: CaseStudy = get_all(&mut cache)?.remove(0);
+
+        assert_eq!(&actual.to_string(), zola_page);
+
+        Ok(())
+    }
+
+    #[test]
+    fn use_case_from_source() -> Result<()> {
+        let raw = r#"---
+type: use-case
+identifier: property-matching
+publisher: geoplace
+creation_date: 2021-04-01
+update_date: 2021-05-14
+standards:
+  - uprn
+---
+# Matching property records across services
+
+Services need to agree on which property a record refers to."#;
+        let uprn_raw = r#"---
+type: standard
+identifier: uprn
+name: Unique Property Reference Number
+topic: geospatial
+specification: https://www.geoplace.co.uk/addresses/uprn
+maintainer: ordnance-survey
+endorsement_state:
+    status: endorsed
+    start_date: 2020-12-16
+    review_date: 2020-12-16
+---
+The Unique Property Reference Number."#;
+        let org_raw = r#"{
+            "id": "geoplace",
+            "name": "Geoplace",
+            "url": "https://www.geoplace.co.uk/"
+        }"#;
+        let zola_page = r#"+++
+title = "Matching property records across services"
+date = "2021-04-01T00:00:00Z"
+slug = "property-matching"
+template = "use-case.html"
+
+[extra]
+identifier = "property-matching"
+creation_date = "2021-04-01"
+update_date = "2021-05-14"
+
+[extra.publisher]
+id = "geoplace"
+name = "Geoplace"
+url = "https://www.geoplace.co.uk/"
+
+[[extra.standards]]
+id = "uprn"
+name = "Unique Property Reference Number"
+url = "/standards/uprn"
++++
+Services need to agree on which property a record refers to."#;
+        let mut cache = Cache::connect(":memory:")?;
+        let resource = source::UseCase::from_str(raw)?;
+        let uprn = source::Standard::from_str(uprn_raw)?;
+        let org = source::Organisation::from_str(org_raw)?;
+
+        cache.add(&org)?;
+        cache.add(&uprn)?;
+        cache.add(&resource)?;
+
+        let actual: UseCase = get_all(&mut cache)?.remove(0);
+
+        assert_eq!(&actual.to_string(), zola_page);
+
+        Ok(())
+    }
+}
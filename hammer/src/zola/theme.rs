@@ -2,13 +2,16 @@
 use anyhow::Result;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
-use super::{StandardId, ThemeId};
+use super::{Document, StandardId, ThemeId};
 use crate::cache::records::*;
 use crate::cache::Cache;
 use crate::checksum::{Checksum, Digest, Hasher};
+use crate::markdown;
 use crate::report;
 use crate::resource::Resource;
+use crate::source::{self, parse_frontmatter};
 
 #[derive(Debug, Clone)]
 pub struct Theme {
@@ -28,6 +31,22 @@ impl Theme {
     pub fn path(&self) -> String {
         format!("{}/", self.id())
     }
+
+    /// The search index entry for the theme, published under the given section path.
+    pub fn document(&self, section_path: &str) -> Document {
+        let path = format!("{}{}/", section_path, &self.metadata.slug);
+
+        Document {
+            theme: Some(self.id().clone()),
+            ..Document::new(
+                "theme",
+                self.id(),
+                path,
+                &self.metadata.title,
+                markdown::plain_text(&self.content),
+            )
+        }
+    }
 }
 
 impl fmt::Display for Theme {
@@ -41,6 +60,19 @@ impl fmt::Display for Theme {
     }
 }
 
+impl FromStr for Theme {
+    type Err = anyhow::Error;
+
+    fn from_str(blob: &str) -> Result<Self, Self::Err> {
+        let (metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
+
+        Ok(Self {
+            metadata,
+            content: content.into(),
+        })
+    }
+}
+
 impl Digest for Theme {
     fn digest(&self, hasher: &mut Hasher) {
         self.metadata.digest(hasher);
@@ -57,6 +89,21 @@ impl From<&Theme> for Checksum {
     }
 }
 
+impl From<&Theme> for source::Theme {
+    fn from(resource: &Theme) -> Self {
+        let metadata = source::theme::Metadata {
+            id: resource.id().clone(),
+            name: resource.metadata.title.clone(),
+            ordinal: resource.metadata.extra.ordinal,
+        };
+
+        source::Theme {
+            metadata,
+            content: resource.content.clone(),
+        }
+    }
+}
+
 #[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Metadata {
     title: String,
@@ -90,8 +137,7 @@ impl Resource<Theme> for Cache {
             result = Some(into_resource(record)?);
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Theme, id, "");
 
         tx.commit()?;
@@ -127,7 +173,7 @@ pub fn get_all(cache: &mut Cache) -> Result<Vec<Theme>> {
 fn into_resource(record: ThemeRecord) -> Result<Theme> {
     let extra = MetadataExtra {
         id: record.id.clone(),
-        ordinal: record.ordinal.clone(),
+        ordinal: record.ordinal,
     };
     let metadata = Metadata {
         title: record.name,
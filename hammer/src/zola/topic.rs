@@ -3,13 +3,16 @@ use anyhow::Result;
 use rusqlite::Transaction;
 use serde::{Deserialize, Serialize};
 use std::fmt;
+use std::str::FromStr;
 
-use super::{StandardId, TopicId};
+use super::{Document, StandardId, TopicId};
 use crate::cache::records::*;
 use crate::cache::Cache;
 use crate::checksum::{Checksum, Digest, Hasher};
+use crate::markdown;
 use crate::report;
 use crate::resource::Resource;
+use crate::source::{self, parse_frontmatter};
 
 #[derive(Debug, Clone)]
 pub struct Topic {
@@ -29,6 +32,39 @@ impl Topic {
     pub fn path(&self) -> String {
         format!("{}.md", self.id())
     }
+
+    /// Recovers the source topic from its page. The page doesn't carry the theme, which is the section it is
+    /// published under.
+    pub fn to_source(&self, theme_id: &str) -> source::Topic {
+        let metadata = source::topic::Metadata {
+            id: self.id().clone(),
+            name: self.metadata.title.clone(),
+            theme: theme_id.into(),
+            ordinal: self.metadata.extra.ordinal,
+        };
+
+        source::Topic {
+            metadata,
+            content: self.content.clone(),
+        }
+    }
+
+    /// The search index entry for the topic, published under the given theme path.
+    pub fn document(&self, theme_path: &str, theme_id: &str) -> Document {
+        let path = format!("{}{}/", theme_path, &self.metadata.slug);
+
+        Document {
+            topic: Some(self.id().clone()),
+            theme: Some(theme_id.into()),
+            ..Document::new(
+                "topic",
+                self.id(),
+                path,
+                &self.metadata.title,
+                markdown::plain_text(&self.content),
+            )
+        }
+    }
 }
 
 impl fmt::Display for Topic {
@@ -42,6 +78,19 @@ impl fmt::Display for Topic {
     }
 }
 
+impl FromStr for Topic {
+    type Err = anyhow::Error;
+
+    fn from_str(blob: &str) -> Result<Self, Self::Err> {
+        let (metadata, content): (Metadata, &str) = parse_frontmatter(blob)?;
+
+        Ok(Self {
+            metadata,
+            content: content.into(),
+        })
+    }
+}
+
 impl Digest for Topic {
     fn digest(&self, hasher: &mut Hasher) {
         self.metadata.digest(hasher);
@@ -126,8 +175,7 @@ impl Resource<Topic> for Cache {
             result = Some(into_resource(&tx, record)?);
         }
 
-        &self
-            .report
+        self.report
             .log(report::Action::Get, report::Entity::Topic, id, "");
 
         tx.commit()?;
@@ -164,7 +212,7 @@ fn into_resource(tx: &Transaction, record: TopicRecord) -> Result<Topic> {
     let standards = TopicStandardRecord::select(tx, &record.id)?;
     let extra = MetadataExtra {
         id: record.id.clone(),
-        ordinal: record.ordinal.clone(),
+        ordinal: record.ordinal,
         standards: standards.into_iter().map(|r| r.into()).collect(),
     };
     let metadata = Metadata {